@@ -0,0 +1,552 @@
+// AI 工具注册表：内置工具 + ~/.cobalt/ai-tools.json 中的自定义工具
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::skill_adapters::SkillFormat;
+use super::stats::get_user_shell;
+
+const AI_TOOLS_CONFIG_FILE_NAME: &str = "ai-tools.json";
+
+/// 获取 Cobalt 配置目录路径
+fn get_cobalt_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".cobalt"))
+        .ok_or_else(|| "无法获取用户主目录".to_string())
+}
+
+fn get_ai_tools_config_path() -> Result<PathBuf, String> {
+    Ok(get_cobalt_dir()?.join(AI_TOOLS_CONFIG_FILE_NAME))
+}
+
+/// AI 工具定义
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AiToolDefinition {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default = "default_tool_icon")]
+    pub icon: String,
+    /// 全局 skills 目录：相对用户主目录，也支持 ~/ 开头或绝对路径
+    pub global_path: String,
+    /// 工作区 skills 目录：相对工作区根目录，为空时与 global_path 相同
+    #[serde(default)]
+    pub workspace_path: Option<String>,
+    /// 命令行可执行文件名，用于检测工具是否安装
+    #[serde(default)]
+    pub binary: Option<String>,
+    /// 配置目录：相对用户主目录，为空时取 global_path 的上级目录
+    #[serde(default)]
+    pub config_dir: Option<String>,
+    /// Skill 格式：claude（默认）| cursor-mdc | agents-md
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+fn default_tool_icon() -> String {
+    "🧩".to_string()
+}
+
+impl AiToolDefinition {
+    fn builtin(id: &str, display_name: &str, icon: &str, path: &str, binary: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+            icon: icon.to_string(),
+            global_path: path.to_string(),
+            workspace_path: None,
+            binary: Some(binary.to_string()),
+            config_dir: None,
+            format: None,
+        }
+    }
+
+    fn with_format(mut self, format: SkillFormat) -> Self {
+        self.format = Some(format.as_str().to_string());
+        self
+    }
+
+    fn with_config_dir(mut self, config_dir: &str) -> Self {
+        self.config_dir = Some(config_dir.to_string());
+        self
+    }
+
+    fn workspace_relative_path(&self) -> &str {
+        self.workspace_path
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())
+            .unwrap_or(&self.global_path)
+    }
+
+    /// 全局 skills 目录
+    pub fn global_dir(&self) -> Option<PathBuf> {
+        let home = dirs::home_dir()?;
+        let path = self.global_path.trim();
+        if let Some(rest) = path.strip_prefix("~/") {
+            Some(join_relative(&home, rest))
+        } else if Path::new(path).is_absolute() {
+            Some(PathBuf::from(path))
+        } else {
+            Some(join_relative(&home, path))
+        }
+    }
+
+    /// 配置目录，用于判断工具是否在本机使用过
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        if let Some(config_dir) = self.config_dir.as_deref().filter(|p| !p.trim().is_empty()) {
+            let home = dirs::home_dir()?;
+            let path = config_dir.trim();
+            return Some(match path.strip_prefix("~/") {
+                Some(rest) => join_relative(&home, rest),
+                None if Path::new(path).is_absolute() => PathBuf::from(path),
+                None => join_relative(&home, path),
+            });
+        }
+        self.global_dir()?.parent().map(Path::to_path_buf)
+    }
+
+    fn binary_name(&self) -> Option<&str> {
+        self.binary
+            .as_deref()
+            .map(str::trim)
+            .filter(|b| is_valid_binary_name(b))
+    }
+
+    /// 工作区 skills 目录
+    pub fn workspace_dir(&self, workspace_path: &Path) -> PathBuf {
+        join_relative(workspace_path, self.workspace_relative_path())
+    }
+}
+
+/// 按 / 拆分相对路径并拼接，保证各平台分隔符一致
+fn join_relative(base: &Path, relative: &str) -> PathBuf {
+    relative
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty())
+        .fold(base.to_path_buf(), |path, part| path.join(part))
+}
+
+/// 自定义工具配置文件
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct AiToolsConfig {
+    #[serde(default)]
+    tools: Vec<AiToolDefinition>,
+}
+
+/// 内置 AI 工具（claude-code 必须排在第一位，作为默认安装目标）
+fn builtin_ai_tools() -> Vec<AiToolDefinition> {
+    vec![
+        AiToolDefinition::builtin("claude-code", "Claude Code", "🤖", ".claude/skills", "claude"),
+        AiToolDefinition::builtin("cursor", "Cursor", "⚡", ".cursor/skills", "cursor").with_format(SkillFormat::CursorMdc),
+        AiToolDefinition::builtin("codex", "Codex", "🔮", ".codex/skills", "codex").with_format(SkillFormat::AgentsMd),
+        AiToolDefinition::builtin("opencode", "OpenCode", "🌟", ".config/opencode/skills", "opencode"),
+        AiToolDefinition::builtin("antigravity", "Antigravity", "🚀", ".gemini/antigravity/global_skills", "antigravity"),
+        AiToolDefinition::builtin("droid", "Droid", "🦾", ".factory/skills", "droid"),
+        // .agents 是多个工具共用的目录，不能作为 Copilot 的安装依据
+        AiToolDefinition::builtin("copilot", "Copilot", "🐙", ".agents/skills", "copilot")
+            .with_config_dir(".copilot")
+            .with_format(SkillFormat::AgentsMd),
+    ]
+}
+
+fn is_builtin_tool(tool_id: &str) -> bool {
+    builtin_ai_tools().iter().any(|tool| tool.id == tool_id)
+}
+
+fn read_ai_tools_config() -> Result<AiToolsConfig, String> {
+    let config_path = get_ai_tools_config_path()?;
+    if !config_path.exists() {
+        return Ok(AiToolsConfig::default());
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("读取 ai-tools.json 失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析 ai-tools.json 失败: {}", e))
+}
+
+fn write_ai_tools_config(config: &AiToolsConfig) -> Result<(), String> {
+    let config_path = get_ai_tools_config_path()?;
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("序列化 ai-tools.json 失败: {}", e))?;
+    fs::write(&config_path, content).map_err(|e| format!("写入 ai-tools.json 失败: {}", e))
+}
+
+/// 加载全部 AI 工具：内置工具在前，自定义工具可覆盖同 id 的内置工具
+/// 配置文件损坏时只使用内置工具，避免影响其他功能
+pub fn load_ai_tools() -> Vec<AiToolDefinition> {
+    let mut tools = builtin_ai_tools();
+
+    let custom_tools = match read_ai_tools_config() {
+        Ok(config) => config.tools,
+        Err(e) => {
+            eprintln!("⚠️  [Backend] {}，仅使用内置 AI 工具", e);
+            Vec::new()
+        }
+    };
+
+    for custom in custom_tools {
+        if validate_ai_tool(&custom).is_err() {
+            eprintln!("⚠️  [Backend] 忽略无效的自定义 AI 工具: {}", custom.id);
+            continue;
+        }
+        if let Some(existing) = tools.iter_mut().find(|tool| tool.id == custom.id) {
+            *existing = custom;
+        } else {
+            tools.push(custom);
+        }
+    }
+
+    tools
+}
+
+fn validate_ai_tool(tool: &AiToolDefinition) -> Result<(), String> {
+    let valid_id = !tool.id.is_empty()
+        && tool
+            .id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid_id {
+        return Err("工具 ID 只能包含小写字母、数字、- 和 _".to_string());
+    }
+    if tool.display_name.trim().is_empty() {
+        return Err("工具名称不能为空".to_string());
+    }
+    if tool.global_path.trim().is_empty() {
+        return Err("全局 skills 目录不能为空".to_string());
+    }
+
+    let workspace_path = tool.workspace_relative_path();
+    if Path::new(workspace_path).is_absolute() || workspace_path.starts_with('~') {
+        return Err("工作区 skills 目录必须是相对路径".to_string());
+    }
+    if workspace_path.split(['/', '\\']).any(|part| part == "..") {
+        return Err("工作区 skills 目录不能包含 ..".to_string());
+    }
+    if let Some(binary) = tool.binary.as_deref().filter(|b| !b.trim().is_empty()) {
+        if !is_valid_binary_name(binary.trim()) {
+            return Err("可执行文件名只能包含字母、数字、.、- 和 _".to_string());
+        }
+    }
+    SkillFormat::parse(tool.format.as_deref())?;
+
+    Ok(())
+}
+
+/// 可执行文件名只允许安全字符，避免拼接进 shell 脚本时被注入
+fn is_valid_binary_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+}
+
+/// AI 工具信息
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiToolInfo {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+    pub icon: String,
+    pub relative_path: String,  // 相对路径，如 ".claude/skills/"
+    pub workspace_relative_path: String,
+    /// 是否为内置工具（自定义覆盖的内置工具仍视为内置）
+    pub builtin: bool,
+    /// 应用 Skill 时使用的格式
+    pub format: String,
+    /// 本机是否安装了该工具（找到可执行文件或配置目录）
+    pub installed: bool,
+    pub config_dir_exists: bool,
+    pub binary_path: Option<String>,
+    pub version: Option<String>,
+}
+
+impl From<&AiToolDefinition> for AiToolInfo {
+    fn from(tool: &AiToolDefinition) -> Self {
+        Self {
+            id: tool.id.clone(),
+            name: tool.id.clone(),
+            display_name: tool.display_name.clone(),
+            icon: tool.icon.clone(),
+            relative_path: format!("{}/", tool.global_path.trim_end_matches('/')),
+            workspace_relative_path: format!("{}/", tool.workspace_relative_path().trim_end_matches('/')),
+            builtin: is_builtin_tool(&tool.id),
+            format: SkillFormat::parse(tool.format.as_deref())
+                .unwrap_or(SkillFormat::Claude)
+                .as_str()
+                .to_string(),
+            installed: false,
+            config_dir_exists: false,
+            binary_path: None,
+            version: None,
+        }
+    }
+}
+
+/// 命令行检测结果：可执行文件路径和版本号
+#[derive(Clone)]
+struct BinaryDetection {
+    path: String,
+    version: Option<String>,
+}
+
+/// login shell 查找可执行文件的超时时间（shell 配置文件可能很慢）
+const BINARY_LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+/// 单个可执行文件 --version 的超时时间
+const BINARY_VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+/// 检测结果的缓存时间
+const BINARY_DETECTION_TTL: Duration = Duration::from_secs(300);
+
+type BinaryDetectionCache = Mutex<HashMap<String, (Instant, Option<BinaryDetection>)>>;
+
+fn binary_detection_cache() -> &'static BinaryDetectionCache {
+    static CACHE: OnceLock<BinaryDetectionCache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 执行命令，超时后结束进程并返回 None
+fn output_with_timeout(cmd: &mut Command, timeout: Duration) -> std::io::Result<Option<Output>> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // 在单独的线程中读取输出，避免管道写满后进程阻塞
+    let mut stdout_pipe = child.stdout.take();
+    let stdout_reader = std::thread::spawn(move || {
+        let mut stdout = Vec::new();
+        if let Some(pipe) = stdout_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut stdout);
+        }
+        stdout
+    });
+    let mut stderr_pipe = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut stderr = Vec::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut stderr);
+        }
+        stderr
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            // 子进程启动的后台进程可能仍持有管道，不等待读取线程
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    Ok(Some(Output { status, stdout, stderr }))
+}
+
+/// 在一个 login shell 中查找所有可执行文件，确保加载用户完整的 PATH 配置
+/// 返回（可执行文件路径，login shell 的 PATH）
+fn lookup_binary_paths(binaries: &[&str]) -> (HashMap<String, String>, Option<String>) {
+    let mut paths = HashMap::new();
+    // 第一行输出 login shell 的 PATH（名称为空），之后每行为找到的可执行文件
+    let mut lines = vec!["printf '\\t%s\\n' \"$PATH\"".to_string()];
+    lines.extend(binaries.iter().map(|bin| {
+        format!(
+            "if p=$(command -v {bin} 2>/dev/null); then printf '%s\\t%s\\n' {bin} \"$p\"; fi",
+            bin = bin
+        )
+    }));
+    let script = lines.join("\n");
+
+    let mut cmd = Command::new(get_user_shell());
+    cmd.args(["-l", "-c", &script]);
+    let output = match output_with_timeout(&mut cmd, BINARY_LOOKUP_TIMEOUT) {
+        Ok(Some(output)) => output,
+        Ok(None) => {
+            eprintln!("⚠️  [Backend] 检测 AI 工具可执行文件超时");
+            return (paths, None);
+        }
+        Err(e) => {
+            eprintln!("⚠️  [Backend] 检测 AI 工具可执行文件失败: {}", e);
+            return (paths, None);
+        }
+    };
+
+    let mut shell_path = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((bin, path)) = line.split_once('\t') else {
+            continue;
+        };
+        if bin.is_empty() {
+            shell_path = Some(path.to_string()).filter(|p| !p.is_empty());
+        } else if path.starts_with('/') {
+            // zsh 的 command -v 对 alias/函数会返回非路径内容，只认绝对路径
+            paths.insert(bin.to_string(), path.to_string());
+        }
+    }
+    (paths, shell_path)
+}
+
+/// 读取可执行文件的版本号（--version 输出的第一行）
+/// 使用 login shell 的 PATH，脚本形式的工具（#!/usr/bin/env node 等）才能找到解释器
+fn read_binary_version(path: &str, shell_path: Option<&str>) -> Option<String> {
+    let mut cmd = Command::new(path);
+    cmd.arg("--version");
+    if let Some(shell_path) = shell_path {
+        cmd.env("PATH", shell_path);
+    }
+    match output_with_timeout(&mut cmd, BINARY_VERSION_TIMEOUT) {
+        Ok(Some(output)) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()),
+        Ok(None) => {
+            eprintln!("⚠️  [Backend] {} --version 超时", path);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// 检测可执行文件路径和版本号；结果缓存一段时间，版本号并行读取且各自有超时
+fn detect_binaries(binaries: &[&str]) -> HashMap<String, BinaryDetection> {
+    let mut detected = HashMap::new();
+    let mut missing = Vec::new();
+    {
+        let cache = binary_detection_cache().lock().unwrap_or_else(|e| e.into_inner());
+        for bin in binaries {
+            match cache.get(*bin) {
+                Some((checked_at, detection)) if checked_at.elapsed() < BINARY_DETECTION_TTL => {
+                    if let Some(detection) = detection {
+                        detected.insert(bin.to_string(), detection.clone());
+                    }
+                }
+                _ => missing.push(*bin),
+            }
+        }
+    }
+    if missing.is_empty() {
+        return detected;
+    }
+
+    let (paths, shell_path) = lookup_binary_paths(&missing);
+    let shell_path = shell_path.as_deref();
+    let versions: Vec<(String, BinaryDetection)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .iter()
+            .map(|(bin, path)| scope.spawn(move || (bin.clone(), read_binary_version(path, shell_path))))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok())
+            .map(|(bin, version)| {
+                let path = paths[&bin].clone();
+                (bin, BinaryDetection { path, version })
+            })
+            .collect()
+    });
+
+    let now = Instant::now();
+    let mut cache = binary_detection_cache().lock().unwrap_or_else(|e| e.into_inner());
+    for bin in missing {
+        cache.insert(bin.to_string(), (now, None));
+    }
+    for (bin, detection) in versions {
+        cache.insert(bin.clone(), (now, Some(detection.clone())));
+        detected.insert(bin, detection);
+    }
+
+    detected
+}
+
+/// 检测工具是否安装，并生成工具信息
+fn detect_ai_tools(tools: &[AiToolDefinition]) -> Vec<AiToolInfo> {
+    let binaries: Vec<&str> = tools.iter().filter_map(|tool| tool.binary_name()).collect();
+    let detected = detect_binaries(&binaries);
+
+    tools
+        .iter()
+        .map(|tool| {
+            let mut info = AiToolInfo::from(tool);
+            info.config_dir_exists = tool.config_dir().map(|dir| dir.is_dir()).unwrap_or(false);
+            if let Some(binary) = tool.binary_name().and_then(|bin| detected.get(bin)) {
+                info.binary_path = Some(binary.path.clone());
+                info.version = binary.version.clone();
+            }
+            info.installed = info.config_dir_exists || info.binary_path.is_some();
+            info
+        })
+        .collect()
+}
+
+/// 获取所有支持的 AI 工具信息（包含本机安装检测结果）
+#[tauri::command]
+pub async fn get_supported_ai_tools() -> Vec<AiToolInfo> {
+    let tools = load_ai_tools();
+    // 检测会执行外部命令，不能阻塞异步运行时
+    let infos = match tauri::async_runtime::spawn_blocking({
+        let tools = tools.clone();
+        move || detect_ai_tools(&tools)
+    })
+    .await
+    {
+        Ok(infos) => infos,
+        Err(e) => {
+            eprintln!("⚠️  [Backend] 检测 AI 工具失败: {}", e);
+            tools.iter().map(AiToolInfo::from).collect()
+        }
+    };
+    let installed: Vec<&str> = infos.iter().filter(|t| t.installed).map(|t| t.id.as_str()).collect();
+    println!("🔍 [Backend] 检测到已安装的 AI 工具: {}", installed.join(", "));
+    infos
+}
+
+/// 新增或更新自定义 AI 工具
+#[tauri::command]
+pub fn save_custom_ai_tool(tool: AiToolDefinition) -> Result<AiToolInfo, String> {
+    validate_ai_tool(&tool)?;
+
+    let mut config = read_ai_tools_config()?;
+    if let Some(existing) = config.tools.iter_mut().find(|t| t.id == tool.id) {
+        *existing = tool.clone();
+    } else {
+        config.tools.push(tool.clone());
+    }
+    write_ai_tools_config(&config)?;
+
+    println!("✅ [Backend] 已保存自定义 AI 工具: {}", tool.id);
+    Ok(detect_ai_tools(std::slice::from_ref(&tool)).remove(0))
+}
+
+/// 删除自定义 AI 工具（内置工具会恢复默认配置）
+#[tauri::command]
+pub fn remove_custom_ai_tool(tool_id: String) -> Result<(), String> {
+    let mut config = read_ai_tools_config()?;
+    let original_len = config.tools.len();
+    config.tools.retain(|t| t.id != tool_id);
+
+    if config.tools.len() == original_len {
+        return Err(format!("自定义 AI 工具 {} 不存在", tool_id));
+    }
+
+    write_ai_tools_config(&config)?;
+    println!("✅ [Backend] 已删除自定义 AI 工具: {}", tool_id);
+    Ok(())
+}
@@ -19,6 +19,7 @@ pub struct CacheInfo {
     pub conversations: CacheItem,
     pub backups: CacheItem,
     pub marketplace: CacheItem,
+    pub git: CacheItem,
 }
 
 /// 清理结果
@@ -108,10 +109,12 @@ pub fn get_cache_info() -> Result<CacheInfo, String> {
     let conversations_path = claude_dir.join("projects");
     let backups_path = claude_dir.join("backups");
     let marketplace_path = cobalt_dir.join(".cache").join("marketplace");
+    let git_path = super::git_cache::git_cache_dir()?;
 
     let (conv_size, conv_count) = calc_dir_size(&conversations_path);
     let (backup_size, backup_count) = calc_dir_size(&backups_path);
     let (market_size, market_count) = calc_dir_size(&marketplace_path);
+    let (git_size, git_count) = calc_dir_size(&git_path);
 
     Ok(CacheInfo {
         conversations: CacheItem {
@@ -129,6 +132,11 @@ pub fn get_cache_info() -> Result<CacheInfo, String> {
             file_count: market_count,
             path: marketplace_path.to_string_lossy().to_string(),
         },
+        git: CacheItem {
+            size_bytes: git_size,
+            file_count: git_count,
+            path: git_path.to_string_lossy().to_string(),
+        },
     })
 }
 
@@ -175,7 +183,7 @@ pub fn clear_cache(cache_type: String) -> Result<ClearCacheResult, String> {
     let mut total_files: u64 = 0;
 
     let types: Vec<&str> = if cache_type == "all" {
-        vec!["conversations", "backups", "marketplace"]
+        vec!["conversations", "backups", "marketplace", "git"]
     } else {
         vec![cache_type.as_str()]
     };
@@ -185,6 +193,7 @@ pub fn clear_cache(cache_type: String) -> Result<ClearCacheResult, String> {
             "conversations" => claude_dir.join("projects"),
             "backups" => claude_dir.join("backups"),
             "marketplace" => cobalt_dir.join(".cache").join("marketplace"),
+            "git" => super::git_cache::git_cache_dir()?,
             _ => return Err(format!("未知的缓存类型: {}", t)),
         };
 
@@ -0,0 +1,143 @@
+// Git 仓库缓存：每个远程仓库在 ~/.cobalt/git-cache 下保留一份裸镜像
+// 完整克隆从镜像本地克隆，镜像之后只做增量 fetch；浅克隆和部分克隆不经过镜像
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use sha2::{Digest, Sha256};
+
+use super::skills::{
+    build_auth_required_error, clone_from_remote, is_auth_related_error, parse_git_clone_error, run_git, CloneKind,
+    GitAuthInput,
+};
+
+/// Git 缓存目录
+pub fn git_cache_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".cobalt").join("git-cache"))
+        .ok_or_else(|| "无法获取用户主目录".to_string())
+}
+
+/// 只缓存网络仓库；本地路径直接克隆
+pub fn is_cacheable_url(url: &str) -> bool {
+    let url = url.trim();
+    ["https://", "http://", "ssh://", "git://", "git@"]
+        .iter()
+        .any(|prefix| url.starts_with(prefix))
+}
+
+/// 镜像目录名：仓库名加 URL 哈希，末尾的 / 和 .git 不影响结果
+fn mirror_dir_name(url: &str) -> String {
+    let normalized = url.trim().trim_end_matches('/').trim_end_matches(".git");
+    let repo_name: String = normalized
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+
+    let mut hasher = Sha256::new();
+    hasher.update(normalized.as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+    format!("{}-{}", repo_name, &hash[..16])
+}
+
+/// 同一镜像同时只允许一个命令同步
+fn mirror_lock(name: &str) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    locks.entry(name.to_string()).or_default().clone()
+}
+
+/// 同步仓库镜像并返回镜像目录：已有镜像时增量 fetch，否则创建镜像
+pub fn sync_mirror(url: &str, auth: Option<&GitAuthInput>) -> Result<PathBuf, String> {
+    let name = mirror_dir_name(url);
+    let mirror_dir = git_cache_dir()?.join(&name);
+    let lock = mirror_lock(&name);
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+
+    if mirror_dir.exists() {
+        fetch_mirror(url, &mirror_dir, auth)?;
+        // 远程默认分支被删除或改名后镜像的 HEAD 会失效，重新创建镜像
+        if run_git(Some(&mirror_dir), &["rev-parse", "--verify", "--quiet", "HEAD"], None)?
+            .status
+            .success()
+        {
+            return Ok(mirror_dir);
+        }
+        println!("🔄 [Backend] 镜像的默认分支已失效，重新创建: {}", url);
+        fs::remove_dir_all(&mirror_dir).map_err(|e| format!("删除失效的镜像失败: {}", e))?;
+    }
+
+    // 先克隆到临时目录再改名，避免中断后留下不完整的镜像
+    let staging_dir = git_cache_dir()?.join(format!("{}.tmp-{}", name, std::process::id()));
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir).map_err(|e| format!("删除临时镜像失败: {}", e))?;
+    }
+    fs::create_dir_all(git_cache_dir()?).map_err(|e| format!("创建 Git 缓存目录失败: {}", e))?;
+
+    println!("📥 [Backend] 创建仓库镜像: {}", url);
+    let staging = staging_dir.to_str().ok_or_else(|| "缓存目录路径无效".to_string())?;
+    if let Err(e) = clone_from_remote(url, staging, false, None, auth, CloneKind::Mirror) {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+    fs::rename(&staging_dir, &mirror_dir).map_err(|e| {
+        let _ = fs::remove_dir_all(&staging_dir);
+        format!("保存仓库镜像失败: {}", e)
+    })?;
+
+    Ok(mirror_dir)
+}
+
+fn fetch_mirror(url: &str, mirror_dir: &Path, auth: Option<&GitAuthInput>) -> Result<(), String> {
+    println!("🔄 [Backend] 更新仓库镜像: {}", url);
+    super::progress::phase(super::progress::ProgressPhase::Clone, "更新仓库镜像");
+    let output = run_git(Some(mirror_dir), &["fetch", "--prune", "--quiet", "origin"], auth)?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if is_auth_related_error(&stderr) {
+        return Err(match auth {
+            Some(_) => "认证失败，请检查凭据后重试".to_string(),
+            None => build_auth_required_error(url, &stderr),
+        });
+    }
+    Err(format!("更新仓库镜像失败: {}", stderr.trim()))
+}
+
+/// 从镜像克隆到工作目录；本地克隆使用硬链接，不需要网络
+pub fn clone_from_mirror(
+    mirror_dir: &Path,
+    target_dir: &str,
+    branch: Option<&str>,
+    kind: CloneKind,
+) -> Result<(), String> {
+    let mirror = mirror_dir.to_str().ok_or_else(|| "缓存目录路径无效".to_string())?;
+    super::progress::phase(super::progress::ProgressPhase::Clone, "从缓存检出");
+    let mut args = vec!["clone", "--quiet"];
+    // 镜像中已有全部对象，稀疏检出只需要跳过检出
+    if kind == CloneKind::Blobless {
+        args.push("--no-checkout");
+    }
+    if let Some(branch) = branch {
+        args.push("--branch");
+        args.push(branch);
+    }
+    args.push(mirror);
+    args.push(target_dir);
+
+    let output = run_git(None, &args, None)?;
+    if !output.status.success() {
+        return Err(parse_git_clone_error(&String::from_utf8_lossy(&output.stderr)));
+    }
+    println!("✅ [Backend] 已从缓存克隆");
+    Ok(())
+}
@@ -23,38 +23,15 @@ fn get_skills_dir() -> Result<PathBuf, String> {
     Ok(get_claude_dir()?.join("skills"))
 }
 
-fn get_workspace_tool_skill_dirs(workspace_path: &str) -> Vec<PathBuf> {
-    let ws_root = PathBuf::from(workspace_path);
-    let mut dirs = Vec::new();
-    for (_, parts) in super::skills::get_tool_relative_paths() {
-        let mut path = ws_root.clone();
-        for part in parts {
-            path = path.join(part);
-        }
-        dirs.push(path);
-    }
-    dirs
-}
-
-fn get_global_tool_skill_dirs() -> Result<Vec<PathBuf>, String> {
-    let home = dirs::home_dir().ok_or_else(|| "无法获取用户主目录".to_string())?;
-    let mut dirs = Vec::new();
-    for (_, parts) in super::skills::get_tool_relative_paths() {
-        let mut path = home.clone();
-        for part in parts {
-            path = path.join(part);
-        }
-        dirs.push(path);
-    }
-    Ok(dirs)
-}
-
 fn find_installed_skill_path(skill_name: &str, workspace_path: Option<&str>) -> Result<Option<PathBuf>, String> {
-    let tool_skill_dirs = if let Some(ws_path) = workspace_path {
-        get_workspace_tool_skill_dirs(ws_path)
+    let tool_skill_dirs: Vec<PathBuf> = if let Some(ws_path) = workspace_path {
+        super::skills::get_all_tool_workspace_skills_dirs(&PathBuf::from(ws_path))
     } else {
-        get_global_tool_skill_dirs()?
-    };
+        super::skills::get_all_tool_skills_dirs()
+    }
+    .into_iter()
+    .map(|(_, dir)| dir)
+    .collect();
 
     if let Some(path) = tool_skill_dirs
         .iter()
@@ -226,6 +203,24 @@ pub struct CachedSkillInfo {
     #[serde(default)]
     pub target_tools: Vec<String>,
     #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub user_invocable: Option<bool>,
+    #[serde(default)]
+    pub disable_model_invocation: Option<bool>,
+    #[serde(default)]
+    pub argument_hint: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    /// 依赖的其他 Skill
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<super::skill_deps::SkillRequirement>,
+    /// 未识别的 frontmatter 字段
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra_frontmatter: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
     pub installed: bool,
     #[serde(default)]
     pub installed_version: Option<String>,
@@ -444,13 +439,17 @@ pub fn update_marketplace(
 }
 
 /// 刷新市场源（扫描并缓存 Skills）
+/// operation_id: 前端生成的操作 ID，提供时通过 operation-progress 事件推送克隆和扫描进度
 #[tauri::command]
 pub async fn refresh_marketplace(
     source_id: String,
     git_auth: Option<super::skills::GitAuthInput>,
+    operation_id: Option<String>,
 ) -> Result<MarketplaceCache, String> {
     use chrono::Utc;
 
+    let _progress = super::progress::ProgressScope::begin(operation_id);
+
     let mut config = read_marketplace_config()?;
     let source = config
         .sources
@@ -492,6 +491,7 @@ pub async fn refresh_marketplace(
     )?;
 
     println!("✅ 仓库克隆成功");
+    super::progress::phase(super::progress::ProgressPhase::Scan, "扫描 skills");
 
     // 检查是否有 skills 子目录
     let skills_subdir = temp_dir.join("skills");
@@ -594,6 +594,14 @@ fn scan_marketplace_skills(source_dir: &PathBuf) -> Result<Vec<CachedSkillInfo>,
             version: current_version,
             tags: metadata.as_ref().map(|m| m.tags.clone()).unwrap_or_default(),
             target_tools: metadata.as_ref().map(|m| m.target_tools.clone()).unwrap_or_default(),
+            allowed_tools: metadata.as_ref().map(|m| m.allowed_tools.clone()).unwrap_or_default(),
+            user_invocable: metadata.as_ref().and_then(|m| m.user_invocable),
+            disable_model_invocation: metadata.as_ref().and_then(|m| m.disable_model_invocation),
+            argument_hint: metadata.as_ref().and_then(|m| m.argument_hint.clone()),
+            model: metadata.as_ref().and_then(|m| m.model.clone()),
+            license: metadata.as_ref().and_then(|m| m.license.clone()),
+            requires: metadata.as_ref().map(|m| m.requires.clone()).unwrap_or_default(),
+            extra_frontmatter: metadata.as_ref().map(|m| m.extra_frontmatter.clone()).unwrap_or_default(),
             installed,
             installed_version,
             has_update,
@@ -648,6 +656,14 @@ fn scan_marketplace_skills(source_dir: &PathBuf) -> Result<Vec<CachedSkillInfo>,
                         version: current_version,
                         tags: metadata.as_ref().map(|m| m.tags.clone()).unwrap_or_default(),
                         target_tools: metadata.as_ref().map(|m| m.target_tools.clone()).unwrap_or_default(),
+                        allowed_tools: metadata.as_ref().map(|m| m.allowed_tools.clone()).unwrap_or_default(),
+                        user_invocable: metadata.as_ref().and_then(|m| m.user_invocable),
+                        disable_model_invocation: metadata.as_ref().and_then(|m| m.disable_model_invocation),
+                        argument_hint: metadata.as_ref().and_then(|m| m.argument_hint.clone()),
+                        model: metadata.as_ref().and_then(|m| m.model.clone()),
+                        license: metadata.as_ref().and_then(|m| m.license.clone()),
+                        requires: metadata.as_ref().map(|m| m.requires.clone()).unwrap_or_default(),
+                        extra_frontmatter: metadata.as_ref().map(|m| m.extra_frontmatter.clone()).unwrap_or_default(),
                         installed,
                         installed_version,
                         has_update,
@@ -670,7 +686,7 @@ pub async fn refresh_all_marketplace() -> Result<Vec<MarketplaceCache>, String>
     let mut caches = Vec::new();
 
     for source in config.sources.iter().filter(|s| s.enabled) {
-        match refresh_marketplace(source.id.clone(), None).await {
+        match refresh_marketplace(source.id.clone(), None, None).await {
             Ok(cache) => caches.push(cache),
             Err(e) => {
                 eprintln!("刷新市场源 {} 失败: {}", source.name, e);
@@ -712,15 +728,31 @@ pub fn get_marketplace_skills(source_id: String, workspace_path: Option<String>)
 }
 
 /// 从市场源安装 Skills
+/// git_ref: 可选的分支、标签或提交，为空时使用默认分支
+/// install_mode: copy（默认）| link，仅对新安装的 Skill 生效
+/// security_threshold: 脚本安全扫描达到该级别时拒绝安装或更新
+/// collisions: 已安装的同名 Skill 来自其他来源时的处理方式，未指定时返回 COBALT_SKILL_CONFLICT 错误
+/// operation_id: 前端生成的操作 ID，提供时通过 operation-progress 事件推送进度
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn install_skill_from_marketplace(
     source_id: String,
     skill_names: Vec<String>,
+    git_ref: Option<String>,
     target_tools: Option<Vec<String>>,
     workspace_path: Option<String>,
     git_auth: Option<super::skills::GitAuthInput>,
+    install_mode: Option<String>,
+    security_threshold: Option<String>,
+    collisions: Option<super::skill_collision::SkillCollisionResolutions>,
+    operation_id: Option<String>,
 ) -> Result<String, String> {
-    use super::skills::{install_skill_from_repo, read_skill_registry, set_skill_repository, update_skill, write_skill_registry};
+    use super::skill_collision::SkillCollisionResolution;
+    use super::skill_signature::normalize_repo_url;
+    use super::skills::{
+        install_skill_from_repo, read_skill_registry, resolve_skill_repository, set_skill_repository, update_skill,
+        write_skill_registry,
+    };
 
     let config = read_marketplace_config()?;
     let source = config
@@ -732,11 +764,15 @@ pub async fn install_skill_from_marketplace(
     let mut install_targets = Vec::new();
     let mut update_targets = Vec::new();
 
+    // 已安装且来自该市场源的 skill 直接更新；来自其他来源的同名 skill 按重名处理
     for skill_name in &skill_names {
-        if find_installed_skill_path(skill_name, workspace_path.as_deref())?.is_some() {
-            update_targets.push(skill_name.clone());
-        } else {
-            install_targets.push(skill_name.clone());
+        let installed_repo = find_installed_skill_path(skill_name, workspace_path.as_deref())?
+            .map(|dir| resolve_skill_repository(&dir, skill_name, workspace_path.as_deref()));
+        match installed_repo {
+            Some(Some(repo)) if normalize_repo_url(&repo) == normalize_repo_url(&source.url) => {
+                update_targets.push(skill_name.clone());
+            }
+            _ => install_targets.push(skill_name.clone()),
         }
     }
 
@@ -745,18 +781,36 @@ pub async fn install_skill_from_marketplace(
     if !install_targets.is_empty() {
         let result = install_skill_from_repo(
             source.url.clone(),
+            git_ref.clone(),
             Some(install_targets.clone()),
             target_tools.clone(),
             workspace_path.clone(),
-            git_auth,
+            git_auth.clone(),
+            install_mode,
+            security_threshold.clone(),
+            collisions.clone(),
+            // 进度由内层命令推送，操作 ID 显式传递（跨 .await 不能依赖线程内的当前操作）
+            operation_id.clone(),
         ).await?;
         results.push(result);
     }
 
     for skill_name in &update_targets {
-        set_skill_repository(skill_name.clone(), source.url.clone(), workspace_path.clone())?;
-        let result = update_skill(skill_name.clone(), workspace_path.clone()).await?;
-        results.push(format!("{}: {}", skill_name, result));
+        set_skill_repository(
+            skill_name.clone(),
+            source.url.clone(),
+            git_ref.clone(),
+            workspace_path.clone(),
+        )?;
+        let result = update_skill(
+            skill_name.clone(),
+            workspace_path.clone(),
+            None,
+            security_threshold.clone(),
+            git_auth.clone(),
+            operation_id.clone(),
+        ).await?;
+        results.push(format!("{}: {}", skill_name, result.message));
     }
 
     // 只在全局模式下更新 metadata 中的 sourceId（因为注册表是全局的）
@@ -764,7 +818,13 @@ pub async fn install_skill_from_marketplace(
         let mut registry = read_skill_registry().map_err(|e| format!("读取注册表失败: {}", e))?;
 
         for skill_name in &skill_names {
-            if let Some(entry) = registry.skills.iter_mut().find(|s| s.name == *skill_name) {
+            // 以别名安装的 skill 在注册表中使用别名
+            let installed_name = match collisions.as_ref().and_then(|c| c.get(skill_name)) {
+                Some(SkillCollisionResolution::Skip) => continue,
+                Some(SkillCollisionResolution::Alias { alias }) => alias.trim(),
+                _ => skill_name.as_str(),
+            };
+            if let Some(entry) = registry.skills.iter_mut().find(|s| s.name == installed_name) {
                 if let Some(ref mut metadata) = entry.metadata {
                     metadata.source_id = Some(source_id.clone());
                 }
@@ -1,6 +1,25 @@
 // 命令模块
 pub mod config;
+pub mod ai_tools;
 pub mod skills;
+pub mod skill_lock;
+pub mod skill_merge;
+pub mod skill_lint;
+pub mod skill_frontmatter;
+pub mod skill_security;
+pub mod skill_signature;
+pub mod skill_local;
+pub mod skill_export;
+pub mod skill_bundle;
+pub mod skill_deps;
+pub mod skill_collision;
+pub mod skill_sparse;
+pub mod skill_adapters;
+pub mod skill_links;
+pub mod skill_transaction;
+pub mod skill_history;
+pub mod git_cache;
+pub mod progress;
 pub mod marketplace;
 pub mod workspace;
 pub mod stats;
@@ -8,7 +27,15 @@ pub mod cache;
 
 // 重新导出所有命令
 pub use config::*;
+pub use ai_tools::*;
 pub use skills::*;
+pub use skill_lock::*;
+pub use skill_history::*;
+pub use skill_lint::*;
+pub use skill_frontmatter::*;
+pub use skill_signature::*;
+pub use skill_export::*;
+pub use skill_bundle::*;
 pub use marketplace::*;
 pub use workspace::*;
 pub use stats::*;
@@ -0,0 +1,238 @@
+// 长时间操作（克隆、扫描、安装）的进度事件
+// 命令开始时用 ProgressScope 标记当前操作，执行过程中的进度通过 operation-progress 事件推送给前端
+// ProgressScope 只在同步执行的代码中有效；async 命令之间通过 operation_id 参数显式传递操作
+use serde::Serialize;
+use std::cell::RefCell;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+
+/// 进度事件名称（payload 为 OperationProgress）
+pub const OPERATION_PROGRESS_EVENT: &str = "operation-progress";
+
+static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+thread_local! {
+    // 当前操作记录在线程内，只在不跨 .await 的同步代码中读取
+    static CURRENT_OPERATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// 应用启动时注册，用于推送进度事件
+pub fn init(app: tauri::AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// 操作所处的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProgressPhase {
+    Clone,
+    Scan,
+    Install,
+    Done,
+}
+
+/// 进度事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationProgress {
+    pub operation_id: String,
+    pub phase: ProgressPhase,
+    /// 当前阶段的百分比，无法估计时为空
+    pub percent: Option<u8>,
+    pub message: String,
+    /// 正在安装的 skill
+    pub skill: Option<String>,
+    /// git 已接收的字节数
+    pub bytes: Option<u64>,
+}
+
+/// 命令执行期间的当前操作；离开作用域时推送 done 并恢复外层操作
+/// 不实现 Send：async 命令跨 .await 持有时无法编译，避免任务切换线程后进度丢失或串到其他操作
+pub struct ProgressScope {
+    previous: Option<Option<String>>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ProgressScope {
+    /// operation_id 为空时沿用外层操作（同一线程中同步调用的内层命令）
+    pub fn begin(operation_id: Option<String>) -> Self {
+        let Some(operation_id) = operation_id.filter(|id| !id.trim().is_empty()) else {
+            return Self { previous: None, _not_send: PhantomData };
+        };
+        let previous = CURRENT_OPERATION.with(|current| current.replace(Some(operation_id)));
+        Self { previous: Some(previous), _not_send: PhantomData }
+    }
+}
+
+impl Drop for ProgressScope {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            emit(ProgressPhase::Done, Some(100), "完成".to_string(), None, None);
+            CURRENT_OPERATION.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+}
+
+/// 当前是否有需要推送进度的操作
+pub fn is_active() -> bool {
+    APP_HANDLE.get().is_some() && CURRENT_OPERATION.with(|current| current.borrow().is_some())
+}
+
+fn emit(phase: ProgressPhase, percent: Option<u8>, message: String, skill: Option<String>, bytes: Option<u64>) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    let Some(operation_id) = CURRENT_OPERATION.with(|current| current.borrow().clone()) else {
+        return;
+    };
+
+    let progress = OperationProgress {
+        operation_id,
+        phase,
+        percent,
+        message,
+        skill,
+        bytes,
+    };
+    if let Err(e) = app.emit(OPERATION_PROGRESS_EVENT, &progress) {
+        eprintln!("⚠️  [Backend] 推送进度失败: {}", e);
+    }
+}
+
+/// 进入新阶段
+pub fn phase(phase: ProgressPhase, message: impl Into<String>) {
+    emit(phase, None, message.into(), None, None);
+}
+
+/// 开始安装第 index 个（从 0 开始）skill
+pub fn skill(skill_name: &str, index: usize, total: usize) {
+    let percent = (index * 100 / total.max(1)).min(100) as u8;
+    emit(
+        ProgressPhase::Install,
+        Some(percent),
+        format!("安装 {} ({}/{})", skill_name, index + 1, total),
+        Some(skill_name.to_string()),
+        None,
+    );
+}
+
+/// 执行 git 命令并解析 --progress 输出（stderr 中以 \r 刷新的进度行），返回完整输出
+/// cancel 被设置时结束 git 进程并返回 Interrupted
+pub fn run_git_with_progress(cmd: &mut Command, cancel: Option<&AtomicBool>) -> std::io::Result<Output> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // stdout 在单独的线程中读取，避免管道写满后 git 阻塞
+    let mut stdout_pipe = child.stdout.take();
+    let stdout_reader = std::thread::spawn(move || {
+        let mut stdout = Vec::new();
+        if let Some(pipe) = stdout_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut stdout);
+        }
+        stdout
+    });
+
+    // stderr 也在单独的线程中读取，当前线程解析进度（操作记录在当前线程）并检查取消
+    let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+    let mut stderr_pipe = child.stderr.take();
+    std::thread::spawn(move || {
+        let Some(pipe) = stderr_pipe.as_mut() else {
+            return;
+        };
+        let mut buffer = [0u8; 4096];
+        while let Ok(read) = pipe.read(&mut buffer) {
+            if read == 0 || sender.send(buffer[..read].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stderr = Vec::new();
+    let mut line = Vec::new();
+    let mut last_reported: Option<(String, u8, Option<u64>)> = None;
+    loop {
+        if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            let _ = child.kill();
+            let _ = child.wait();
+            // git 启动的子进程（如 ssh）可能仍持有管道，不等待读取线程
+            return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "已取消"));
+        }
+        let chunk = match receiver.recv_timeout(Duration::from_millis(20)) {
+            Ok(chunk) => chunk,
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        for &byte in &chunk {
+            if byte == b'\r' || byte == b'\n' {
+                report_git_progress(&String::from_utf8_lossy(&line), &mut last_reported);
+                line.clear();
+            } else {
+                line.push(byte);
+            }
+        }
+        stderr.extend_from_slice(&chunk);
+    }
+
+    let status = child.wait()?;
+    let stdout = stdout_reader.join().unwrap_or_default();
+    Ok(Output { status, stdout, stderr })
+}
+
+/// 解析一行 git 进度，例如 "Receiving objects:  45% (450/1000), 1.20 MiB | 1.00 MiB/s"
+fn report_git_progress(line: &str, last_reported: &mut Option<(String, u8, Option<u64>)>) {
+    let line = line.trim();
+    let line = line.strip_prefix("remote:").map(str::trim_start).unwrap_or(line);
+    let Some((stage, rest)) = line.split_once(':') else {
+        return;
+    };
+    let Some((percent, rest)) = rest.trim_start().split_once('%') else {
+        return;
+    };
+    let Ok(percent) = percent.parse::<u8>() else {
+        return;
+    };
+    let stage = stage.trim().to_string();
+    let percent = percent.min(100);
+
+    // "(450/1000), 1.20 MiB | 1.00 MiB/s" 中逗号后为已接收的数据量
+    let bytes = rest.split_once(',').and_then(|(_, size)| {
+        let mut parts = size.split('|').next()?.split_whitespace();
+        let value: f64 = parts.next()?.parse().ok()?;
+        let unit = match parts.next()? {
+            "KiB" => 1024.0,
+            "MiB" => 1024.0 * 1024.0,
+            "GiB" => 1024.0 * 1024.0 * 1024.0,
+            _ => 1.0,
+        };
+        Some((value * unit) as u64)
+    });
+
+    // git 会频繁刷新同一进度，只推送有变化的
+    let current = (stage, percent, bytes);
+    if last_reported.as_ref() == Some(&current) {
+        return;
+    }
+    emit(ProgressPhase::Clone, Some(percent), git_stage_label(&current.0), None, bytes);
+    *last_reported = Some(current);
+}
+
+fn git_stage_label(stage: &str) -> String {
+    match stage {
+        "Enumerating objects" => "枚举对象",
+        "Counting objects" => "统计对象",
+        "Compressing objects" => "压缩对象",
+        "Receiving objects" => "接收对象",
+        "Resolving deltas" => "处理差异",
+        "Updating files" => "检出文件",
+        other => other,
+    }
+    .to_string()
+}
@@ -0,0 +1,282 @@
+// Skill 格式适配：将 Claude 格式的 SKILL.md 转换为其他 AI 工具需要的格式
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 记录适配器生成了哪些文件，更新、回滚时据此清理，生成清单时据此排除
+pub const ADAPTER_STATE_FILE_NAME: &str = ".cobalt-adapter.json";
+
+/// Skill 在工具目录中的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillFormat {
+    /// 原样复制 SKILL.md
+    Claude,
+    /// 额外生成 Cursor 规则文件 rules/<skill>.mdc（description / globs / alwaysApply）
+    CursorMdc,
+    /// 额外生成 AGENTS.md 风格的说明文件
+    AgentsMd,
+}
+
+impl SkillFormat {
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(str::trim).filter(|v| !v.is_empty()).unwrap_or("claude") {
+            "claude" => Ok(Self::Claude),
+            "cursor-mdc" => Ok(Self::CursorMdc),
+            "agents-md" => Ok(Self::AgentsMd),
+            other => Err(format!("未知的 Skill 格式: {}", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::CursorMdc => "cursor-mdc",
+            Self::AgentsMd => "agents-md",
+        }
+    }
+
+    /// 获取工具需要的格式
+    fn for_tool(tool_name: &str) -> Self {
+        super::ai_tools::load_ai_tools()
+            .iter()
+            .find(|tool| tool.id == tool_name)
+            .and_then(|tool| Self::parse(tool.format.as_deref()).ok())
+            .unwrap_or(Self::Claude)
+    }
+
+    /// 生成文件的位置和记录在适配状态中的路径
+    /// skill 目录内的文件记录相对路径；Cursor 只从 <配置目录>/rules/*.mdc 加载规则，
+    /// 规则文件生成在工具 skills 目录的同级 rules 目录中，记录绝对路径
+    fn generated_file(&self, skill_dir: &Path, tool_skill_dir: &Path, skill_name: &str) -> Option<(PathBuf, String)> {
+        match self {
+            Self::Claude => None,
+            Self::CursorMdc => {
+                let rules_dir = tool_skill_dir.parent()?.parent()?.join("rules");
+                let path = rules_dir.join(format!("{}.mdc", skill_name));
+                let record = path.to_string_lossy().to_string();
+                Some((path, record))
+            }
+            Self::AgentsMd => Some((skill_dir.join("AGENTS.md"), "AGENTS.md".to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SkillAdapterState {
+    #[serde(default)]
+    formats: Vec<String>,
+    #[serde(default)]
+    generated_files: Vec<String>,
+}
+
+fn read_adapter_state(skill_dir: &Path) -> Option<SkillAdapterState> {
+    let content = fs::read_to_string(skill_dir.join(ADAPTER_STATE_FILE_NAME)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 适配器生成的文件（相对 skill 目录），这些文件不属于 Skill 内容本身
+pub fn read_generated_files(skill_dir: &Path) -> Vec<String> {
+    read_adapter_state(skill_dir)
+        .map(|state| state.generated_files)
+        .unwrap_or_default()
+}
+
+/// 生成在 skill 目录之外的文件（如 Cursor 规则），删除工具副本时需要一并删除
+/// 链接到规范副本的目录由 sync_linked_skill_formats 统一维护，返回空
+pub fn external_generated_files(skill_dir: &Path, workspace_path: Option<&str>) -> Vec<PathBuf> {
+    if super::skill_links::linked_store_dir(skill_dir, workspace_path).is_some() {
+        return Vec::new();
+    }
+    read_generated_files(skill_dir)
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute() && path.is_file())
+        .collect()
+}
+
+/// 删除生成在 skill 目录之外的文件，用于不经过事务直接删除工具副本的场景
+pub fn remove_external_generated_files(skill_dir: &Path, workspace_path: Option<&str>) -> Result<(), String> {
+    for path in external_generated_files(skill_dir, workspace_path) {
+        fs::remove_file(&path).map_err(|e| format!("删除生成文件 {} 失败: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// 文件是否由适配器生成（带有生成标记），生成文件可以直接覆盖
+fn is_generated_file(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|content| content.contains(GENERATED_NOTICE))
+        .unwrap_or(false)
+}
+
+/// 删除之前生成的文件，使目录回到 Claude 格式
+fn clear_generated_files(skill_dir: &Path) -> Result<(), String> {
+    for file in read_generated_files(skill_dir) {
+        // 绝对路径（skill 目录之外的文件）join 后保持不变
+        let path = skill_dir.join(&file);
+        if path.is_file() {
+            fs::remove_file(&path).map_err(|e| format!("删除生成文件 {} 失败: {}", file, e))?;
+        }
+    }
+
+    let state_path = skill_dir.join(ADAPTER_STATE_FILE_NAME);
+    if state_path.exists() {
+        fs::remove_file(&state_path).map_err(|e| format!("删除适配状态失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 按目标工具的格式转换 skill 目录（目录可能是从其他工具复制来的，先清理旧的生成文件）
+/// skill_dir 为工具 skills 目录下的 skill 目录
+pub fn apply_skill_format(tool_name: &str, skill_dir: &Path, skill_name: &str) -> Result<(), String> {
+    apply_skill_formats(&[(tool_name.to_string(), skill_dir.to_path_buf())], skill_dir, skill_name)
+}
+
+/// 为多个工具生成格式文件，用于链接模式下多个工具共用的规范副本
+/// tools 为（工具名称，工具 skills 目录下的 skill 路径）
+pub fn apply_skill_formats(tools: &[(String, PathBuf)], skill_dir: &Path, skill_name: &str) -> Result<(), String> {
+    clear_generated_files(skill_dir)?;
+
+    let tools: Vec<(SkillFormat, &Path)> = tools
+        .iter()
+        .map(|(tool_name, tool_skill_dir)| (SkillFormat::for_tool(tool_name), tool_skill_dir.as_path()))
+        .filter(|(format, _)| *format != SkillFormat::Claude)
+        .collect();
+    if tools.is_empty() {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(skill_dir.join("SKILL.md"))
+        .map_err(|e| format!("读取 SKILL.md 失败: {}", e))?;
+    let (frontmatter, body) = split_frontmatter(&content);
+
+    let mut state = SkillAdapterState::default();
+    for (format, tool_skill_dir) in tools {
+        let Some((target, record)) = format.generated_file(skill_dir, tool_skill_dir, skill_name) else {
+            continue;
+        };
+        // 多个工具共用同一个生成文件（如 AGENTS.md）时只生成一次
+        if state.generated_files.contains(&record) {
+            continue;
+        }
+
+        // 已有同名文件且不是生成的时不覆盖，避免之后被当作生成文件删除
+        if target.exists() && !is_generated_file(&target) {
+            eprintln!(
+                "⚠️  [Backend] Skill '{}' 已存在 {}，跳过 {} 格式转换",
+                skill_name,
+                target.display(),
+                format.as_str()
+            );
+            continue;
+        }
+
+        let rendered = match format {
+            SkillFormat::Claude => continue,
+            SkillFormat::CursorMdc => render_cursor_mdc(frontmatter.as_ref(), body),
+            SkillFormat::AgentsMd => render_agents_md(frontmatter.as_ref(), body, skill_name),
+        };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录 {} 失败: {}", parent.display(), e))?;
+        }
+        fs::write(&target, rendered).map_err(|e| format!("写入 {} 失败: {}", target.display(), e))?;
+
+        println!("🔁 [Backend] 已将 {} 转换为 {} 格式", skill_name, format.as_str());
+        if !state.formats.contains(&format.as_str().to_string()) {
+            state.formats.push(format.as_str().to_string());
+        }
+        state.generated_files.push(record);
+    }
+
+    if state.generated_files.is_empty() {
+        return Ok(());
+    }
+
+    let state_content = serde_json::to_string_pretty(&state)
+        .map_err(|e| format!("序列化适配状态失败: {}", e))?;
+    fs::write(skill_dir.join(ADAPTER_STATE_FILE_NAME), state_content)
+        .map_err(|e| format!("写入适配状态失败: {}", e))
+}
+
+/// 拆分 YAML frontmatter 与正文
+fn split_frontmatter(content: &str) -> (Option<serde_yaml::Value>, &str) {
+    let Ok(re) = regex::Regex::new(r"(?s)^---\s*\n(.*?)\n---[ \t]*\r?\n?") else {
+        return (None, content);
+    };
+    match re.captures(content) {
+        Some(caps) => {
+            let frontmatter = caps.get(1).and_then(|m| serde_yaml::from_str(m.as_str()).ok());
+            let end = caps.get(0).map(|m| m.end()).unwrap_or(0);
+            (frontmatter, content[end..].trim_start_matches(['\r', '\n']))
+        }
+        None => (None, content),
+    }
+}
+
+fn frontmatter_str<'a>(frontmatter: Option<&'a serde_yaml::Value>, key: &str) -> Option<&'a str> {
+    frontmatter?
+        .get(key)?
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// 读取 globs / paths，支持逗号分隔字符串或列表
+fn frontmatter_globs(frontmatter: Option<&serde_yaml::Value>) -> Vec<String> {
+    let Some(value) = frontmatter.and_then(|fm| fm.get("globs").or_else(|| fm.get("paths"))) else {
+        return Vec::new();
+    };
+
+    let items: Vec<String> = match value {
+        serde_yaml::Value::String(s) => s.split(',').map(str::to_string).collect(),
+        serde_yaml::Value::Sequence(seq) => seq.iter().filter_map(|v| v.as_str().map(str::to_string)).collect(),
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+const GENERATED_NOTICE: &str = "<!-- 由 Cobalt 根据 SKILL.md 生成，修改请编辑 SKILL.md -->";
+
+fn render_cursor_mdc(frontmatter: Option<&serde_yaml::Value>, body: &str) -> String {
+    let description = frontmatter_str(frontmatter, "description").unwrap_or_default();
+    let always_apply = frontmatter
+        .and_then(|fm| fm.get("alwaysApply").or_else(|| fm.get("always-apply")))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    // description 可能包含冒号等特殊字符，交给 serde_yaml 处理引号
+    let description_yaml = serde_yaml::to_string(&serde_yaml::Value::String(description.to_string()))
+        .map(|s| s.trim_end().to_string())
+        .unwrap_or_default();
+
+    let globs = frontmatter_globs(frontmatter);
+    let globs_line = if globs.is_empty() {
+        "globs:".to_string()
+    } else {
+        format!("globs: {}", globs.join(","))
+    };
+
+    format!(
+        "---\ndescription: {}\n{}\nalwaysApply: {}\n---\n{}\n\n{}",
+        description_yaml,
+        globs_line,
+        always_apply,
+        GENERATED_NOTICE,
+        body
+    )
+}
+
+fn render_agents_md(frontmatter: Option<&serde_yaml::Value>, body: &str, skill_name: &str) -> String {
+    let name = frontmatter_str(frontmatter, "name").unwrap_or(skill_name);
+    let mut content = format!("{}\n\n# {}\n\n", GENERATED_NOTICE, name);
+    if let Some(description) = frontmatter_str(frontmatter, "description") {
+        content.push_str(&format!("> {}\n\n", description.replace('\n', " ")));
+    }
+    content.push_str(body);
+    content
+}
@@ -0,0 +1,698 @@
+// Skill 套件：一份 YAML 描述一组 Skills（仓库、ref、skill 名称、目标工具），整体安装、更新、对比和移除
+// 已安装的套件全局记录在注册表中，工作区记录在 <workspace>/.cobalt/skill-bundles.json
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skill_collision::SKILL_CONFLICT_PREFIX;
+use super::skill_deps::find_dependents;
+use super::skill_local::local_source_path;
+use super::skill_signature::normalize_repo_url;
+use super::skill_transaction::SkillTransaction;
+use super::skills::{
+    check_skill_update, get_skill_registry_path, install_skill_from_local, install_skill_from_repo,
+    read_skill_manifest, read_skill_registry, resolve_local_skill_tool_dirs, scan_local_skills, scan_repo_skills,
+    set_skill_repository, uninstall_skill, update_skill, write_skill_registry, SkillRegistryEntry,
+    SECURITY_BLOCKED_PREFIX,
+};
+
+const WORKSPACE_BUNDLES_FILE_NAME: &str = "skill-bundles.json";
+
+/// 套件定义（YAML）
+///
+/// ```yaml
+/// name: team-standard
+/// description: 团队统一使用的 Skills
+/// targetTools: [claude-code, cursor]
+/// skills:
+///   - repo: https://github.com/org/skills
+///     ref: v1.2.0
+///     skills: [code-review, commit-message]
+///   - repo: https://github.com/org/single-skill   # 不写 skills 表示安装仓库中的全部 skill
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillBundle {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 套件默认的目标工具，为空时安装到 claude-code
+    #[serde(default, alias = "target-tools", skip_serializing_if = "Vec::is_empty")]
+    pub target_tools: Vec<String>,
+    pub skills: Vec<SkillBundleSource>,
+}
+
+/// 套件中的一个来源仓库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillBundleSource {
+    /// Git 仓库地址，或 file:// 开头的本地目录 / 归档
+    pub repo: String,
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// 要安装的 skill，为空表示仓库中的全部 skill
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// 覆盖套件默认的目标工具
+    #[serde(default, alias = "target-tools", skip_serializing_if = "Vec::is_empty")]
+    pub target_tools: Vec<String>,
+}
+
+/// 已安装的套件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledSkillBundle {
+    pub definition: SkillBundle,
+    /// 套件文件路径，更新和对比时优先重新读取
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
+    /// 由该套件安装的 skill（安装套件前已存在的同名 skill 不计入，移除套件时不会卸载）
+    #[serde(default)]
+    pub skills: Vec<String>,
+    pub installed_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+/// 套件安装、更新或移除的结果
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillBundleResult {
+    pub bundle_name: String,
+    pub installed: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// 套件定义与本地已安装状态的差异
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillBundleDiff {
+    pub bundle_name: String,
+    /// 套件中有、本地未安装
+    pub missing: Vec<String>,
+    /// 之前随套件安装、已从套件定义中移除
+    pub extra: Vec<String>,
+    /// 远程有更新
+    pub outdated: Vec<String>,
+    /// 仓库地址或 ref 与套件定义不一致
+    pub ref_changed: Vec<String>,
+    pub up_to_date: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// 解析后的来源：skill 名称已确定
+struct ResolvedBundleSource {
+    source: SkillBundleSource,
+    skill_names: Vec<String>,
+}
+
+struct BundlePlan {
+    sources: Vec<ResolvedBundleSource>,
+    errors: Vec<String>,
+}
+
+impl BundlePlan {
+    fn skill_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self.sources.iter().flat_map(|s| s.skill_names.iter()) {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+fn get_workspace_bundles_path(workspace_path: &str) -> PathBuf {
+    PathBuf::from(workspace_path).join(".cobalt").join(WORKSPACE_BUNDLES_FILE_NAME)
+}
+
+/// 读取已安装的套件
+pub fn load_installed_bundles(workspace_path: Option<&str>) -> Result<Vec<InstalledSkillBundle>, String> {
+    match workspace_path {
+        Some(ws_path) => {
+            let path = get_workspace_bundles_path(ws_path);
+            if !path.exists() {
+                return Ok(Vec::new());
+            }
+            let content = fs::read_to_string(&path).map_err(|e| format!("读取套件记录失败: {}", e))?;
+            serde_json::from_str(&content).map_err(|e| format!("解析套件记录失败: {}", e))
+        }
+        None => Ok(read_skill_registry()?.bundles),
+    }
+}
+
+fn save_installed_bundles(workspace_path: Option<&str>, bundles: Vec<InstalledSkillBundle>) -> Result<(), String> {
+    match workspace_path {
+        Some(ws_path) => {
+            let path = get_workspace_bundles_path(ws_path);
+            let content = serde_json::to_string_pretty(&bundles).map_err(|e| format!("序列化套件记录失败: {}", e))?;
+            super::skill_transaction::write_file_atomic(&path, content.as_bytes())
+        }
+        None => {
+            let mut registry = read_skill_registry()?;
+            registry.bundles = bundles;
+            write_skill_registry(registry)
+        }
+    }
+}
+
+/// 为列表中的 skill 标记所属套件
+pub fn apply_bundle_membership(skills: &mut [SkillRegistryEntry], workspace_path: Option<&str>) {
+    let bundles = match load_installed_bundles(workspace_path) {
+        Ok(bundles) => bundles,
+        Err(e) => {
+            eprintln!("⚠️  [Backend] 读取套件记录失败: {}", e);
+            return;
+        }
+    };
+
+    for skill in skills.iter_mut() {
+        skill.bundles = bundles
+            .iter()
+            .filter(|bundle| bundle.skills.contains(&skill.name))
+            .map(|bundle| bundle.definition.name.clone())
+            .collect();
+    }
+}
+
+fn parse_skill_bundle(content: &str) -> Result<SkillBundle, String> {
+    let mut bundle: SkillBundle = serde_yaml::from_str(content).map_err(|e| format!("解析套件文件失败: {}", e))?;
+
+    bundle.name = bundle.name.trim().to_string();
+    if bundle.name.is_empty() {
+        return Err("套件缺少 name 字段".to_string());
+    }
+    if bundle.skills.is_empty() {
+        return Err(format!("套件 '{}' 没有列出任何来源", bundle.name));
+    }
+    for source in &mut bundle.skills {
+        source.repo = source.repo.trim().to_string();
+        if source.repo.is_empty() {
+            return Err(format!("套件 '{}' 中存在缺少 repo 的来源", bundle.name));
+        }
+        source.git_ref = source.git_ref.take().map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    }
+
+    Ok(bundle)
+}
+
+fn read_bundle_file(path: &Path) -> Result<SkillBundle, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取套件文件 {} 失败: {}", path.display(), e))?;
+    parse_skill_bundle(&content)
+}
+
+fn find_installed_bundle(name: &str, workspace_path: Option<&str>) -> Result<InstalledSkillBundle, String> {
+    load_installed_bundles(workspace_path)?
+        .into_iter()
+        .find(|bundle| bundle.definition.name == name)
+        .ok_or_else(|| format!("套件 '{}' 未安装", name))
+}
+
+/// 套件文件仍存在时重新读取，以便同步团队对套件的修改
+fn current_definition(installed: &InstalledSkillBundle) -> Result<SkillBundle, String> {
+    match installed.source_path.as_deref().map(Path::new) {
+        Some(path) if path.exists() => read_bundle_file(path),
+        _ => Ok(installed.definition.clone()),
+    }
+}
+
+fn is_skill_installed(skill_name: &str, workspace_path: Option<&str>) -> bool {
+    resolve_local_skill_tool_dirs(skill_name, workspace_path).is_ok()
+}
+
+/// 已安装 skill 的仓库地址和 ref 与套件来源不一致
+fn has_ref_changed(skill_name: &str, source: &SkillBundleSource, workspace_path: Option<&str>) -> bool {
+    let manifest = resolve_local_skill_tool_dirs(skill_name, workspace_path)
+        .ok()
+        .and_then(|dirs| dirs.iter().find_map(|(_, dir)| read_skill_manifest(dir)));
+    let Some(manifest) = manifest else {
+        return false;
+    };
+
+    let same_repo = manifest
+        .repository
+        .as_deref()
+        .is_some_and(|repo| normalize_repo_url(repo) == normalize_repo_url(&source.repo));
+    !same_repo || manifest.git_ref != source.git_ref
+}
+
+/// 改写 skill 来源前保存清单和注册表，更新失败时恢复原来源
+fn snapshot_skill_source(
+    txn: &mut SkillTransaction,
+    skill_name: &str,
+    workspace_path: Option<&str>,
+) -> Result<(), String> {
+    for (_, dir) in resolve_local_skill_tool_dirs(skill_name, workspace_path)? {
+        txn.snapshot_file(&dir.join(".manifest.json"))?;
+    }
+    if workspace_path.is_none() {
+        txn.snapshot_file(&get_skill_registry_path()?)?;
+    }
+    Ok(())
+}
+
+/// 批量卸载套件的 skill：被套件外 skill 依赖的保留并记为失败（保留后其依赖也一并保留），
+/// 只有依赖方都在本次卸载范围内时才跳过依赖确认
+fn uninstall_bundle_skills(
+    candidates: Vec<String>,
+    workspace_path: Option<&str>,
+    result: &mut SkillBundleResult,
+) -> Vec<String> {
+    let mut removing = candidates;
+    let mut kept = Vec::new();
+    loop {
+        let blocked: Vec<(String, Vec<String>)> = removing
+            .iter()
+            .filter_map(|name| {
+                let outside: Vec<String> = find_dependents(name, workspace_path)
+                    .into_iter()
+                    .filter(|dependent| !removing.contains(dependent))
+                    .collect();
+                (!outside.is_empty()).then(|| (name.clone(), outside))
+            })
+            .collect();
+        if blocked.is_empty() {
+            break;
+        }
+        for (name, outside) in blocked {
+            removing.retain(|n| n != &name);
+            result.failed.push(format!("{}（被套件外的 skill 依赖: {}）", name, outside.join(", ")));
+            kept.push(name);
+        }
+    }
+
+    for name in removing {
+        let force = !find_dependents(&name, workspace_path).is_empty();
+        match uninstall_skill(name.clone(), workspace_path.map(str::to_string), Some(force)) {
+            Ok(()) => result.removed.push(name),
+            Err(e) => {
+                result.failed.push(format!("{}（{}）", name, e));
+                kept.push(name);
+            }
+        }
+    }
+    kept
+}
+
+/// 确定每个来源要安装的 skill：未列出名称的来源需要扫描仓库
+async fn plan_bundle(bundle: &SkillBundle) -> BundlePlan {
+    let mut plan = BundlePlan { sources: Vec::new(), errors: Vec::new() };
+
+    for source in &bundle.skills {
+        if !source.skills.is_empty() {
+            plan.sources.push(ResolvedBundleSource {
+                source: source.clone(),
+                skill_names: source.skills.clone(),
+            });
+            continue;
+        }
+
+        let scanned = match local_source_path(&source.repo) {
+            Some(path) => scan_local_skills(path.to_string_lossy().to_string()).await,
+            None => scan_repo_skills(source.repo.clone(), source.git_ref.clone(), None, None).await,
+        };
+        match scanned {
+            Ok(skills) => plan.sources.push(ResolvedBundleSource {
+                source: source.clone(),
+                skill_names: skills.into_iter().map(|s| s.name).collect(),
+            }),
+            Err(e) => plan.errors.push(format!("{}（{}）", source.repo, e)),
+        }
+    }
+
+    plan
+}
+
+fn describe_install_error(error: &str) -> String {
+    if error.starts_with(SECURITY_BLOCKED_PREFIX) {
+        "安全扫描发现风险，已取消".to_string()
+    } else if error.starts_with(SKILL_CONFLICT_PREFIX) {
+        "已安装来自其他来源的同名 skill".to_string()
+    } else {
+        error.to_string()
+    }
+}
+
+/// 按套件定义安装缺少的 skill；update_existing 时同时更新套件已有的 skill
+/// 不属于套件（members 之外）的同名 skill 保持不动
+#[allow(clippy::too_many_arguments)]
+async fn sync_bundle(
+    bundle: &SkillBundle,
+    plan: &BundlePlan,
+    members: &[String],
+    workspace_path: Option<&str>,
+    install_mode: Option<String>,
+    security_threshold: Option<String>,
+    on_conflict: Option<String>,
+    update_existing: bool,
+    result: &mut SkillBundleResult,
+) {
+    result.failed.extend(plan.errors.iter().cloned());
+
+    for resolved in &plan.sources {
+        let source = &resolved.source;
+        let mut to_install = Vec::new();
+
+        for name in &resolved.skill_names {
+            if !is_skill_installed(name, workspace_path) {
+                to_install.push(name.clone());
+                continue;
+            }
+            if !update_existing || !members.contains(name) {
+                result.unchanged.push(name.clone());
+                continue;
+            }
+
+            // 套件修改了仓库或 ref 时先改写来源，再按新来源更新；更新未完成时恢复原来源
+            let mut source_txn = None;
+            if has_ref_changed(name, source, workspace_path) {
+                let rewritten = SkillTransaction::begin("改写 skill 来源").and_then(|mut txn| {
+                    snapshot_skill_source(&mut txn, name, workspace_path)?;
+                    set_skill_repository(
+                        name.clone(),
+                        source.repo.clone(),
+                        source.git_ref.clone(),
+                        workspace_path.map(str::to_string),
+                    )?;
+                    Ok(txn)
+                });
+                match rewritten {
+                    Ok(txn) => source_txn = Some(txn),
+                    Err(e) => {
+                        result.failed.push(format!("{}（{}）", name, e));
+                        continue;
+                    }
+                }
+            } else {
+                match check_skill_update(name.clone(), workspace_path.map(str::to_string), None).await {
+                    Ok(check) if check.has_update => {}
+                    Ok(check) => {
+                        match check.error {
+                            Some(error) => result.failed.push(format!("{}（{}）", name, error)),
+                            None => result.unchanged.push(name.clone()),
+                        }
+                        continue;
+                    }
+                    Err(e) => {
+                        result.failed.push(format!("{}（{}）", name, e));
+                        continue;
+                    }
+                }
+            }
+
+            match update_skill(
+                name.clone(),
+                workspace_path.map(str::to_string),
+                on_conflict.clone(),
+                security_threshold.clone(),
+                None,
+                None,
+            ).await {
+                Ok(outcome) if outcome.applied => {
+                    if let Some(txn) = source_txn {
+                        txn.commit();
+                    }
+                    result.updated.push(name.clone());
+                }
+                Ok(outcome) => result.failed.push(format!("{}（{}）", name, outcome.message)),
+                Err(e) => result.failed.push(format!("{}（{}）", name, describe_install_error(&e))),
+            }
+        }
+
+        if to_install.is_empty() {
+            continue;
+        }
+
+        let target_tools = if !source.target_tools.is_empty() {
+            Some(source.target_tools.clone())
+        } else if !bundle.target_tools.is_empty() {
+            Some(bundle.target_tools.clone())
+        } else {
+            None
+        };
+        let install_result = match local_source_path(&source.repo) {
+            Some(path) => {
+                install_skill_from_local(
+                    path.to_string_lossy().to_string(),
+                    Some(to_install.clone()),
+                    target_tools,
+                    workspace_path.map(str::to_string),
+                    install_mode.clone(),
+                    security_threshold.clone(),
+                    None,
+                )
+                .await
+            }
+            None => {
+                install_skill_from_repo(
+                    source.repo.clone(),
+                    source.git_ref.clone(),
+                    Some(to_install.clone()),
+                    target_tools,
+                    workspace_path.map(str::to_string),
+                    None,
+                    install_mode.clone(),
+                    security_threshold.clone(),
+                    None,
+                    None,
+                )
+                .await
+            }
+        };
+
+        // 部分 skill 可能已安装成功，按实际结果记录
+        let error = install_result.err().map(|e| describe_install_error(&e));
+        for name in to_install {
+            if is_skill_installed(&name, workspace_path) {
+                result.installed.push(name);
+            } else {
+                let reason = error.clone().unwrap_or_else(|| "安装失败".to_string());
+                result.failed.push(format!("{}（{}）", name, reason));
+            }
+        }
+    }
+}
+
+/// 读取并校验套件文件，用于安装前预览
+#[tauri::command]
+pub fn read_skill_bundle(path: String) -> Result<SkillBundle, String> {
+    read_bundle_file(Path::new(path.trim()))
+}
+
+/// 列出已安装的套件
+#[tauri::command]
+pub fn list_skill_bundles(workspace_path: Option<String>) -> Result<Vec<InstalledSkillBundle>, String> {
+    load_installed_bundles(workspace_path.as_deref())
+}
+
+/// 安装套件：安装套件中尚未安装的 skill；已存在的同名 skill 保持不动，也不计入套件
+#[tauri::command]
+pub async fn install_skill_bundle(
+    path: String,
+    workspace_path: Option<String>,
+    install_mode: Option<String>,
+    security_threshold: Option<String>,
+) -> Result<SkillBundleResult, String> {
+    let source_path = fs::canonicalize(path.trim()).map_err(|_| format!("套件文件不存在: {}", path))?;
+    let bundle = read_bundle_file(&source_path)?;
+    let workspace = workspace_path.as_deref();
+
+    let mut bundles = load_installed_bundles(workspace)?;
+    if bundles.iter().any(|b| b.definition.name == bundle.name) {
+        return Err(format!("套件 '{}' 已安装，请使用更新同步套件的修改", bundle.name));
+    }
+
+    println!("📦 [Backend] 安装套件 '{}'（{} 个来源）", bundle.name, bundle.skills.len());
+    let mut result = SkillBundleResult { bundle_name: bundle.name.clone(), ..Default::default() };
+    let plan = plan_bundle(&bundle).await;
+    sync_bundle(&bundle, &plan, &[], workspace, install_mode, security_threshold, None, false, &mut result).await;
+
+    let members = result.installed.clone();
+    if members.is_empty() && !result.failed.is_empty() {
+        return Err(format!("套件 '{}' 安装失败: {}", bundle.name, result.failed.join("; ")));
+    }
+
+    bundles.push(InstalledSkillBundle {
+        definition: bundle,
+        source_path: Some(source_path.to_string_lossy().to_string()),
+        skills: members,
+        installed_at: chrono::Utc::now().to_rfc3339(),
+        updated_at: None,
+    });
+    save_installed_bundles(workspace, bundles)?;
+
+    println!(
+        "✅ [Backend] 套件 '{}' 安装完成: 新安装 {} 个，已存在 {} 个，失败 {} 个",
+        result.bundle_name,
+        result.installed.len(),
+        result.unchanged.len(),
+        result.failed.len()
+    );
+    Ok(result)
+}
+
+/// 对比套件定义与本地已安装的 skill
+#[tauri::command]
+pub async fn diff_skill_bundle(name: String, workspace_path: Option<String>) -> Result<SkillBundleDiff, String> {
+    let workspace = workspace_path.as_deref();
+    let installed = find_installed_bundle(&name, workspace)?;
+    let bundle = current_definition(&installed)?;
+    let plan = plan_bundle(&bundle).await;
+
+    let mut diff = SkillBundleDiff { bundle_name: name, errors: plan.errors.clone(), ..Default::default() };
+    for resolved in &plan.sources {
+        for skill_name in &resolved.skill_names {
+            if !is_skill_installed(skill_name, workspace) {
+                diff.missing.push(skill_name.clone());
+            } else if !installed.skills.contains(skill_name) {
+                diff.errors.push(format!("{}（已安装的同名 skill 不属于该套件）", skill_name));
+            } else if has_ref_changed(skill_name, &resolved.source, workspace) {
+                diff.ref_changed.push(skill_name.clone());
+            } else {
+                match check_skill_update(skill_name.clone(), workspace_path.clone(), None).await {
+                    Ok(check) if check.has_update => diff.outdated.push(skill_name.clone()),
+                    Ok(check) => match check.error {
+                        Some(error) => diff.errors.push(format!("{}（{}）", skill_name, error)),
+                        None => diff.up_to_date.push(skill_name.clone()),
+                    },
+                    Err(e) => diff.errors.push(format!("{}（{}）", skill_name, e)),
+                }
+            }
+        }
+    }
+
+    // 有来源无法解析时无法确定哪些 skill 已被移出套件
+    if plan.errors.is_empty() {
+        let desired = plan.skill_names();
+        diff.extra = installed
+            .skills
+            .iter()
+            .filter(|name| !desired.contains(name) && is_skill_installed(name, workspace))
+            .cloned()
+            .collect();
+    }
+
+    Ok(diff)
+}
+
+/// 更新套件：重新读取套件文件，安装新增的 skill，更新已有的 skill，卸载已移出套件的 skill
+#[tauri::command]
+pub async fn update_skill_bundle(
+    name: String,
+    workspace_path: Option<String>,
+    on_conflict: Option<String>,
+    install_mode: Option<String>,
+    security_threshold: Option<String>,
+) -> Result<SkillBundleResult, String> {
+    let workspace = workspace_path.as_deref();
+    let installed = find_installed_bundle(&name, workspace)?;
+    let bundle = current_definition(&installed)?;
+
+    println!("🔄 [Backend] 更新套件 '{}'", name);
+    let mut result = SkillBundleResult { bundle_name: name.clone(), ..Default::default() };
+    let plan = plan_bundle(&bundle).await;
+    sync_bundle(
+        &bundle,
+        &plan,
+        &installed.skills,
+        workspace,
+        install_mode,
+        security_threshold,
+        on_conflict,
+        true,
+        &mut result,
+    ).await;
+
+    // 只有之前由套件安装或本次新安装的 skill 属于套件
+    let mut members: Vec<String> = plan
+        .skill_names()
+        .into_iter()
+        .filter(|skill_name| installed.skills.contains(skill_name) || result.installed.contains(skill_name))
+        .filter(|skill_name| is_skill_installed(skill_name, workspace))
+        .collect();
+
+    let bundles = load_installed_bundles(workspace)?;
+    if plan.errors.is_empty() {
+        // 只卸载不属于其他套件的 skill
+        let desired = plan.skill_names();
+        let removing: Vec<String> = installed
+            .skills
+            .iter()
+            .filter(|skill_name| !desired.contains(skill_name))
+            .filter(|skill_name| {
+                !bundles
+                    .iter()
+                    .any(|b| b.definition.name != name && b.skills.contains(skill_name))
+            })
+            .filter(|skill_name| is_skill_installed(skill_name, workspace))
+            .cloned()
+            .collect();
+        members.extend(uninstall_bundle_skills(removing, workspace, &mut result));
+    } else {
+        // 保留无法解析的来源之前安装的 skill
+        for skill_name in &installed.skills {
+            if !members.contains(skill_name) && is_skill_installed(skill_name, workspace) {
+                members.push(skill_name.clone());
+            }
+        }
+    }
+
+    let bundles = bundles
+        .into_iter()
+        .map(|mut b| {
+            if b.definition.name == name {
+                b.definition = bundle.clone();
+                b.skills = members.clone();
+                b.updated_at = Some(chrono::Utc::now().to_rfc3339());
+            }
+            b
+        })
+        .collect();
+    save_installed_bundles(workspace, bundles)?;
+
+    println!(
+        "✅ [Backend] 套件 '{}' 更新完成: 新安装 {} 个，更新 {} 个，卸载 {} 个，失败 {} 个",
+        name,
+        result.installed.len(),
+        result.updated.len(),
+        result.removed.len(),
+        result.failed.len()
+    );
+    Ok(result)
+}
+
+/// 移除套件：卸载套件中不属于其他套件的 skill；keep_skills 为 true 时只删除套件记录
+#[tauri::command]
+pub fn remove_skill_bundle(
+    name: String,
+    workspace_path: Option<String>,
+    keep_skills: Option<bool>,
+) -> Result<SkillBundleResult, String> {
+    let workspace = workspace_path.as_deref();
+    let installed = find_installed_bundle(&name, workspace)?;
+    let mut result = SkillBundleResult { bundle_name: name.clone(), ..Default::default() };
+
+    let bundles: Vec<InstalledSkillBundle> = load_installed_bundles(workspace)?
+        .into_iter()
+        .filter(|b| b.definition.name != name)
+        .collect();
+
+    let mut removing = Vec::new();
+    for skill_name in &installed.skills {
+        let shared = bundles.iter().any(|b| b.skills.contains(skill_name));
+        if keep_skills.unwrap_or(false) || shared || !is_skill_installed(skill_name, workspace) {
+            result.unchanged.push(skill_name.clone());
+        } else {
+            removing.push(skill_name.clone());
+        }
+    }
+    uninstall_bundle_skills(removing, workspace, &mut result);
+
+    save_installed_bundles(workspace, bundles)?;
+    println!("🗑️  [Backend] 已移除套件 '{}'，卸载 {} 个 skill", name, result.removed.len());
+    Ok(result)
+}
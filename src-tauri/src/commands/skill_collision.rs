@@ -0,0 +1,217 @@
+// Skill 重名处理：已安装的同名 Skill 来自其他仓库时，由用户选择跳过、替换或以别名安装
+// 别名安装会改写 SKILL.md 的 name，并在注册表、清单和锁文件中记录上游名称，检查更新和更新时按上游名称查找
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skills::{copy_dir_recursive, resolve_local_skill_tool_dirs, resolve_skill_repository};
+
+/// 检测到重名时返回的错误前缀，后接重名列表（JSON）
+pub const SKILL_CONFLICT_PREFIX: &str = "COBALT_SKILL_CONFLICT:";
+
+/// 重名的处理方式
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum SkillCollisionResolution {
+    /// 不安装来源中的这个 skill
+    Skip,
+    /// 卸载已安装的 skill 后安装
+    Replace,
+    /// 以别名安装，保留已安装的 skill
+    Alias { alias: String },
+}
+
+/// 按原名称指定的处理方式
+pub type SkillCollisionResolutions = HashMap<String, SkillCollisionResolution>;
+
+/// 检测到的重名
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillCollision {
+    pub name: String,
+    /// 已安装 skill 的来源，手动放入的 skill 没有来源
+    pub existing_repository: Option<String>,
+    pub incoming_repository: String,
+    pub suggested_alias: String,
+}
+
+/// 处理重名后要安装的 skill
+pub struct SkillInstallCandidate {
+    /// 来源中的目录，签名验证和锁文件 subpath 使用
+    pub origin_path: PathBuf,
+    /// 复制内容的目录，别名安装时为改写 name 后的临时副本
+    pub source_path: PathBuf,
+    pub name: String,
+    /// 别名安装时的上游名称
+    pub upstream_name: Option<String>,
+    /// 先移除已安装的同名 skill
+    pub replace: bool,
+}
+
+impl SkillInstallCandidate {
+    pub fn new(path: PathBuf, name: String) -> Self {
+        Self {
+            origin_path: path.clone(),
+            source_path: path,
+            name,
+            upstream_name: None,
+            replace: false,
+        }
+    }
+
+    /// 以别名安装：复制到临时目录并改写 SKILL.md 的 name
+    pub fn aliased(path: PathBuf, upstream_name: String, alias: String) -> Result<Self, String> {
+        let source_path = stage_aliased_copy(&path, &alias)?;
+        Ok(Self {
+            origin_path: path,
+            source_path,
+            name: alias,
+            upstream_name: Some(upstream_name),
+            replace: false,
+        })
+    }
+
+    /// 删除别名安装用的临时副本
+    pub fn cleanup(&self) {
+        if self.source_path != self.origin_path {
+            let _ = fs::remove_dir_all(&self.source_path);
+        }
+    }
+}
+
+/// 把 skill 复制到临时目录，并把 SKILL.md 的 name 改为别名
+/// 来源可能是用户的本地目录，不能原地修改
+pub fn stage_aliased_copy(skill_dir: &Path, alias: &str) -> Result<PathBuf, String> {
+    let staging_dir = std::env::temp_dir().join(format!("cobalt-skill-alias-{}", alias));
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir).map_err(|e| format!("删除临时目录失败: {}", e))?;
+    }
+    copy_dir_recursive(&skill_dir.to_path_buf(), &staging_dir)?;
+
+    let skill_md = staging_dir.join("SKILL.md");
+    let renamed = fs::read_to_string(&skill_md)
+        .map_err(|e| format!("读取 SKILL.md 失败: {}", e))
+        .map(|content| super::skill_frontmatter::rename_skill_frontmatter(&content, alias))
+        .and_then(|content| fs::write(&skill_md, content).map_err(|e| format!("写入 SKILL.md 失败: {}", e)));
+    if let Err(e) = renamed {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+    Ok(staging_dir)
+}
+
+/// 已安装 skill 的来源；未安装时返回 None
+fn installed_repository(skill_name: &str, workspace_path: Option<&str>) -> Option<Option<String>> {
+    let tool_dirs = resolve_local_skill_tool_dirs(skill_name, workspace_path).ok()?;
+    Some(
+        tool_dirs
+            .iter()
+            .find_map(|(_, dir)| resolve_skill_repository(dir, skill_name, workspace_path)),
+    )
+}
+
+/// 建议的别名：<名称>-<仓库所有者>，已被占用时追加序号
+fn suggest_alias(skill_name: &str, repo_url: &str, workspace_path: Option<&str>) -> String {
+    let segments: Vec<&str> = repo_url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .filter(|s| !s.is_empty())
+        .collect();
+    let owner = segments.get(1).or(segments.first()).copied().unwrap_or("alt");
+    let owner: String = owner
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let base = format!("{}-{}", skill_name, owner.trim_matches('-'));
+
+    let mut alias = base.clone();
+    let mut index = 2;
+    while resolve_local_skill_tool_dirs(&alias, workspace_path).is_ok() {
+        alias = format!("{}-{}", base, index);
+        index += 1;
+    }
+    alias
+}
+
+/// 检查要安装的 skill 是否与已安装的、来自其他仓库的 skill 重名，并按处理方式生成安装项
+/// 存在未指定处理方式的重名时返回 COBALT_SKILL_CONFLICT 错误，不安装任何 skill
+pub fn resolve_collisions(
+    candidates: Vec<(PathBuf, String)>,
+    repo_url: &str,
+    resolutions: Option<&SkillCollisionResolutions>,
+    workspace_path: Option<&str>,
+) -> Result<Vec<SkillInstallCandidate>, String> {
+    let incoming = super::skill_signature::normalize_repo_url(repo_url);
+    let mut planned: Vec<SkillInstallCandidate> = Vec::new();
+    let mut conflicts: Vec<SkillCollision> = Vec::new();
+
+    for (path, name) in candidates {
+        let existing = match installed_repository(&name, workspace_path) {
+            None => {
+                planned.push(SkillInstallCandidate::new(path, name));
+                continue;
+            }
+            Some(existing) => existing,
+        };
+
+        // 来自同一仓库时不算重名，沿用跳过已存在副本的行为
+        let same_source = existing
+            .as_deref()
+            .is_some_and(|repo| super::skill_signature::normalize_repo_url(repo) == incoming);
+        if same_source {
+            planned.push(SkillInstallCandidate::new(path, name));
+            continue;
+        }
+
+        match resolutions.and_then(|r| r.get(&name)) {
+            None => conflicts.push(SkillCollision {
+                suggested_alias: suggest_alias(&name, repo_url, workspace_path),
+                name,
+                existing_repository: existing,
+                incoming_repository: repo_url.to_string(),
+            }),
+            Some(SkillCollisionResolution::Skip) => {
+                println!("⏭️  [Backend] 跳过重名的 skill: {}", name);
+            }
+            Some(SkillCollisionResolution::Replace) => {
+                println!("♻️  [Backend] 替换已安装的 skill: {}", name);
+                planned.push(SkillInstallCandidate {
+                    replace: true,
+                    ..SkillInstallCandidate::new(path, name)
+                });
+            }
+            Some(SkillCollisionResolution::Alias { alias }) => {
+                let alias = alias.trim().to_string();
+                let aliased = super::skill_lint::validate_skill_name(&alias).and_then(|_| {
+                    if resolve_local_skill_tool_dirs(&alias, workspace_path).is_ok()
+                        || planned.iter().any(|c| c.name == alias)
+                    {
+                        return Err(format!("别名 '{}' 已被其他 Skill 使用", alias));
+                    }
+                    println!("🏷️  [Backend] 以别名 {} 安装 {}", alias, name);
+                    SkillInstallCandidate::aliased(path, name, alias)
+                });
+                match aliased {
+                    Ok(candidate) => planned.push(candidate),
+                    Err(e) => {
+                        planned.iter().for_each(SkillInstallCandidate::cleanup);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    if !conflicts.is_empty() {
+        planned.iter().for_each(SkillInstallCandidate::cleanup);
+        let names: Vec<&str> = conflicts.iter().map(|c| c.name.as_str()).collect();
+        eprintln!("⚠️  [Backend] 已安装来自其他来源的同名 skill: {}", names.join(", "));
+        let payload = serde_json::to_string(&conflicts).map_err(|e| format!("序列化重名信息失败: {}", e))?;
+        return Err(format!("{}{}", SKILL_CONFLICT_PREFIX, payload));
+    }
+
+    Ok(planned)
+}
@@ -0,0 +1,363 @@
+// Skill 依赖：SKILL.md frontmatter 的 requires 字段声明依赖的其他 Skill
+// 安装时自动安装缺少的依赖（同一来源 > 指定仓库 > 市场源），卸载或禁用时提示会受影响的 Skill
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skills::{
+    get_all_tool_skills_dirs, get_all_tool_workspace_skills_dirs, parse_skill_frontmatter,
+    resolve_local_skill_tool_dirs,
+};
+
+/// 卸载或禁用会影响其他 Skill 时返回的错误前缀，后接受影响的 skill 名称（JSON 数组）
+pub const DEPENDENTS_WARNING_PREFIX: &str = "COBALT_DEPENDENTS_WARNING:";
+
+/// frontmatter 中声明的依赖
+///
+/// ```yaml
+/// requires:
+///   - helper-skill                          # 同一来源或已添加的市场源中的 skill
+///   - name: git-helper
+///     repo: https://github.com/org/skills    # 指定来源仓库
+///     ref: v1.0.0
+///   - name: lint-helper
+///     source: official                       # 指定市场源 ID
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillRequirement {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// 市场源 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl SkillRequirement {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            repo: None,
+            git_ref: None,
+            source: None,
+        }
+    }
+}
+
+/// 解析 requires 字段，支持逗号分隔字符串、名称列表或带来源的映射列表
+pub fn parse_requirements(value: Option<&serde_yaml::Value>) -> Vec<SkillRequirement> {
+    let text = |value: Option<&serde_yaml::Value>| {
+        value
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let mut requirements: Vec<SkillRequirement> = Vec::new();
+    let items: Vec<SkillRequirement> = match value {
+        Some(serde_yaml::Value::String(s)) => s.split(',').map(|name| SkillRequirement::named(name.trim())).collect(),
+        Some(serde_yaml::Value::Sequence(seq)) => seq
+            .iter()
+            .filter_map(|item| match item {
+                serde_yaml::Value::String(name) => Some(SkillRequirement::named(name.trim())),
+                serde_yaml::Value::Mapping(_) => Some(SkillRequirement {
+                    name: text(item.get("name"))?,
+                    repo: text(item.get("repo")),
+                    git_ref: text(item.get("ref")),
+                    source: text(item.get("source")),
+                }),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    for item in items {
+        if !item.name.is_empty() && !requirements.iter().any(|r| r.name == item.name) {
+            requirements.push(item);
+        }
+    }
+    requirements
+}
+
+fn is_skill_installed(skill_name: &str, workspace_path: Option<&str>) -> bool {
+    resolve_local_skill_tool_dirs(skill_name, workspace_path).is_ok()
+}
+
+fn read_requirements(skill_dir: &Path, default_name: &str) -> Option<(String, Vec<SkillRequirement>)> {
+    let content = fs::read_to_string(skill_dir.join("SKILL.md")).ok()?;
+    let metadata = parse_skill_frontmatter(&content, default_name)?;
+    Some((metadata.name, metadata.requires))
+}
+
+/// 来源目录中的 skill 及其依赖，命名规则与安装时一致
+fn collect_source_skills(source_dir: &Path, fallback_name: Option<&str>) -> Vec<(String, Vec<SkillRequirement>)> {
+    if source_dir.join("SKILL.md").exists() {
+        let dir_name = source_dir.file_name().and_then(|n| n.to_str()).unwrap_or("skill");
+        return read_requirements(source_dir, "")
+            .map(|(name, requires)| {
+                let name = Some(name.trim())
+                    .filter(|n| !n.is_empty())
+                    .or(fallback_name)
+                    .unwrap_or(dir_name)
+                    .to_string();
+                (name, requires)
+            })
+            .into_iter()
+            .collect();
+    }
+
+    let Ok(entries) = fs::read_dir(source_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().join("SKILL.md").exists())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let requires = read_requirements(&entry.path(), &name).map(|(_, r)| r).unwrap_or_default();
+            Some((name, requires))
+        })
+        .collect()
+}
+
+/// 安装前的依赖解析结果
+pub struct DependencyPlan {
+    /// 补全依赖后要安装的 skill，None 表示安装来源中的全部 skill
+    pub skill_names: Option<Vec<String>>,
+    /// 需要从其他来源安装的依赖
+    pub external: Vec<SkillRequirement>,
+}
+
+/// 补全要安装的 skill 的依赖：同一来源中能找到的依赖一起安装，其余交给 install_external_dependencies
+pub fn plan_dependencies(
+    source_dir: &Path,
+    repo_url: &str,
+    selected: Option<&Vec<String>>,
+    fallback_name: Option<&str>,
+    workspace_path: Option<&str>,
+) -> DependencyPlan {
+    let available = collect_source_skills(source_dir, fallback_name);
+    let mut names: Vec<String> = match selected {
+        Some(selected) => selected.clone(),
+        None => available.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    let mut external: Vec<SkillRequirement> = Vec::new();
+    let same_repo = super::skill_signature::normalize_repo_url(repo_url);
+
+    let mut index = 0;
+    while index < names.len() {
+        let requires = available
+            .iter()
+            .find(|(name, _)| *name == names[index])
+            .map(|(_, requires)| requires.clone())
+            .unwrap_or_default();
+        index += 1;
+
+        for requirement in requires {
+            if names.contains(&requirement.name)
+                || external.iter().any(|r| r.name == requirement.name)
+                || is_skill_installed(&requirement.name, workspace_path)
+            {
+                continue;
+            }
+
+            let from_same_source = requirement.source.is_none()
+                && requirement
+                    .repo
+                    .as_deref()
+                    .is_none_or(|repo| super::skill_signature::normalize_repo_url(repo) == same_repo)
+                && available.iter().any(|(name, _)| *name == requirement.name);
+            if from_same_source {
+                println!("🔗 [Backend] 同时安装依赖: {}", requirement.name);
+                names.push(requirement.name);
+            } else {
+                external.push(requirement);
+            }
+        }
+    }
+
+    DependencyPlan {
+        skill_names: selected.map(|_| names),
+        external,
+    }
+}
+
+/// 依赖安装结果
+#[derive(Debug, Default)]
+pub struct DependencyInstallOutcome {
+    pub installed: Vec<String>,
+    /// 无法安装的依赖及原因
+    pub missing: Vec<String>,
+}
+
+impl DependencyInstallOutcome {
+    /// 追加到安装结果消息末尾的说明
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.installed.is_empty() {
+            parts.push(format!("；已安装依赖: {}", self.installed.join(", ")));
+        }
+        if !self.missing.is_empty() {
+            parts.push(format!("；缺少依赖: {}", self.missing.join(", ")));
+        }
+        parts.concat()
+    }
+}
+
+/// 在已启用的市场源中查找依赖，返回 (源 ID, 仓库地址)；优先级高的源优先
+fn find_in_marketplace(requirement: &SkillRequirement) -> Option<(String, String)> {
+    let config = super::marketplace::read_marketplace_config().ok()?;
+    let mut sources: Vec<_> = config
+        .sources
+        .into_iter()
+        .filter(|s| s.enabled)
+        .filter(|s| requirement.source.as_ref().is_none_or(|id| *id == s.id))
+        .collect();
+    sources.sort_by_key(|s| std::cmp::Reverse(s.priority));
+
+    sources.into_iter().find_map(|source| {
+        let cache = super::marketplace::read_marketplace_cache(&source.id).ok()?;
+        cache
+            .skills
+            .iter()
+            .any(|skill| skill.name == requirement.name)
+            .then(|| (source.id.clone(), source.url.clone()))
+    })
+}
+
+/// 从其他仓库或市场源安装依赖，依赖自身的依赖也会继续安装
+/// 依赖来自其他仓库，不使用原仓库的认证信息
+pub fn install_external_dependencies(
+    pending: Vec<SkillRequirement>,
+    target_dirs: &Vec<(String, PathBuf)>,
+    workspace_path: Option<&str>,
+    install_mode: super::skill_links::SkillInstallMode,
+    security_threshold: Option<super::skill_security::SecuritySeverity>,
+) -> DependencyInstallOutcome {
+    let mut outcome = DependencyInstallOutcome::default();
+    let mut attempted: Vec<String> = Vec::new();
+    let mut pending = pending;
+
+    while let Some(requirement) = pending.pop() {
+        if attempted.contains(&requirement.name) || is_skill_installed(&requirement.name, workspace_path) {
+            continue;
+        }
+        attempted.push(requirement.name.clone());
+
+        let (source_id, repo_url) = match requirement.repo.clone() {
+            Some(repo) => (None, repo),
+            None => match find_in_marketplace(&requirement) {
+                Some((source_id, url)) => (Some(source_id), url),
+                None => {
+                    let reason = match requirement.source {
+                        Some(ref id) => format!("市场源 {} 中未找到", id),
+                        None => "未指定来源，已添加的市场源中也未找到".to_string(),
+                    };
+                    eprintln!("⚠️  [Backend] 无法解析依赖 {}: {}", requirement.name, reason);
+                    outcome.missing.push(format!("{}（{}）", requirement.name, reason));
+                    continue;
+                }
+            },
+        };
+
+        println!("🔗 [Backend] 安装依赖 {}（来自 {}）", requirement.name, repo_url);
+        let result = super::skills::install_skills_from_repo_source(
+            &repo_url,
+            requirement.git_ref.clone(),
+            Some(&vec![requirement.name.clone()]),
+            target_dirs,
+            workspace_path,
+            None,
+            install_mode,
+            security_threshold,
+            None,
+        );
+        match result {
+            Ok((installed, external)) => {
+                if workspace_path.is_none() {
+                    if let Some(ref source_id) = source_id {
+                        if let Err(e) = set_skill_source_id(&installed, source_id) {
+                            eprintln!("⚠️  [Backend] 记录依赖的市场源失败: {}", e);
+                        }
+                    }
+                }
+                outcome.installed.extend(installed);
+                pending.extend(external);
+            }
+            Err(e) => {
+                let reason = if e.starts_with(super::skills::SECURITY_BLOCKED_PREFIX) {
+                    "安全扫描发现风险，已取消安装".to_string()
+                } else if e.starts_with(super::skill_collision::SKILL_CONFLICT_PREFIX) {
+                    "已安装来自其他来源的同名 skill".to_string()
+                } else {
+                    e
+                };
+                eprintln!("⚠️  [Backend] 安装依赖 {} 失败: {}", requirement.name, reason);
+                outcome.missing.push(format!("{}（{}）", requirement.name, reason));
+            }
+        }
+    }
+
+    outcome
+}
+
+fn set_skill_source_id(skill_names: &[String], source_id: &str) -> Result<(), String> {
+    let mut registry = super::skills::read_skill_registry()?;
+    for entry in registry.skills.iter_mut().filter(|s| skill_names.contains(&s.name)) {
+        if let Some(ref mut metadata) = entry.metadata {
+            metadata.source_id = Some(source_id.to_string());
+        }
+    }
+    super::skills::write_skill_registry(registry)
+}
+
+/// 已启用的 skill 中依赖 skill_name 的 skill
+pub fn find_dependents(skill_name: &str, workspace_path: Option<&str>) -> Vec<String> {
+    let tool_dirs = match workspace_path {
+        Some(ws_path) => get_all_tool_workspace_skills_dirs(&PathBuf::from(ws_path)),
+        None => get_all_tool_skills_dirs(),
+    };
+
+    let mut dependents: Vec<String> = Vec::new();
+    for (_, tool_dir) in tool_dirs {
+        let Ok(entries) = fs::read_dir(&tool_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if name == skill_name || dependents.contains(&name) {
+                continue;
+            }
+            let requires_skill = read_requirements(&entry.path(), &name)
+                .is_some_and(|(_, requires)| requires.iter().any(|r| r.name == skill_name));
+            if requires_skill {
+                dependents.push(name);
+            }
+        }
+    }
+
+    dependents.sort();
+    dependents
+}
+
+/// 卸载或禁用前检查依赖它的 skill；force 为 true 时跳过检查
+pub fn check_dependents(skill_name: &str, workspace_path: Option<&str>, force: bool) -> Result<(), String> {
+    if force {
+        return Ok(());
+    }
+
+    let dependents = find_dependents(skill_name, workspace_path);
+    if dependents.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!("⚠️  [Backend] {} 被以下 skill 依赖: {}", skill_name, dependents.join(", "));
+    let payload = serde_json::to_string(&dependents).map_err(|e| format!("序列化依赖信息失败: {}", e))?;
+    Err(format!("{}{}", DEPENDENTS_WARNING_PREFIX, payload))
+}
@@ -0,0 +1,236 @@
+// Skill 导出：把一个或多个 Skill 打包为 .zip / .tar.gz 归档，可通过本地安装重新导入
+// 归档结构：cobalt-export.json（索引）+ skills/<name>/（含重新生成的 .manifest.json）
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::skill_local::ArchiveKind;
+use super::skill_signature::{SIGNATURE_FILE_NAME, SIGNED_MANIFEST_FILE_NAME};
+use super::skills::{generate_skill_manifest, read_skill_manifest, resolve_local_skill_tool_dirs};
+
+/// 归档根目录下的索引文件
+pub const EXPORT_INDEX_FILE_NAME: &str = "cobalt-export.json";
+
+/// 导出归档的索引
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillExportIndex {
+    pub version: u32,
+    pub exported_at: String,
+    pub skills: Vec<SkillExportEntry>,
+}
+
+/// 索引中的单个 Skill
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillExportEntry {
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 相对归档根目录的路径
+    pub path: String,
+    pub file_count: usize,
+}
+
+/// 导出结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillExportResult {
+    pub output_path: String,
+    pub skills: Vec<String>,
+    pub file_count: usize,
+}
+
+/// 待写入归档的文件
+struct ExportFile {
+    name: String,
+    content: Vec<u8>,
+    mode: u32,
+}
+
+/// 导出 Skills 为归档，格式由 output_path 的扩展名决定（.zip、.tar.gz、.tgz、.tar）
+#[tauri::command]
+pub async fn export_skill(
+    skill_names: Vec<String>,
+    output_path: String,
+    workspace_path: Option<String>,
+) -> Result<SkillExportResult, String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in skill_names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        if !names.iter().any(|existing| existing == name) {
+            names.push(name.to_string());
+        }
+    }
+    if names.is_empty() {
+        return Err("请至少选择一个要导出的 Skill".to_string());
+    }
+
+    let output = PathBuf::from(output_path.trim());
+    let kind = ArchiveKind::detect(&output)
+        .ok_or_else(|| format!("不支持的导出格式: {}（支持 .zip、.tar.gz、.tgz、.tar）", output.display()))?;
+
+    println!("📦 [Backend] 导出 Skills {:?} 到 {:?}", names, output);
+
+    let mut files = Vec::new();
+    let mut index = SkillExportIndex {
+        version: 1,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        skills: Vec::new(),
+    };
+    for name in &names {
+        let (entry, skill_files) = collect_skill_files(name, workspace_path.as_deref())?;
+        index.skills.push(entry);
+        files.extend(skill_files);
+    }
+
+    let index_content = serde_json::to_vec_pretty(&index).map_err(|e| format!("序列化导出索引失败: {}", e))?;
+    files.insert(0, ExportFile {
+        name: EXPORT_INDEX_FILE_NAME.to_string(),
+        content: index_content,
+        mode: 0o644,
+    });
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("创建导出目录失败: {}", e))?;
+    }
+
+    // 先写入临时文件，完成后再替换，避免留下不完整的归档
+    let partial_path = PathBuf::from(format!("{}.partial", output.to_string_lossy()));
+    let written = match kind {
+        ArchiveKind::Zip => write_zip(&partial_path, &files),
+        ArchiveKind::TarGz => fs::File::create(&partial_path)
+            .map_err(|e| format!("创建归档失败: {}", e))
+            .and_then(|file| {
+                let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+                write_tar(encoder, &files)?
+                    .finish()
+                    .map(|_| ())
+                    .map_err(|e| format!("写入归档失败: {}", e))
+            }),
+        ArchiveKind::Tar => fs::File::create(&partial_path)
+            .map_err(|e| format!("创建归档失败: {}", e))
+            .and_then(|file| write_tar(file, &files).map(|_| ())),
+    };
+    if let Err(e) = written.and_then(|_| fs::rename(&partial_path, &output).map_err(|e| format!("保存归档失败: {}", e))) {
+        let _ = fs::remove_file(&partial_path);
+        return Err(e);
+    }
+
+    let file_count = index.skills.iter().map(|s| s.file_count).sum();
+    println!("✅ [Backend] 已导出 {} 个 skill，共 {} 个文件", names.len(), file_count);
+
+    Ok(SkillExportResult {
+        output_path: output.to_string_lossy().to_string(),
+        skills: names,
+        file_count,
+    })
+}
+
+/// 读取 Skill 的内容文件，并生成新的 .manifest.json
+/// 只导出清单中的文件（不含格式适配生成的文件和隐藏文件），签名文件原样带上
+fn collect_skill_files(skill_name: &str, workspace_path: Option<&str>) -> Result<(SkillExportEntry, Vec<ExportFile>), String> {
+    let tool_dirs = resolve_local_skill_tool_dirs(skill_name, workspace_path)?;
+    let skill_dir = tool_dirs
+        .iter()
+        .find(|(tool_name, _)| tool_name == "claude-code")
+        .or_else(|| tool_dirs.first())
+        .map(|(_, dir)| dir.clone())
+        .ok_or_else(|| format!("Skill '{}' 不存在", skill_name))?;
+
+    if !skill_dir.join("SKILL.md").exists() {
+        return Err(format!("Skill '{}' 缺少 SKILL.md，无法导出", skill_name));
+    }
+
+    // 保留远程仓库来源，本地路径对接收方没有意义
+    let stored = read_skill_manifest(&skill_dir)
+        .filter(|m| m.repository.as_deref().is_some_and(|r| super::skill_local::local_source_path(r).is_none()));
+    let mut manifest = generate_skill_manifest(&skill_dir, stored.as_ref().and_then(|m| m.repository.as_deref()))?;
+    if let Some(stored) = stored {
+        manifest.commit = stored.commit;
+        manifest.git_ref = stored.git_ref;
+    }
+
+    let prefix = format!("skills/{}", skill_name);
+    let mut files = Vec::new();
+    let mut relative_paths: Vec<String> = manifest.files.iter().map(|f| f.path.replace('\\', "/")).collect();
+    for extra in [SIGNED_MANIFEST_FILE_NAME, SIGNATURE_FILE_NAME] {
+        if skill_dir.join(extra).is_file() {
+            relative_paths.push(extra.to_string());
+        }
+    }
+
+    for relative in &relative_paths {
+        let path = skill_dir.join(relative);
+        let content = fs::read(&path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+        files.push(ExportFile {
+            name: format!("{}/{}", prefix, relative),
+            content,
+            mode: file_mode(&path),
+        });
+    }
+
+    let manifest_content = serde_json::to_vec_pretty(&manifest).map_err(|e| format!("序列化清单失败: {}", e))?;
+    files.push(ExportFile {
+        name: format!("{}/.manifest.json", prefix),
+        content: manifest_content,
+        mode: 0o644,
+    });
+
+    let entry = SkillExportEntry {
+        name: skill_name.to_string(),
+        version: manifest.version.clone(),
+        description: manifest.description.clone(),
+        path: prefix,
+        file_count: files.len(),
+    };
+    Ok((entry, files))
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).map(|m| m.permissions().mode() & 0o777).unwrap_or(0o644)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> u32 {
+    0o644
+}
+
+fn write_zip(path: &Path, files: &[ExportFile]) -> Result<(), String> {
+    let file = fs::File::create(path).map_err(|e| format!("创建归档失败: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    for export_file in files {
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(export_file.mode);
+        zip.start_file(export_file.name.as_str(), options)
+            .map_err(|e| format!("写入 {} 失败: {}", export_file.name, e))?;
+        zip.write_all(&export_file.content)
+            .map_err(|e| format!("写入 {} 失败: {}", export_file.name, e))?;
+    }
+
+    zip.finish().map_err(|e| format!("写入归档失败: {}", e))?;
+    Ok(())
+}
+
+fn write_tar<W: Write>(writer: W, files: &[ExportFile]) -> Result<W, String> {
+    let mut builder = tar::Builder::new(writer);
+    let mtime = chrono::Utc::now().timestamp().max(0) as u64;
+
+    for export_file in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(export_file.content.len() as u64);
+        header.set_mode(export_file.mode);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, &export_file.name, export_file.content.as_slice())
+            .map_err(|e| format!("写入 {} 失败: {}", export_file.name, e))?;
+    }
+
+    builder.into_inner().map_err(|e| format!("写入归档失败: {}", e))
+}
@@ -0,0 +1,399 @@
+// Skill frontmatter 编辑：按行修改 SKILL.md 中的单个字段，保留其他字段的顺序、注释和正文
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+use super::skills::{
+    read_skill_metadata, read_skill_registry, resolve_local_skill_tool_dirs, write_skill_registry,
+};
+
+/// 要修改的 frontmatter 字段，None 表示不修改，空字符串或空列表表示删除该字段
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillFrontmatterUpdate {
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub ai_tools: Option<Vec<String>>,
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+/// 列表字段的写法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListStyle {
+    /// key: a, b
+    Comma,
+    /// key: [a, b]
+    Flow,
+    /// key:\n  - a\n  - b（记录列表项的缩进）
+    Block,
+}
+
+/// 按行编辑 SKILL.md 的 frontmatter
+struct FrontmatterEditor {
+    lines: Vec<String>,
+    /// 结束 `---` 所在行的下标
+    end: usize,
+    line_ending: &'static str,
+    trailing_newline: bool,
+}
+
+impl FrontmatterEditor {
+    fn parse(content: &str, skill_name: &str) -> Self {
+        let line_ending = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let trailing_newline = content.ends_with('\n') || content.is_empty();
+
+        let end = if lines.first().map(|l| l.trim_end()) == Some("---") {
+            lines.iter().skip(1).position(|l| l.trim_end() == "---").map(|i| i + 1)
+        } else {
+            None
+        };
+
+        // 没有 frontmatter 时补上，name 取目录名
+        let end = end.unwrap_or_else(|| {
+            lines.splice(
+                0..0,
+                ["---".to_string(), format!("name: {}", skill_name), "---".to_string()],
+            );
+            2
+        });
+
+        Self {
+            lines,
+            end,
+            line_ending,
+            trailing_newline,
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut content = self.lines.join(self.line_ending);
+        if self.trailing_newline {
+            content.push_str(self.line_ending);
+        }
+        content
+    }
+
+    /// 顶层字段占用的行范围（字段行及其缩进的续行）
+    fn key_range(&self, key: &str) -> Option<(usize, usize)> {
+        let prefix = format!("{}:", key);
+        let start = (1..self.end).find(|&i| self.lines[i].starts_with(&prefix))?;
+
+        let mut end = start + 1;
+        while end < self.end {
+            let line = &self.lines[end];
+            if is_continuation(line) {
+                end += 1;
+                continue;
+            }
+            // 空行之后仍是续行时（如多行字符串中的空行）一并包含
+            if line.trim().is_empty() {
+                let next = (end + 1..self.end).find(|&i| !self.lines[i].trim().is_empty());
+                if let Some(next) = next {
+                    if is_continuation(&self.lines[next]) {
+                        end = next + 1;
+                        continue;
+                    }
+                }
+            }
+            break;
+        }
+        Some((start, end))
+    }
+
+    /// 替换或删除字段，字段不存在时追加到 frontmatter 末尾
+    fn set(&mut self, key: &str, rendered: Option<Vec<String>>) {
+        let range = self.key_range(key);
+        let new_lines = rendered.unwrap_or_default();
+        let new_len = new_lines.len();
+
+        match range {
+            Some((start, end)) => {
+                self.lines.splice(start..end, new_lines);
+                self.end = self.end - (end - start) + new_len;
+            }
+            None => {
+                self.lines.splice(self.end..self.end, new_lines);
+                self.end += new_len;
+            }
+        }
+    }
+
+    /// 当前字段列表使用的写法
+    fn list_style(&self, key: &str) -> Option<(ListStyle, String)> {
+        let (start, end) = self.key_range(key)?;
+        let inline = self.lines[start][key.len() + 1..].trim();
+        if inline.starts_with('[') {
+            return Some((ListStyle::Flow, String::new()));
+        }
+        if inline.is_empty() {
+            if let Some(item) = self.lines[start + 1..end].iter().find(|l| l.trim_start().starts_with('-')) {
+                let indent = item[..item.len() - item.trim_start().len()].to_string();
+                return Some((ListStyle::Block, indent));
+            }
+        }
+        Some((ListStyle::Comma, String::new()))
+    }
+
+    fn set_string(&mut self, key: &str, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            self.set(key, None);
+            return;
+        }
+        let scalar = yaml_scalar(value);
+        let mut scalar_lines = scalar.lines();
+        let mut lines = vec![format!("{}: {}", key, scalar_lines.next().unwrap_or_default())];
+        lines.extend(scalar_lines.map(str::to_string));
+        self.set(key, Some(lines));
+    }
+
+    fn set_list(&mut self, key: &str, items: &[String], default_style: ListStyle) {
+        let items: Vec<&str> = items.iter().map(|i| i.trim()).filter(|i| !i.is_empty()).collect();
+        if items.is_empty() {
+            self.set(key, None);
+            return;
+        }
+
+        let (style, indent) = self
+            .list_style(key)
+            .unwrap_or((default_style, "  ".to_string()));
+        let lines = match style {
+            ListStyle::Comma => vec![format!("{}: {}", key, yaml_scalar(&items.join(", ")))],
+            ListStyle::Flow => vec![format!(
+                "{}: [{}]",
+                key,
+                items.iter().map(|i| flow_item(i)).collect::<Vec<_>>().join(", ")
+            )],
+            ListStyle::Block => {
+                let mut lines = vec![format!("{}:", key)];
+                lines.extend(items.iter().map(|i| format!("{}- {}", indent, yaml_scalar(i))));
+                lines
+            }
+        };
+        self.set(key, Some(lines));
+    }
+}
+
+fn is_continuation(line: &str) -> bool {
+    line.starts_with(' ') || line.starts_with('\t') || line.starts_with("- ") || line == "-"
+}
+
+/// 单行或多行字符串的 YAML 写法（需要时由 serde_yaml 加引号）
+fn yaml_scalar(value: &str) -> String {
+    serde_yaml::to_string(&serde_yaml::Value::String(value.to_string()))
+        .map(|s| s.trim_end().to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// flow 列表中的元素，包含分隔符等特殊字符时用双引号
+fn flow_item(value: &str) -> String {
+    let needs_quotes = value
+        .chars()
+        .any(|c| matches!(c, ',' | '[' | ']' | '{' | '}' | '#' | ':' | '"' | '\'' | '&' | '*' | '!' | '|' | '>' | '%' | '@' | '`'));
+    if needs_quotes {
+        serde_json::to_string(value).unwrap_or_else(|_| value.to_string())
+    } else {
+        value.to_string()
+    }
+}
+
+/// 按更新内容修改 SKILL.md 文本
+fn apply_frontmatter_update(content: &str, skill_name: &str, update: &SkillFrontmatterUpdate) -> String {
+    let mut editor = FrontmatterEditor::parse(content, skill_name);
+    if let Some(ref description) = update.description {
+        editor.set_string("description", description);
+    }
+    if let Some(ref tags) = update.tags {
+        editor.set_list("tags", tags, ListStyle::Flow);
+    }
+    if let Some(ref ai_tools) = update.ai_tools {
+        editor.set_list("ai-tools", ai_tools, ListStyle::Comma);
+    }
+    if let Some(ref allowed_tools) = update.allowed_tools {
+        editor.set_list("allowed-tools", allowed_tools, ListStyle::Comma);
+    }
+    editor.render()
+}
+
+/// 改写 SKILL.md 的 name 字段（别名安装），其余内容保持不变
+pub fn rename_skill_frontmatter(content: &str, name: &str) -> String {
+    let mut editor = FrontmatterEditor::parse(content, name);
+    editor.set_string("name", name);
+    editor.render()
+}
+
+/// 修改 Skill 的 frontmatter 字段，并同步到所有工具副本和注册表
+#[tauri::command]
+pub fn update_skill_frontmatter(
+    skill_name: String,
+    update: SkillFrontmatterUpdate,
+    workspace_path: Option<String>,
+) -> Result<String, String> {
+    println!("📝 [Backend] 修改 Skill '{}' 的 frontmatter", skill_name);
+
+    let tool_dirs = resolve_local_skill_tool_dirs(&skill_name, workspace_path.as_deref())?;
+    let mut txn = super::skill_transaction::SkillTransaction::begin(&format!("修改 Skill '{}' ", skill_name))?;
+
+    // 链接模式下多个工具共用一份规范副本，只写一次
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut has_linked_copy = false;
+    for (tool_name, skill_dir) in &tool_dirs {
+        if super::skill_links::linked_store_dir(skill_dir, workspace_path.as_deref()).is_some() {
+            has_linked_copy = true;
+        }
+        let skill_md_path = skill_dir.join("SKILL.md");
+        let canonical = fs::canonicalize(&skill_md_path).unwrap_or_else(|_| skill_md_path.clone());
+        if written.contains(&canonical) {
+            continue;
+        }
+
+        let content = fs::read_to_string(&skill_md_path)
+            .map_err(|e| format!("读取 {} 的 SKILL.md 失败: {}", tool_name, e))?;
+        let updated = apply_frontmatter_update(&content, &skill_name, &update);
+
+        txn.snapshot_file(&canonical)?;
+        super::skill_transaction::write_file_atomic(&canonical, updated.as_bytes())?;
+        written.push(canonical);
+    }
+
+    let parsed = tool_dirs
+        .first()
+        .and_then(|(_, dir)| read_skill_metadata(dir, &skill_name))
+        .ok_or_else(|| "修改后的 frontmatter 无法解析".to_string())?;
+
+    // 只在全局模式下更新注册表
+    if workspace_path.is_none() {
+        let mut registry = read_skill_registry()?;
+        if let Some(entry) = registry.skills.iter_mut().find(|s| s.name == skill_name) {
+            entry.description = parsed.description.clone();
+            match entry.metadata {
+                Some(ref mut meta) => meta.apply_frontmatter(parsed),
+                None => entry.metadata = Some(parsed),
+            }
+            txn.snapshot_file(&super::skills::get_skill_registry_path()?)?;
+            write_skill_registry(registry)?;
+        }
+    }
+
+    // 重新生成各工具需要的格式文件；失败时撤销修改，并按恢复后的 SKILL.md 重新生成
+    if let Err(e) = regenerate_skill_formats(&skill_name, &tool_dirs, workspace_path.as_deref(), has_linked_copy) {
+        drop(txn);
+        let _ = regenerate_skill_formats(&skill_name, &tool_dirs, workspace_path.as_deref(), has_linked_copy);
+        return Err(e);
+    }
+    txn.commit();
+
+    println!("✅ [Backend] 已更新 {} 个 SKILL.md", written.len());
+    Ok(format!("已更新 {} 个 SKILL.md", written.len()))
+}
+
+fn regenerate_skill_formats(
+    skill_name: &str,
+    tool_dirs: &[(String, PathBuf)],
+    workspace_path: Option<&str>,
+    has_linked_copy: bool,
+) -> Result<(), String> {
+    for (tool_name, skill_dir) in tool_dirs {
+        if super::skill_links::linked_store_dir(skill_dir, workspace_path).is_some() {
+            continue;
+        }
+        super::skill_adapters::apply_skill_format(tool_name, skill_dir, skill_name)
+            .map_err(|e| format!("转换 {} 的 {} 格式失败: {}", skill_name, tool_name, e))?;
+    }
+    if has_linked_copy {
+        super::skill_links::sync_linked_skill_formats(skill_name, workspace_path)
+            .map_err(|e| format!("生成 {} 的格式文件失败: {}", skill_name, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(description: Option<&str>, tags: Option<&[&str]>) -> SkillFrontmatterUpdate {
+        SkillFrontmatterUpdate {
+            description: description.map(str::to_string),
+            tags: tags.map(|tags| tags.iter().map(|t| t.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn parse_yaml(content: &str) -> serde_yaml::Value {
+        let frontmatter = content.split("---").nth(1).unwrap();
+        serde_yaml::from_str(frontmatter).unwrap()
+    }
+
+    #[test]
+    fn keeps_other_fields_comments_and_body() {
+        let content = "---\nname: demo\n# 注释\ndescription: old\nversion: 1.0.0\n---\n# Body\n";
+        let updated = apply_frontmatter_update(content, "demo", &update(Some("new"), None));
+        assert_eq!(updated, "---\nname: demo\n# 注释\ndescription: new\nversion: 1.0.0\n---\n# Body\n");
+    }
+
+    #[test]
+    fn quotes_special_characters() {
+        let content = "---\nname: demo\ndescription: old\n---\n";
+        for value in ["key: value", "#not a comment", "yes", "123", "- item", "'quoted'", "\"double\""] {
+            let updated = apply_frontmatter_update(content, "demo", &update(Some(value), None));
+            let yaml = parse_yaml(&updated);
+            assert_eq!(yaml["description"].as_str(), Some(value), "{}", updated);
+        }
+    }
+
+    #[test]
+    fn replaces_multiline_values() {
+        let content = "---\nname: demo\ndescription: |\n  第一行\n\n  第二行\nversion: 1.0.0\n---\nbody\n";
+        let updated = apply_frontmatter_update(content, "demo", &update(Some("单行"), None));
+        assert_eq!(updated, "---\nname: demo\ndescription: 单行\nversion: 1.0.0\n---\nbody\n");
+
+        let updated = apply_frontmatter_update(content, "demo", &update(Some("a\nb"), None));
+        let yaml = parse_yaml(&updated);
+        assert_eq!(yaml["description"].as_str(), Some("a\nb"));
+        assert_eq!(yaml["version"].as_str(), Some("1.0.0"));
+        assert!(updated.ends_with("---\nbody\n"));
+    }
+
+    #[test]
+    fn empty_value_removes_field() {
+        let content = "---\nname: demo\ndescription: old\ntags: [a, b]\n---\n";
+        let updated = apply_frontmatter_update(content, "demo", &update(Some(""), Some(&[])));
+        assert_eq!(updated, "---\nname: demo\n---\n");
+    }
+
+    #[test]
+    fn adds_frontmatter_when_missing() {
+        let content = "# 没有 frontmatter\n";
+        let updated = apply_frontmatter_update(content, "demo", &update(Some("描述"), None));
+        assert_eq!(updated, "---\nname: demo\ndescription: 描述\n---\n# 没有 frontmatter\n");
+    }
+
+    #[test]
+    fn keeps_list_style() {
+        let flow = "---\nname: demo\ntags: [a]\n---\n";
+        let updated = apply_frontmatter_update(flow, "demo", &update(None, Some(&["x", "y,z"])));
+        assert_eq!(updated, "---\nname: demo\ntags: [x, \"y,z\"]\n---\n");
+
+        let block = "---\nname: demo\ntags:\n    - a\n    - b\n---\n";
+        let updated = apply_frontmatter_update(block, "demo", &update(None, Some(&["x"])));
+        assert_eq!(updated, "---\nname: demo\ntags:\n    - x\n---\n");
+
+        let missing = "---\nname: demo\n---\n";
+        let updated = apply_frontmatter_update(missing, "demo", &update(None, Some(&["x", "y"])));
+        assert_eq!(updated, "---\nname: demo\ntags: [x, y]\n---\n");
+    }
+
+    #[test]
+    fn preserves_crlf_line_endings() {
+        let content = "---\r\nname: demo\r\ndescription: old\r\n---\r\nbody\r\n";
+        let updated = apply_frontmatter_update(content, "demo", &update(Some("new"), None));
+        assert_eq!(updated, "---\r\nname: demo\r\ndescription: new\r\n---\r\nbody\r\n");
+    }
+
+    #[test]
+    fn renames_skill() {
+        let content = "---\nname: upstream\ndescription: x\n---\n";
+        assert_eq!(rename_skill_frontmatter(content, "alias"), "---\nname: alias\ndescription: x\n---\n");
+    }
+}
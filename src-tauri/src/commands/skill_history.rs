@@ -0,0 +1,294 @@
+// Skill 版本历史与回滚命令
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skill_links::{linked_store_dir, sync_linked_skill_formats};
+use super::skill_transaction::SkillTransaction;
+use super::skills::{
+    clear_skill_update_cache, compute_manifest_hash, copy_dir_recursive, generate_skill_manifest,
+    get_skill_registry_path, list_installed_skills, read_skill_manifest, read_skill_metadata,
+    read_skill_registry, resolve_local_skill_tool_dirs, write_skill_registry, SkillManifest, SkillMetadata,
+};
+
+const SKILL_HISTORY_FILE_NAME: &str = "history.json";
+/// 每个 Skill 最多保留的历史版本数
+const MAX_SKILL_HISTORY_ENTRIES: usize = 10;
+
+/// 获取 Skill 历史版本目录
+/// 全局: ~/.cobalt/skills/history/<skill>，工作区: <workspace>/.cobalt/skills/history/<skill>
+pub fn get_skill_history_dir(skill_name: &str, workspace_path: Option<&str>) -> Result<PathBuf, String> {
+    // skill 名称直接作为目录名，防止路径穿越
+    let trimmed = skill_name.trim();
+    if trimmed.is_empty() || trimmed == "." || trimmed.contains(['/', '\\', '\0']) || trimmed.contains("..") {
+        return Err(format!("Skill 名称 '{}' 包含非法路径字符", skill_name));
+    }
+
+    let base_dir = if let Some(ws_path) = workspace_path {
+        PathBuf::from(ws_path).join(".cobalt")
+    } else {
+        dirs::home_dir()
+            .map(|home| home.join(".cobalt"))
+            .ok_or_else(|| "无法获取用户主目录".to_string())?
+    };
+    Ok(base_dir.join("skills").join("history").join(skill_name))
+}
+
+/// 获取 Skill 版本历史索引文件路径
+pub fn get_skill_history_file_path(skill_name: &str, workspace_path: Option<&str>) -> Result<PathBuf, String> {
+    Ok(get_skill_history_dir(skill_name, workspace_path)?.join(SKILL_HISTORY_FILE_NAME))
+}
+
+/// 历史版本条目
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillVersionEntry {
+    pub id: String,
+    /// 快照目录
+    pub backup_path: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub commit: Option<String>,
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// 快照时的清单（文件 hash）
+    #[serde(default)]
+    pub manifest: Option<SkillManifest>,
+    /// 产生快照的操作：update | rollback
+    pub reason: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SkillHistoryFile {
+    #[serde(default)]
+    versions: Vec<SkillVersionEntry>,
+}
+
+fn read_skill_history(history_dir: &Path) -> Result<SkillHistoryFile, String> {
+    let history_path = history_dir.join(SKILL_HISTORY_FILE_NAME);
+    if !history_path.exists() {
+        return Ok(SkillHistoryFile::default());
+    }
+
+    let content = fs::read_to_string(&history_path)
+        .map_err(|e| format!("读取版本历史失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析版本历史失败: {}", e))
+}
+
+fn write_skill_history(history_dir: &Path, history: &SkillHistoryFile) -> Result<(), String> {
+    fs::create_dir_all(history_dir).map_err(|e| format!("创建版本历史目录失败: {}", e))?;
+    let content = serde_json::to_string_pretty(history)
+        .map_err(|e| format!("序列化版本历史失败: {}", e))?;
+    super::skill_transaction::write_file_atomic(&history_dir.join(SKILL_HISTORY_FILE_NAME), content.as_bytes())
+}
+
+/// 将当前 skill 目录保存为历史版本
+/// 超出上限的旧快照不在这里删除，由调用方在操作成功后调用 prune_skill_history
+pub fn record_skill_version(
+    skill_name: &str,
+    skill_dir: &PathBuf,
+    workspace_path: Option<&str>,
+    reason: &str,
+) -> Result<SkillVersionEntry, String> {
+    let history_dir = get_skill_history_dir(skill_name, workspace_path)?;
+    let mut history = read_skill_history(&history_dir)?;
+
+    let now = chrono::Utc::now();
+    let mut id = now.format("%Y%m%d%H%M%S%3f").to_string();
+    while history.versions.iter().any(|v| v.id == id) || history_dir.join(&id).exists() {
+        id.push('0');
+    }
+
+    let snapshot_dir = history_dir.join(&id);
+    copy_dir_recursive(skill_dir, &snapshot_dir).map_err(|e| format!("保存历史版本失败: {}", e))?;
+
+    let manifest = read_skill_manifest(skill_dir).or_else(|| generate_skill_manifest(skill_dir, None).ok());
+    let entry = SkillVersionEntry {
+        id,
+        backup_path: snapshot_dir.to_string_lossy().to_string(),
+        version: manifest.as_ref().map(|m| m.version.clone()).filter(|v| !v.is_empty()),
+        repository: manifest.as_ref().and_then(|m| m.repository.clone()),
+        commit: manifest.as_ref().and_then(|m| m.commit.clone()),
+        git_ref: manifest.as_ref().and_then(|m| m.git_ref.clone()),
+        manifest,
+        reason: reason.to_string(),
+        created_at: now.to_rfc3339(),
+    };
+
+    history.versions.insert(0, entry.clone());
+    write_skill_history(&history_dir, &history)?;
+    println!("🗂️  [Backend] 已保存 Skill '{}' 的历史版本: {}", skill_name, entry.id);
+    Ok(entry)
+}
+
+/// 超出上限时删除最旧的快照
+/// 在更新或回滚成功后调用，避免操作失败时已经删掉了要恢复的快照
+pub fn prune_skill_history(skill_name: &str, workspace_path: Option<&str>) -> Result<(), String> {
+    let history_dir = get_skill_history_dir(skill_name, workspace_path)?;
+    let mut history = read_skill_history(&history_dir)?;
+    if history.versions.len() <= MAX_SKILL_HISTORY_ENTRIES {
+        return Ok(());
+    }
+
+    for oldest in history.versions.split_off(MAX_SKILL_HISTORY_ENTRIES) {
+        if let Err(e) = fs::remove_dir_all(&oldest.backup_path) {
+            eprintln!("⚠️  [Backend] 删除旧的历史版本 {} 失败: {}", oldest.id, e);
+        }
+    }
+    write_skill_history(&history_dir, &history)
+}
+
+/// 列出 Skill 的历史版本（最新的在前）
+#[tauri::command]
+pub fn list_skill_versions(
+    skill_name: String,
+    workspace_path: Option<String>,
+) -> Result<Vec<SkillVersionEntry>, String> {
+    let history_dir = get_skill_history_dir(&skill_name, workspace_path.as_deref())?;
+    Ok(read_skill_history(&history_dir)?.versions)
+}
+
+/// 将 Skill 回滚到指定的历史版本（同步到所有已安装的工具）
+#[tauri::command]
+pub fn rollback_skill(
+    skill_name: String,
+    version_id: String,
+    workspace_path: Option<String>,
+) -> Result<String, String> {
+    println!("⏪ [Backend] 回滚 Skill '{}' 到版本 {}", skill_name, version_id);
+
+    let history_dir = get_skill_history_dir(&skill_name, workspace_path.as_deref())?;
+    let history = read_skill_history(&history_dir)?;
+    let target = history
+        .versions
+        .iter()
+        .find(|v| v.id == version_id)
+        .cloned()
+        .ok_or_else(|| format!("找不到版本 {}", version_id))?;
+
+    let snapshot_dir = PathBuf::from(&target.backup_path);
+    if !snapshot_dir.exists() {
+        return Err(format!("版本 {} 的快照目录不存在", version_id));
+    }
+
+    let installed_by = list_installed_skills(workspace_path.clone())?
+        .into_iter()
+        .find(|skill| skill.name == skill_name)
+        .map(|skill| skill.installed_by)
+        .unwrap_or_default();
+
+    // 包括禁用目录中的副本
+    let tool_dirs: Vec<(String, PathBuf)> = resolve_local_skill_tool_dirs(&skill_name, workspace_path.as_deref())?
+        .into_iter()
+        .filter(|(tool_name, _)| installed_by.is_empty() || installed_by.contains(tool_name))
+        .collect();
+
+    // 之后的任一步失败都会恢复各工具副本、版本历史、注册表和锁文件
+    let mut txn = SkillTransaction::begin(&format!("回滚 Skill '{}' ", skill_name))?;
+
+    // 先保存当前版本，便于撤销本次回滚
+    if let Some((_, current_dir)) = tool_dirs.first() {
+        txn.snapshot_file(&get_skill_history_file_path(&skill_name, workspace_path.as_deref())?)?;
+        let version = record_skill_version(&skill_name, current_dir, workspace_path.as_deref(), "rollback")?;
+        txn.track_new_path(Path::new(&version.backup_path));
+    }
+
+    let mut restored_tools = Vec::new();
+    let mut restored_store = false;
+    for (tool_name, skill_dir) in &tool_dirs {
+        // 链接模式下只恢复一次规范副本
+        let store_dir = linked_store_dir(skill_dir, workspace_path.as_deref());
+        let (restore_dir, needs_restore) = match store_dir {
+            Some(store_dir) => (store_dir, !std::mem::replace(&mut restored_store, true)),
+            None => (skill_dir.clone(), true),
+        };
+        let linked = restore_dir != *skill_dir;
+
+        if needs_restore {
+            txn.remove_path(&restore_dir)?;
+            txn.track_new_path(&restore_dir);
+            copy_dir_recursive(&snapshot_dir, &restore_dir)
+                // 快照可能来自其他格式的工具，按当前工具重新转换（链接的格式文件稍后统一生成）
+                .and_then(|_| {
+                    if linked {
+                        Ok(())
+                    } else {
+                        super::skill_adapters::apply_skill_format(tool_name, &restore_dir, &skill_name)
+                    }
+                })
+                .map_err(|e| format!("回滚 {} 失败: {}", tool_name, e))?;
+        }
+        restored_tools.push(tool_name.clone());
+    }
+
+    if restored_tools.is_empty() {
+        return Err(format!("Skill '{}' 没有已安装的副本", skill_name));
+    }
+
+    if restored_store {
+        sync_linked_skill_formats(&skill_name, workspace_path.as_deref())?;
+    }
+
+    // 只在全局模式下更新注册表
+    if workspace_path.is_none() {
+        let mut registry = read_skill_registry()?;
+        if let Some(entry) = registry.skills.iter_mut().find(|s| s.name == skill_name) {
+            let description = target.manifest.as_ref().and_then(|m| m.description.clone());
+            let meta = entry.metadata.get_or_insert_with(|| SkillMetadata {
+                name: skill_name.clone(),
+                ..Default::default()
+            });
+            meta.version = target.version.clone();
+            meta.commit_hash = target.commit.clone();
+            meta.git_ref = target.git_ref.clone();
+            if target.repository.is_some() {
+                meta.repository = target.repository.clone();
+            }
+            if description.is_some() {
+                meta.description = description.clone();
+                entry.description = description;
+            }
+            if let Some(parsed) = tool_dirs
+                .first()
+                .and_then(|(_, dir)| read_skill_metadata(dir, &skill_name))
+            {
+                meta.apply_frontmatter(parsed);
+            }
+        }
+        txn.snapshot_file(&get_skill_registry_path()?)?;
+        write_skill_registry(registry)?;
+    }
+
+    // 同步锁文件中的提交和内容 hash
+    let lockfile = super::skill_lock::load_skill_lockfile(workspace_path.as_deref())?;
+    if let Some(mut lock_entry) = lockfile.skills.into_iter().find(|s| s.name == skill_name) {
+        lock_entry.commit = target.commit.clone();
+        lock_entry.git_ref = target.git_ref.clone();
+        if let Some(ref manifest) = target.manifest {
+            lock_entry.manifest_hash = compute_manifest_hash(manifest);
+        }
+        txn.snapshot_file(&super::skill_lock::get_skill_lockfile_path(workspace_path.as_deref())?)?;
+        super::skill_lock::upsert_skill_lock_entry(workspace_path.as_deref(), lock_entry)?;
+    }
+    txn.commit();
+
+    // 回滚成功后再清理超出上限的旧快照（回滚目标可能就是最旧的一个）
+    if let Err(e) = prune_skill_history(&skill_name, workspace_path.as_deref()) {
+        eprintln!("⚠️  [Backend] 清理历史版本失败: {}", e);
+    }
+    clear_skill_update_cache(workspace_path.as_deref())?;
+
+    let message = format!(
+        "已回滚到 {}，同步到 {} 个工具: {}",
+        target.version.as_deref().unwrap_or(&target.id),
+        restored_tools.len(),
+        restored_tools.join(", ")
+    );
+    println!("✅ [Backend] {}", message);
+    Ok(message)
+}
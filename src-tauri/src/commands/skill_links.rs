@@ -0,0 +1,149 @@
+// Skill 链接安装：规范副本保存在 ~/.cobalt/skills/store，各工具目录通过符号链接引用
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skills::{
+    copy_dir_recursive, get_all_tool_skills_dirs, get_all_tool_workspace_skills_dirs, get_disabled_skills_dir,
+    get_legacy_disabled_skills_dir,
+};
+
+/// 安装模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillInstallMode {
+    /// 每个工具目录一份独立副本
+    Copy,
+    /// 一份规范副本，工具目录为符号链接
+    Link,
+}
+
+impl SkillInstallMode {
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.unwrap_or("copy") {
+            "copy" => Ok(Self::Copy),
+            "link" => Ok(Self::Link),
+            other => Err(format!("未知的安装模式: {}", other)),
+        }
+    }
+}
+
+/// 获取规范副本目录
+/// 全局: ~/.cobalt/skills/store，工作区: <workspace>/.cobalt/skills/store
+pub fn get_skill_store_dir(workspace_path: Option<&str>) -> Result<PathBuf, String> {
+    let base_dir = if let Some(ws_path) = workspace_path {
+        PathBuf::from(ws_path).join(".cobalt")
+    } else {
+        dirs::home_dir()
+            .map(|home| home.join(".cobalt"))
+            .ok_or_else(|| "无法获取用户主目录".to_string())?
+    };
+    Ok(base_dir.join("skills").join("store"))
+}
+
+/// 如果路径是指向 store 的符号链接，返回其规范副本目录（用户手动创建的其他链接不算）
+pub fn linked_store_dir(path: &Path, workspace_path: Option<&str>) -> Option<PathBuf> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if !metadata.file_type().is_symlink() {
+        return None;
+    }
+
+    let target = fs::canonicalize(path).ok()?;
+    let store_dir = fs::canonicalize(get_skill_store_dir(workspace_path).ok()?).ok()?;
+    if target.starts_with(&store_dir) {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link_path: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link_path)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link_path: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link_path)
+}
+
+/// 在工具目录中创建指向规范副本的链接
+/// 系统不支持符号链接时（如 Windows 未开启开发者模式）退回复制，返回 false
+pub fn link_skill_dir(store_skill_dir: &Path, link_path: &Path) -> Result<bool, String> {
+    if let Some(parent) = link_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+
+    match create_symlink(store_skill_dir, link_path) {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            eprintln!("⚠️  [Backend] 创建符号链接失败，改为复制: {}", e);
+            copy_dir_recursive(&store_skill_dir.to_path_buf(), &link_path.to_path_buf())?;
+            Ok(false)
+        }
+    }
+}
+
+/// 将 skill 放入 store（已存在时替换为新内容），返回规范副本目录
+pub fn store_skill(source_dir: &Path, skill_name: &str, workspace_path: Option<&str>) -> Result<PathBuf, String> {
+    let store_skill_dir = get_skill_store_dir(workspace_path)?.join(skill_name);
+    if store_skill_dir.exists() {
+        fs::remove_dir_all(&store_skill_dir).map_err(|e| format!("清理规范副本失败: {}", e))?;
+    }
+    copy_dir_recursive(&source_dir.to_path_buf(), &store_skill_dir)?;
+    Ok(store_skill_dir)
+}
+
+/// 链接到规范副本的工具及其 skill 路径（不含禁用目录）
+pub fn find_linked_tools(skill_name: &str, workspace_path: Option<&str>) -> Vec<(String, PathBuf)> {
+    tool_skill_dirs(workspace_path)
+        .into_iter()
+        .map(|(tool_name, dir)| (tool_name, dir.join(skill_name)))
+        .filter(|(_, dir)| linked_store_dir(dir, workspace_path).is_some())
+        .collect()
+}
+
+fn tool_skill_dirs(workspace_path: Option<&str>) -> Vec<(String, PathBuf)> {
+    match workspace_path {
+        Some(ws_path) => get_all_tool_workspace_skills_dirs(&PathBuf::from(ws_path)),
+        None => get_all_tool_skills_dirs(),
+    }
+}
+
+/// 规范副本只有一份，需要同时生成所有链接工具需要的格式文件
+pub fn sync_linked_skill_formats(skill_name: &str, workspace_path: Option<&str>) -> Result<(), String> {
+    let store_skill_dir = get_skill_store_dir(workspace_path)?.join(skill_name);
+    if !store_skill_dir.exists() {
+        return Ok(());
+    }
+    let tools = find_linked_tools(skill_name, workspace_path);
+    super::skill_adapters::apply_skill_formats(&tools, &store_skill_dir, skill_name)
+}
+
+/// 规范副本是否仍被工具目录或禁用目录中的链接引用
+pub fn is_store_referenced(skill_name: &str, workspace_path: Option<&str>) -> Result<bool, String> {
+    let mut candidates: Vec<PathBuf> = tool_skill_dirs(workspace_path)
+        .into_iter()
+        .map(|(_, dir)| dir.join(skill_name))
+        .collect();
+    candidates.push(get_disabled_skills_dir(workspace_path)?.join(skill_name));
+    candidates.push(get_legacy_disabled_skills_dir(workspace_path)?.join(skill_name));
+
+    Ok(candidates
+        .iter()
+        .any(|path| linked_store_dir(path, workspace_path).is_some()))
+}
+
+/// 规范副本不再被任何工具目录或禁用目录引用时删除
+pub fn prune_skill_store(skill_name: &str, workspace_path: Option<&str>) -> Result<(), String> {
+    let store_skill_dir = get_skill_store_dir(workspace_path)?.join(skill_name);
+    if !store_skill_dir.exists() {
+        return Ok(());
+    }
+
+    if is_store_referenced(skill_name, workspace_path)? {
+        return sync_linked_skill_formats(skill_name, workspace_path);
+    }
+
+    fs::remove_dir_all(&store_skill_dir).map_err(|e| format!("删除规范副本失败: {}", e))?;
+    println!("🧹 [Backend] 已删除 {} 的规范副本", skill_name);
+    Ok(())
+}
@@ -0,0 +1,649 @@
+// Skill 规范检查：按 Claude Code 的 Skill 格式校验 SKILL.md，并检查文件引用、文件大小和重名
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::skills::{get_all_tool_skills_dirs, get_all_tool_workspace_skills_dirs, resolve_local_skill_tool_dirs};
+
+/// name 的最大长度
+const MAX_SKILL_NAME_LENGTH: usize = 64;
+/// description 的最大长度（字符数）
+const MAX_DESCRIPTION_LENGTH: usize = 1024;
+/// SKILL.md 建议的最大行数，更多内容应拆分到引用文件中
+const MAX_SKILL_MD_LINES: usize = 500;
+/// 单个文件的最大大小
+const MAX_SKILL_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Claude Code 及 Cobalt 认识的 frontmatter 字段
+const KNOWN_FRONTMATTER_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "allowed-tools",
+    "user-invocable",
+    "disable-model-invocation",
+    "argument-hint",
+    "model",
+    "context",
+    "agent",
+    "hooks",
+    "license",
+    "metadata",
+    "version",
+    "tags",
+    "ai-tools",
+    "requires",
+    "repository",
+    "globs",
+    "paths",
+    "alwaysApply",
+    "always-apply",
+];
+
+/// 诊断级别
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// 单条检查结果
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDiagnostic {
+    pub severity: SkillDiagnosticSeverity,
+    /// 规则标识，如 invalid-name、broken-reference
+    pub code: String,
+    pub message: String,
+    /// 相对 skill 目录的文件路径
+    pub file: String,
+    /// 行号（从 1 开始）
+    pub line: Option<usize>,
+}
+
+/// Skill 检查报告
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLintReport {
+    pub skill_name: String,
+    pub skill_path: String,
+    pub diagnostics: Vec<SkillDiagnostic>,
+    pub error_count: usize,
+    pub warning_count: usize,
+}
+
+struct DiagnosticSink {
+    diagnostics: Vec<SkillDiagnostic>,
+}
+
+impl DiagnosticSink {
+    fn push(&mut self, severity: SkillDiagnosticSeverity, code: &str, file: &str, line: Option<usize>, message: String) {
+        self.diagnostics.push(SkillDiagnostic {
+            severity,
+            code: code.to_string(),
+            message,
+            file: file.to_string(),
+            line,
+        });
+    }
+
+    fn error(&mut self, code: &str, file: &str, line: Option<usize>, message: String) {
+        self.push(SkillDiagnosticSeverity::Error, code, file, line, message);
+    }
+
+    fn warning(&mut self, code: &str, file: &str, line: Option<usize>, message: String) {
+        self.push(SkillDiagnosticSeverity::Warning, code, file, line, message);
+    }
+}
+
+/// 校验 Skill 名称：小写字母、数字和连字符，不以连字符开头或结尾，最长 64 个字符
+pub fn validate_skill_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Skill 名称不能为空".to_string());
+    }
+    if name.len() > MAX_SKILL_NAME_LENGTH {
+        return Err(format!("Skill 名称不能超过 {} 个字符", MAX_SKILL_NAME_LENGTH));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(format!("Skill 名称 '{}' 只能包含小写字母、数字和连字符", name));
+    }
+    if name.starts_with('-') || name.ends_with('-') || name.contains("--") {
+        return Err(format!("Skill 名称 '{}' 不能以连字符开头或结尾，也不能包含连续的连字符", name));
+    }
+    Ok(())
+}
+
+/// 检查 Skill 是否符合规范
+#[tauri::command]
+pub fn lint_skill(skill_name: String, workspace_path: Option<String>) -> Result<SkillLintReport, String> {
+    let tool_dirs = resolve_local_skill_tool_dirs(&skill_name, workspace_path.as_deref())?;
+    let skill_dir = tool_dirs
+        .iter()
+        .find(|(tool_name, _)| tool_name == "claude-code")
+        .or_else(|| tool_dirs.first())
+        .map(|(_, dir)| dir.clone())
+        .ok_or_else(|| format!("Skill '{}' 不存在", skill_name))?;
+
+    let diagnostics = lint_skill_dir(&skill_dir, &skill_name, workspace_path.as_deref());
+    let error_count = diagnostics
+        .iter()
+        .filter(|d| d.severity == SkillDiagnosticSeverity::Error)
+        .count();
+    let warning_count = diagnostics.len() - error_count;
+
+    println!(
+        "🔎 [Backend] 检查 Skill '{}': {} 个错误，{} 个警告",
+        skill_name, error_count, warning_count
+    );
+
+    Ok(SkillLintReport {
+        skill_name,
+        skill_path: skill_dir.to_string_lossy().to_string(),
+        diagnostics,
+        error_count,
+        warning_count,
+    })
+}
+
+/// 检查 skill 目录，返回全部诊断
+pub fn lint_skill_dir(skill_dir: &Path, skill_name: &str, workspace_path: Option<&str>) -> Vec<SkillDiagnostic> {
+    let mut sink = DiagnosticSink { diagnostics: Vec::new() };
+
+    match fs::read_to_string(skill_dir.join("SKILL.md")) {
+        Ok(content) => {
+            let frontmatter_name = lint_skill_md(&content, skill_dir, skill_name, &mut sink);
+            lint_duplicate_names(skill_name, frontmatter_name.as_deref(), workspace_path, &mut sink);
+        }
+        Err(_) => sink.error("missing-skill-md", "SKILL.md", None, "缺少 SKILL.md".to_string()),
+    }
+
+    lint_file_sizes(skill_dir, skill_dir, &mut sink);
+
+    let mut diagnostics = sink.diagnostics;
+    diagnostics.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    diagnostics
+}
+
+/// SKILL.md 按行拆分后的 frontmatter 与正文位置
+struct SkillMdLayout<'a> {
+    lines: Vec<&'a str>,
+    /// 结束 `---` 所在行的下标，None 表示没有 frontmatter
+    frontmatter_end: Option<usize>,
+}
+
+impl<'a> SkillMdLayout<'a> {
+    fn parse(content: &'a str) -> Self {
+        let lines: Vec<&str> = content.lines().collect();
+        let frontmatter_end = if lines.first().map(|l| l.trim_end()) == Some("---") {
+            lines.iter().skip(1).position(|l| l.trim_end() == "---").map(|i| i + 1)
+        } else {
+            None
+        };
+        Self { lines, frontmatter_end }
+    }
+
+    fn frontmatter(&self) -> Option<String> {
+        self.frontmatter_end.map(|end| self.lines[1..end].join("\n"))
+    }
+
+    /// 字段所在行号（从 1 开始）
+    fn key_line(&self, key: &str) -> Option<usize> {
+        let end = self.frontmatter_end?;
+        let prefix = format!("{}:", key);
+        self.lines[1..end]
+            .iter()
+            .position(|line| line.starts_with(&prefix))
+            .map(|i| i + 2)
+    }
+
+    /// 正文各行及其行号
+    fn body_lines(&self) -> impl Iterator<Item = (usize, &&'a str)> {
+        let start = self.frontmatter_end.map(|end| end + 1).unwrap_or(0);
+        self.lines.iter().enumerate().skip(start).map(|(i, line)| (i + 1, line))
+    }
+}
+
+/// 检查 SKILL.md，返回 frontmatter 中的 name
+fn lint_skill_md(content: &str, skill_dir: &Path, skill_name: &str, sink: &mut DiagnosticSink) -> Option<String> {
+    const FILE: &str = "SKILL.md";
+    let layout = SkillMdLayout::parse(content);
+
+    if layout.lines.len() > MAX_SKILL_MD_LINES {
+        sink.warning(
+            "skill-md-too-long",
+            FILE,
+            None,
+            format!(
+                "SKILL.md 共 {} 行，超过建议的 {} 行，可将详细内容拆分到单独文件中引用",
+                layout.lines.len(),
+                MAX_SKILL_MD_LINES
+            ),
+        );
+    }
+
+    lint_references(&layout, skill_dir, sink);
+
+    let Some(frontmatter) = layout.frontmatter() else {
+        sink.error("missing-frontmatter", FILE, Some(1), "SKILL.md 缺少 YAML frontmatter（以 --- 开头和结尾）".to_string());
+        return None;
+    };
+
+    let yaml: serde_yaml::Value = match serde_yaml::from_str(&frontmatter) {
+        Ok(yaml) => yaml,
+        Err(e) => {
+            // frontmatter 从第 2 行开始
+            let line = e.location().map(|loc| loc.line() + 1);
+            sink.error("invalid-yaml", FILE, line, format!("frontmatter 不是合法的 YAML: {}", e));
+            return None;
+        }
+    };
+    let Some(mapping) = yaml.as_mapping() else {
+        sink.error("invalid-frontmatter", FILE, Some(2), "frontmatter 必须是键值对".to_string());
+        return None;
+    };
+
+    for key in mapping.keys() {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        if !KNOWN_FRONTMATTER_FIELDS.contains(&key) {
+            sink.warning("unknown-field", FILE, layout.key_line(key), format!("未知的 frontmatter 字段 '{}'", key));
+        }
+    }
+
+    let name = match yaml.get("name") {
+        None => {
+            sink.error("missing-name", FILE, Some(2), "frontmatter 缺少 name 字段".to_string());
+            None
+        }
+        Some(value) => match value.as_str() {
+            Some(name) => {
+                let line = layout.key_line("name");
+                if let Err(e) = validate_skill_name(name) {
+                    sink.error("invalid-name", FILE, line, e);
+                }
+                if name != skill_name {
+                    sink.warning(
+                        "name-mismatch",
+                        FILE,
+                        line,
+                        format!("name '{}' 与目录名 '{}' 不一致", name, skill_name),
+                    );
+                }
+                Some(name.to_string())
+            }
+            None => {
+                sink.error("invalid-name", FILE, layout.key_line("name"), "name 必须是字符串".to_string());
+                None
+            }
+        },
+    };
+
+    let description_line = layout.key_line("description");
+    match yaml.get("description") {
+        None => sink.error(
+            "missing-description",
+            FILE,
+            Some(2),
+            "frontmatter 缺少 description 字段，Claude 依靠它判断何时使用该 Skill".to_string(),
+        ),
+        Some(value) => match value.as_str().map(str::trim) {
+            Some("") => sink.error("empty-description", FILE, description_line, "description 不能为空".to_string()),
+            Some(description) => {
+                let length = description.chars().count();
+                if length > MAX_DESCRIPTION_LENGTH {
+                    sink.error(
+                        "description-too-long",
+                        FILE,
+                        description_line,
+                        format!("description 共 {} 个字符，不能超过 {} 个", length, MAX_DESCRIPTION_LENGTH),
+                    );
+                }
+                if regex::Regex::new(r"</?[A-Za-z][^>]*>").map(|re| re.is_match(description)).unwrap_or(false) {
+                    sink.warning("description-xml", FILE, description_line, "description 不应包含 XML 标签".to_string());
+                }
+            }
+            None => sink.error("invalid-description", FILE, description_line, "description 必须是字符串".to_string()),
+        },
+    }
+
+    if let Some(value) = yaml.get("allowed-tools") {
+        lint_allowed_tools(value, layout.key_line("allowed-tools"), sink);
+    }
+
+    for key in ["user-invocable", "disable-model-invocation"] {
+        if let Some(value) = yaml.get(key) {
+            if !value.is_bool() {
+                sink.error(
+                    &format!("invalid-{}", key),
+                    FILE,
+                    layout.key_line(key),
+                    format!("{} 必须是 true 或 false", key),
+                );
+            }
+        }
+    }
+
+    name
+}
+
+/// allowed-tools 支持逗号分隔字符串或列表，每项为工具名，可带参数规则，如 Bash(git:*)
+fn lint_allowed_tools(value: &serde_yaml::Value, line: Option<usize>, sink: &mut DiagnosticSink) {
+    let tools: Vec<String> = match value {
+        serde_yaml::Value::String(s) => s.split(',').map(|t| t.trim().to_string()).collect(),
+        serde_yaml::Value::Sequence(seq) => {
+            let tools: Vec<String> = seq.iter().filter_map(|v| v.as_str().map(|t| t.trim().to_string())).collect();
+            if tools.len() != seq.len() {
+                sink.error("invalid-allowed-tools", "SKILL.md", line, "allowed-tools 列表只能包含字符串".to_string());
+            }
+            tools
+        }
+        _ => {
+            sink.error(
+                "invalid-allowed-tools",
+                "SKILL.md",
+                line,
+                "allowed-tools 必须是逗号分隔的字符串或字符串列表".to_string(),
+            );
+            return;
+        }
+    };
+
+    let Ok(tool_re) = regex::Regex::new(r"^[A-Za-z][A-Za-z0-9_-]*(\(.+\))?$") else {
+        return;
+    };
+    for tool in tools.iter().filter(|t| !t.is_empty()) {
+        if !tool_re.is_match(tool) {
+            sink.warning(
+                "invalid-allowed-tool",
+                "SKILL.md",
+                line,
+                format!("allowed-tools 中的 '{}' 不是有效的工具名", tool),
+            );
+        }
+    }
+}
+
+/// 检查正文中指向 skill 内文件的 Markdown 链接（代码块中的链接忽略）
+fn lint_references(layout: &SkillMdLayout, skill_dir: &Path, sink: &mut DiagnosticSink) {
+    let Ok(link_re) = regex::Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#) else {
+        return;
+    };
+
+    let mut in_code_block = false;
+    for (line_number, line) in layout.body_lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        for caps in link_re.captures_iter(line) {
+            let Some(target) = caps.get(1).map(|m| m.as_str()) else {
+                continue;
+            };
+            if target.starts_with('#') || target.starts_with('/') || target.contains(':') {
+                continue;
+            }
+
+            let path = target.split(['#', '?']).next().unwrap_or(target);
+            if path.is_empty() {
+                continue;
+            }
+
+            let relative = PathBuf::from(path);
+            let escapes = relative.components().fold(0i32, |depth, component| {
+                if depth < 0 {
+                    return depth;
+                }
+                match component {
+                    Component::ParentDir => depth - 1,
+                    Component::Normal(_) => depth + 1,
+                    _ => depth,
+                }
+            }) < 0;
+            if escapes {
+                sink.warning(
+                    "reference-outside-skill",
+                    "SKILL.md",
+                    Some(line_number),
+                    format!("引用的 '{}' 位于 Skill 目录之外，安装到其他位置后会失效", target),
+                );
+                continue;
+            }
+
+            if !skill_dir.join(&relative).exists() {
+                sink.error(
+                    "broken-reference",
+                    "SKILL.md",
+                    Some(line_number),
+                    format!("引用的文件 '{}' 不存在", path),
+                );
+            }
+        }
+    }
+}
+
+fn lint_file_sizes(base_dir: &Path, dir: &Path, sink: &mut DiagnosticSink) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        // 不跟随符号链接，避免目录链接成环时无限递归
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.file_type().is_symlink() {
+            continue;
+        }
+
+        if metadata.is_dir() {
+            lint_file_sizes(base_dir, &path, sink);
+        } else if metadata.len() > MAX_SKILL_FILE_SIZE {
+            let relative = path
+                .strip_prefix(base_dir)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            sink.warning(
+                "file-too-large",
+                &relative,
+                None,
+                format!(
+                    "文件大小 {:.1} MB，超过 {} MB，会拖慢加载并占用上下文",
+                    metadata.len() as f64 / 1024.0 / 1024.0,
+                    MAX_SKILL_FILE_SIZE / 1024 / 1024
+                ),
+            );
+        }
+    }
+}
+
+/// 检查其他 skill 目录是否使用了相同的 name（同名目录只是同一 Skill 在不同工具中的副本）
+fn lint_duplicate_names(
+    skill_name: &str,
+    frontmatter_name: Option<&str>,
+    workspace_path: Option<&str>,
+    sink: &mut DiagnosticSink,
+) {
+    let name = frontmatter_name.unwrap_or(skill_name);
+    let tool_dirs = match workspace_path {
+        Some(ws_path) => get_all_tool_workspace_skills_dirs(&PathBuf::from(ws_path)),
+        None => get_all_tool_skills_dirs(),
+    };
+
+    for (tool_name, tool_dir) in tool_dirs {
+        let Ok(entries) = fs::read_dir(&tool_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            if dir_name == skill_name || !entry.path().is_dir() {
+                continue;
+            }
+
+            let other_name = fs::read_to_string(entry.path().join("SKILL.md"))
+                .ok()
+                .and_then(|content| super::skills::parse_skill_frontmatter(&content, &dir_name))
+                .map(|meta| meta.name)
+                .unwrap_or_else(|| dir_name.clone());
+            if other_name == name || dir_name == name {
+                sink.error(
+                    "duplicate-name",
+                    "SKILL.md",
+                    None,
+                    format!("{} 中的 Skill '{}' 也使用了名称 '{}'", tool_name, dir_name, name),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的临时 skill 目录，离开作用域时删除
+    struct TempSkillDir(PathBuf);
+
+    impl TempSkillDir {
+        fn new(skill_md: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("cobalt-lint-test-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), skill_md).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempSkillDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn lint(content: &str, skill_name: &str) -> Vec<SkillDiagnostic> {
+        let dir = TempSkillDir::new(content);
+        let mut sink = DiagnosticSink { diagnostics: Vec::new() };
+        lint_skill_md(content, &dir.0, skill_name, &mut sink);
+        sink.diagnostics
+    }
+
+    fn codes(diagnostics: &[SkillDiagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+
+    #[test]
+    fn valid_skill_has_no_diagnostics() {
+        let diagnostics = lint("---\nname: pdf-tools\ndescription: 处理 PDF 文件\n---\n# PDF\n", "pdf-tools");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn validate_skill_name_rules() {
+        assert!(validate_skill_name("pdf-tools2").is_ok());
+        assert!(validate_skill_name("").is_err());
+        assert!(validate_skill_name("PDF").is_err());
+        assert!(validate_skill_name("pdf_tools").is_err());
+        assert!(validate_skill_name("-pdf").is_err());
+        assert!(validate_skill_name("pdf-").is_err());
+        assert!(validate_skill_name("pdf--tools").is_err());
+        assert!(validate_skill_name(&"a".repeat(MAX_SKILL_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn missing_frontmatter() {
+        let diagnostics = lint("# 没有 frontmatter\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["missing-frontmatter"]);
+        assert_eq!(diagnostics[0].line, Some(1));
+    }
+
+    #[test]
+    fn unterminated_frontmatter_is_missing() {
+        let diagnostics = lint("---\nname: demo\ndescription: x\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["missing-frontmatter"]);
+    }
+
+    #[test]
+    fn invalid_yaml_reports_line() {
+        let diagnostics = lint("---\nname: demo\ndescription: [unclosed\n---\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["invalid-yaml"]);
+        assert!(diagnostics[0].line.is_some());
+    }
+
+    #[test]
+    fn name_and_description_rules() {
+        let diagnostics = lint("---\nname: Demo_Skill\ndescription: \"  \"\n---\n", "demo");
+        let found = codes(&diagnostics);
+        assert!(found.contains(&"invalid-name"));
+        assert!(found.contains(&"name-mismatch"));
+        assert!(found.contains(&"empty-description"));
+
+        let diagnostics = lint("---\nname: demo\n---\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["missing-description"]);
+
+        let diagnostics = lint("---\ndescription: x\n---\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["missing-name"]);
+    }
+
+    #[test]
+    fn description_length_and_xml() {
+        let long = "a".repeat(MAX_DESCRIPTION_LENGTH + 1);
+        let diagnostics = lint(&format!("---\nname: demo\ndescription: {}\n---\n", long), "demo");
+        assert_eq!(codes(&diagnostics), vec!["description-too-long"]);
+        assert_eq!(diagnostics[0].line, Some(3));
+
+        let diagnostics = lint("---\nname: demo\ndescription: 使用 <tool> 标签\n---\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["description-xml"]);
+    }
+
+    #[test]
+    fn unknown_field_reports_its_line() {
+        let diagnostics = lint("---\nname: demo\ndescription: x\nfoo: bar\n---\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["unknown-field"]);
+        assert_eq!(diagnostics[0].line, Some(4));
+    }
+
+    #[test]
+    fn allowed_tools_and_boolean_fields() {
+        let diagnostics = lint(
+            "---\nname: demo\ndescription: x\nallowed-tools: Read, Bash(git:*), not a tool\nuser-invocable: yes-please\n---\n",
+            "demo",
+        );
+        assert_eq!(codes(&diagnostics), vec!["invalid-allowed-tool", "invalid-user-invocable"]);
+
+        let diagnostics = lint("---\nname: demo\ndescription: x\nallowed-tools:\n  - Read\n  - 3\n---\n", "demo");
+        assert_eq!(codes(&diagnostics), vec!["invalid-allowed-tools"]);
+    }
+
+    #[test]
+    fn references_outside_code_blocks() {
+        let content = "---\nname: demo\ndescription: x\n---\n[ok](SKILL.md)\n[missing](docs/a.md)\n[up](../other.md)\n[web](https://example.com)\n```\n[ignored](nope.md)\n```\n";
+        let diagnostics = lint(content, "demo");
+        assert_eq!(codes(&diagnostics), vec!["broken-reference", "reference-outside-skill"]);
+        assert_eq!(diagnostics[0].line, Some(6));
+        assert_eq!(diagnostics[1].line, Some(7));
+    }
+
+    #[test]
+    fn file_sizes_skip_symlinks() {
+        let dir = TempSkillDir::new("---\nname: demo\ndescription: x\n---\n");
+        fs::create_dir_all(dir.0.join("assets")).unwrap();
+        fs::write(dir.0.join("assets/big.bin"), vec![0u8; MAX_SKILL_FILE_SIZE as usize + 1]).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dir.0, dir.0.join("assets/loop")).unwrap();
+
+        let mut sink = DiagnosticSink { diagnostics: Vec::new() };
+        lint_file_sizes(&dir.0, &dir.0, &mut sink);
+        assert_eq!(codes(&sink.diagnostics), vec!["file-too-large"]);
+        assert_eq!(sink.diagnostics[0].file, "assets/big.bin");
+    }
+}
@@ -0,0 +1,214 @@
+// 本地 Skill 来源：本地目录或 .zip / .tar.gz / .tgz / .tar 归档
+// 安装时以 file:// 地址记录来源，检查更新和更新时从同一路径重新读取
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+const LOCAL_SOURCE_SCHEME: &str = "file://";
+
+/// 解压后文件总大小上限，防止压缩炸弹
+const MAX_ARCHIVE_UNPACKED_SIZE: u64 = 256 * 1024 * 1024;
+
+/// 支持的归档格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    TarGz,
+    Tar,
+}
+
+impl ArchiveKind {
+    /// 按文件扩展名识别归档格式
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// 把本地路径转换为记录在注册表、清单和锁文件中的来源地址
+pub fn local_source_url(path: &Path) -> String {
+    format!("{}{}", LOCAL_SOURCE_SCHEME, path.to_string_lossy().replace('\\', "/"))
+}
+
+/// 如果来源地址指向本地目录或归档，返回对应路径
+pub fn local_source_path(repo_url: &str) -> Option<PathBuf> {
+    repo_url
+        .strip_prefix(LOCAL_SOURCE_SCHEME)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 校验用户选择的本地路径，返回绝对路径
+pub fn resolve_local_source(path: &str) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("请选择本地目录或归档文件".to_string());
+    }
+
+    let source = fs::canonicalize(path).map_err(|_| format!("本地路径不存在: {}", path))?;
+    if source.is_file() && ArchiveKind::detect(&source).is_none() {
+        return Err(format!("不支持的归档格式: {}（支持 .zip、.tar.gz、.tgz、.tar）", path));
+    }
+    Ok(source)
+}
+
+/// 来源名称：目录名，或去掉扩展名的归档文件名
+pub fn local_source_name(source: &Path) -> String {
+    let name = source.file_name().and_then(|n| n.to_str()).unwrap_or("skill");
+    let lower = name.to_ascii_lowercase();
+    for ext in [".tar.gz", ".tgz", ".zip", ".tar"] {
+        if lower.ends_with(ext) {
+            return name[..name.len() - ext.len()].to_string();
+        }
+    }
+    name.to_string()
+}
+
+/// 准备本地来源的根目录：目录直接使用，归档解压到 temp_dir
+/// 归档只包含一个顶层目录时（常见的 my-skill/SKILL.md 打包方式），返回该目录
+pub fn prepare_local_source(source: &Path, temp_dir: &Path) -> Result<PathBuf, String> {
+    if source.is_dir() {
+        return Ok(source.to_path_buf());
+    }
+    if !source.is_file() {
+        return Err(format!("本地来源不存在: {}", source.display()));
+    }
+
+    let kind = ArchiveKind::detect(source)
+        .ok_or_else(|| format!("不支持的归档格式: {}", source.display()))?;
+
+    if temp_dir.exists() {
+        fs::remove_dir_all(temp_dir).map_err(|e| format!("删除临时目录失败: {}", e))?;
+    }
+    fs::create_dir_all(temp_dir).map_err(|e| format!("创建临时目录失败: {}", e))?;
+
+    println!("📦 [Backend] 解压归档: {:?}", source);
+    let extracted = match kind {
+        ArchiveKind::Zip => extract_zip(source, temp_dir),
+        ArchiveKind::TarGz => {
+            let file = fs::File::open(source).map_err(|e| format!("打开归档失败: {}", e))?;
+            extract_tar(flate2::read::GzDecoder::new(file), temp_dir)
+        }
+        ArchiveKind::Tar => {
+            let file = fs::File::open(source).map_err(|e| format!("打开归档失败: {}", e))?;
+            extract_tar(file, temp_dir)
+        }
+    };
+    if let Err(e) = extracted {
+        let _ = fs::remove_dir_all(temp_dir);
+        return Err(e);
+    }
+
+    Ok(single_top_level_dir(temp_dir).unwrap_or_else(|| temp_dir.to_path_buf()))
+}
+
+/// 解压目录中既没有 SKILL.md 也没有 skills/ 且只有一个顶层目录时，返回该目录
+fn single_top_level_dir(dir: &Path) -> Option<PathBuf> {
+    if dir.join("SKILL.md").exists() || dir.join("skills").is_dir() {
+        return None;
+    }
+
+    let entries: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_name() != "__MACOSX")
+        .map(|entry| entry.path())
+        .collect();
+
+    match entries.as_slice() {
+        [only] if only.is_dir() => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// 检查归档条目路径只包含普通组件，不能逃逸到解压目录之外
+fn is_safe_entry_path(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+fn extract_zip(source: &Path, dest: &Path) -> Result<(), String> {
+    let file = fs::File::open(source).map_err(|e| format!("打开归档失败: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("读取 zip 归档失败: {}", e))?;
+
+    let mut unpacked_size = 0u64;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("读取 zip 条目失败: {}", e))?;
+        let relative = match entry.enclosed_name() {
+            Some(path) if is_safe_entry_path(&path) => path,
+            _ => return Err(format!("归档中包含不安全的路径: {}", entry.name())),
+        };
+        let target = dest.join(&relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&target).map_err(|e| format!("创建目录失败: {}", e))?;
+            continue;
+        }
+
+        unpacked_size += entry.size();
+        if unpacked_size > MAX_ARCHIVE_UNPACKED_SIZE {
+            return Err("归档解压后超过 256MB，已停止解压".to_string());
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+        }
+        let mut output = fs::File::create(&target).map_err(|e| format!("写入文件失败: {}", e))?;
+        io::copy(&mut entry, &mut output).map_err(|e| format!("解压 {} 失败: {}", relative.display(), e))?;
+
+        // 保留脚本的可执行权限
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Some(mode) = entry.unix_mode() {
+                let _ = fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o777));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_tar<R: io::Read>(reader: R, dest: &Path) -> Result<(), String> {
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive.entries().map_err(|e| format!("读取 tar 归档失败: {}", e))?;
+
+    let mut unpacked_size = 0u64;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("读取 tar 条目失败: {}", e))?;
+        let relative = entry
+            .path()
+            .map_err(|e| format!("读取 tar 条目路径失败: {}", e))?
+            .into_owned();
+        if !is_safe_entry_path(&relative) {
+            return Err(format!("归档中包含不安全的路径: {}", relative.display()));
+        }
+
+        // 只解压普通文件和目录，跳过符号链接、硬链接等特殊条目
+        let entry_type = entry.header().entry_type();
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            println!("⚠️  [Backend] 跳过归档中的特殊条目: {}", relative.display());
+            continue;
+        }
+
+        unpacked_size += entry.size();
+        if unpacked_size > MAX_ARCHIVE_UNPACKED_SIZE {
+            return Err("归档解压后超过 256MB，已停止解压".to_string());
+        }
+
+        entry
+            .unpack_in(dest)
+            .map_err(|e| format!("解压 {} 失败: {}", relative.display(), e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+// Skill 锁文件（skills.lock）管理命令
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const SKILL_LOCKFILE_NAME: &str = "skills.lock";
+
+/// 获取 Cobalt 配置目录路径
+fn get_cobalt_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".cobalt"))
+        .ok_or_else(|| "无法获取用户主目录".to_string())
+}
+
+/// 获取锁文件路径
+/// 全局: ~/.cobalt/skills.lock，工作区: <workspace>/.cobalt/skills.lock
+pub fn get_skill_lockfile_path(workspace_path: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(ws_path) = workspace_path {
+        Ok(PathBuf::from(ws_path).join(".cobalt").join(SKILL_LOCKFILE_NAME))
+    } else {
+        Ok(get_cobalt_dir()?.join(SKILL_LOCKFILE_NAME))
+    }
+}
+
+/// 锁文件中的单个 Skill 条目
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLockEntry {
+    pub name: String,
+    pub repository: String,
+    /// 安装时指定的分支、标签或提交
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// 安装时的提交 SHA
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// Skill 在仓库中的相对路径，空字符串表示仓库根目录
+    #[serde(default)]
+    pub subpath: String,
+    /// 清单整体 hash，用于校验重新安装的内容是否一致
+    #[serde(default)]
+    pub manifest_hash: String,
+    #[serde(default)]
+    pub target_tools: Vec<String>,
+    /// 以别名安装时来源仓库中的原名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_name: Option<String>,
+}
+
+/// 锁文件
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLockfile {
+    #[serde(default = "default_lockfile_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub skills: Vec<SkillLockEntry>,
+}
+
+fn default_lockfile_version() -> u32 {
+    1
+}
+
+impl Default for SkillLockfile {
+    fn default() -> Self {
+        Self {
+            version: default_lockfile_version(),
+            skills: Vec::new(),
+        }
+    }
+}
+
+/// 读取锁文件（不存在时返回空锁文件）
+pub fn load_skill_lockfile(workspace_path: Option<&str>) -> Result<SkillLockfile, String> {
+    let lockfile_path = get_skill_lockfile_path(workspace_path)?;
+
+    if !lockfile_path.exists() {
+        return Ok(SkillLockfile::default());
+    }
+
+    let content = fs::read_to_string(&lockfile_path)
+        .map_err(|e| format!("读取 skills.lock 失败: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("解析 skills.lock 失败: {}", e))
+}
+
+/// 写入锁文件（条目按名称排序，保证输出稳定便于 diff）
+pub fn save_skill_lockfile(workspace_path: Option<&str>, lockfile: &SkillLockfile) -> Result<(), String> {
+    let lockfile_path = get_skill_lockfile_path(workspace_path)?;
+
+    if let Some(parent) = lockfile_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建锁文件目录失败: {}", e))?;
+    }
+
+    let mut lockfile = lockfile.clone();
+    lockfile.skills.sort_by(|a, b| a.name.cmp(&b.name));
+    for entry in &mut lockfile.skills {
+        entry.target_tools.sort();
+        entry.target_tools.dedup();
+    }
+
+    let mut content = serde_json::to_string_pretty(&lockfile)
+        .map_err(|e| format!("序列化 skills.lock 失败: {}", e))?;
+    content.push('\n');
+
+    super::skill_transaction::write_file_atomic(&lockfile_path, content.as_bytes())
+}
+
+/// 新增或更新锁文件条目，已有条目的目标工具会与新工具合并
+pub fn upsert_skill_lock_entry(workspace_path: Option<&str>, entry: SkillLockEntry) -> Result<(), String> {
+    let mut lockfile = load_skill_lockfile(workspace_path)?;
+
+    if let Some(existing) = lockfile.skills.iter_mut().find(|s| s.name == entry.name) {
+        let mut target_tools = existing.target_tools.clone();
+        for tool in entry.target_tools {
+            if !target_tools.contains(&tool) {
+                target_tools.push(tool);
+            }
+        }
+        *existing = SkillLockEntry {
+            target_tools,
+            ..entry
+        };
+    } else {
+        lockfile.skills.push(entry);
+    }
+
+    save_skill_lockfile(workspace_path, &lockfile)
+}
+
+/// 从锁文件中移除条目
+pub fn remove_skill_lock_entry(workspace_path: Option<&str>, skill_name: &str) -> Result<(), String> {
+    let mut lockfile = load_skill_lockfile(workspace_path)?;
+    let original_len = lockfile.skills.len();
+    lockfile.skills.retain(|s| s.name != skill_name);
+
+    if lockfile.skills.len() != original_len {
+        save_skill_lockfile(workspace_path, &lockfile)?;
+    }
+    Ok(())
+}
+
+/// 更新锁文件条目的目标工具；工具列表为空时移除条目
+pub fn set_skill_lock_target_tools(
+    workspace_path: Option<&str>,
+    skill_name: &str,
+    target_tools: Vec<String>,
+) -> Result<(), String> {
+    if target_tools.is_empty() {
+        return remove_skill_lock_entry(workspace_path, skill_name);
+    }
+
+    let mut lockfile = load_skill_lockfile(workspace_path)?;
+    if let Some(entry) = lockfile.skills.iter_mut().find(|s| s.name == skill_name) {
+        entry.target_tools = target_tools;
+        save_skill_lockfile(workspace_path, &lockfile)?;
+    }
+    Ok(())
+}
+
+/// 读取 skills.lock
+#[tauri::command]
+pub fn read_skill_lockfile(workspace_path: Option<String>) -> Result<SkillLockfile, String> {
+    load_skill_lockfile(workspace_path.as_deref())
+}
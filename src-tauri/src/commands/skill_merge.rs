@@ -0,0 +1,350 @@
+// Skill 更新时的三方合并（本地修改 / 安装时基线 / 上游新版本）
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::skills::{run_git, GitAuthInput, SkillManifest};
+
+/// 合并基线：旧版本所在的仓库提交
+pub struct SkillMergeBase<'a> {
+    pub repo_dir: &'a Path,
+    pub commit: &'a str,
+    /// Skill 在仓库中的相对路径，空字符串表示仓库根目录
+    pub subpath: &'a str,
+    /// 克隆时使用的凭据；部分克隆读取基线文件时会按需下载，需要同样的认证设置
+    pub auth: Option<&'a GitAuthInput>,
+}
+
+/// 合并冲突
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillMergeConflict {
+    pub path: String,
+    /// content | binary | deleted-locally | deleted-upstream | added-both | missing-base
+    pub reason: String,
+}
+
+/// 冲突处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillConflictStrategy {
+    /// 存在冲突时不应用更新
+    Abort,
+    /// 冲突文件保留本地版本
+    KeepLocal,
+    /// 冲突文件使用上游版本
+    TakeUpstream,
+    /// 文本冲突写入冲突标记，其余冲突保留本地版本
+    Markers,
+}
+
+impl SkillConflictStrategy {
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.unwrap_or("abort") {
+            "abort" => Ok(Self::Abort),
+            "keep-local" => Ok(Self::KeepLocal),
+            "take-upstream" => Ok(Self::TakeUpstream),
+            "markers" => Ok(Self::Markers),
+            other => Err(format!("未知的冲突处理策略: {}", other)),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Abort => "中止",
+            Self::KeepLocal => "保留本地版本",
+            Self::TakeUpstream => "使用上游版本",
+            Self::Markers => "写入冲突标记",
+        }
+    }
+}
+
+enum FileResolution {
+    /// 使用上游版本（上游不存在时删除）
+    Upstream,
+    /// 保留本地版本（本地不存在时删除）
+    Local,
+    /// 自动合并成功
+    Merged(Vec<u8>),
+    Conflict {
+        reason: &'static str,
+        /// 带冲突标记的合并结果（仅文本冲突）
+        markers: Option<Vec<u8>>,
+    },
+}
+
+/// 合并计划
+pub struct SkillMergePlan {
+    resolutions: Vec<(String, FileResolution)>,
+    pub local_modified_files: Vec<String>,
+}
+
+impl SkillMergePlan {
+    pub fn merged_files(&self) -> Vec<String> {
+        self.resolutions
+            .iter()
+            .filter(|(_, r)| matches!(r, FileResolution::Merged(_)))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    pub fn kept_local_files(&self) -> Vec<String> {
+        self.resolutions
+            .iter()
+            .filter(|(_, r)| matches!(r, FileResolution::Local))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    pub fn conflicts(&self) -> Vec<SkillMergeConflict> {
+        self.resolutions
+            .iter()
+            .filter_map(|(path, r)| match r {
+                FileResolution::Conflict { reason, .. } => Some(SkillMergeConflict {
+                    path: path.clone(),
+                    reason: reason.to_string(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.resolutions
+            .iter()
+            .any(|(_, r)| matches!(r, FileResolution::Conflict { .. }))
+    }
+}
+
+fn manifest_hashes(manifest: &SkillManifest) -> HashMap<&str, &str> {
+    manifest
+        .files
+        .iter()
+        .map(|f| (f.path.as_str(), f.hash.as_str()))
+        .collect()
+}
+
+/// 对比安装时记录的清单与当前文件，返回本地修改过的文件（修改、新增、删除）
+pub fn detect_local_modifications(stored: &SkillManifest, current: &SkillManifest) -> Vec<String> {
+    let stored_files = manifest_hashes(stored);
+    let current_files = manifest_hashes(current);
+
+    let paths: BTreeSet<&str> = stored_files.keys().chain(current_files.keys()).copied().collect();
+    paths
+        .into_iter()
+        .filter(|path| stored_files.get(path) != current_files.get(path))
+        .map(|path| path.to_string())
+        .collect()
+}
+
+fn is_text(content: &[u8]) -> bool {
+    !content.contains(&0) && std::str::from_utf8(content).is_ok()
+}
+
+/// 读取基线提交中的文件内容
+fn read_base_file(base: &SkillMergeBase, path: &str) -> Option<Vec<u8>> {
+    let path = path.replace('\\', "/");
+    let object = if base.subpath.is_empty() {
+        format!("{}:{}", base.commit, path)
+    } else {
+        format!("{}:{}/{}", base.commit, base.subpath.trim_end_matches('/'), path)
+    };
+
+    let output = run_git(Some(base.repo_dir), &["show", &object], base.auth).ok()?;
+
+    if output.status.success() {
+        Some(output.stdout)
+    } else {
+        None
+    }
+}
+
+/// 使用 git merge-file 合并文本，返回 (是否有冲突, 合并结果)
+fn merge_text(work_dir: &Path, local: &[u8], base: &[u8], upstream: &[u8]) -> Result<(bool, Vec<u8>), String> {
+    fs::create_dir_all(work_dir).map_err(|e| format!("创建合并临时目录失败: {}", e))?;
+
+    let local_path = work_dir.join("local");
+    let base_path = work_dir.join("base");
+    let upstream_path = work_dir.join("upstream");
+    fs::write(&local_path, local).map_err(|e| format!("写入合并临时文件失败: {}", e))?;
+    fs::write(&base_path, base).map_err(|e| format!("写入合并临时文件失败: {}", e))?;
+    fs::write(&upstream_path, upstream).map_err(|e| format!("写入合并临时文件失败: {}", e))?;
+
+    let output = Command::new("git")
+        .args(["merge-file", "-p", "-L", "本地", "-L", "基线", "-L", "上游"])
+        .arg(&local_path)
+        .arg(&base_path)
+        .arg(&upstream_path)
+        .output()
+        .map_err(|e| format!("执行 git merge-file 失败: {}", e))?;
+
+    // 退出码为冲突数量，负数（>= 128）表示出错
+    match output.status.code() {
+        Some(0) => Ok((false, output.stdout)),
+        Some(code) if (1..128).contains(&code) => Ok((true, output.stdout)),
+        _ => Err(format!(
+            "git merge-file 失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+/// 计算合并计划
+/// stored: 安装时记录的清单（没有时无法识别本地修改，直接使用上游版本）
+pub fn plan_skill_merge(
+    local_dir: &Path,
+    upstream_dir: &Path,
+    stored: Option<&SkillManifest>,
+    local: &SkillManifest,
+    upstream: &SkillManifest,
+    base: Option<&SkillMergeBase>,
+    work_dir: &Path,
+) -> SkillMergePlan {
+    let Some(stored) = stored else {
+        return SkillMergePlan {
+            resolutions: Vec::new(),
+            local_modified_files: Vec::new(),
+        };
+    };
+
+    let stored_files = manifest_hashes(stored);
+    let local_files = manifest_hashes(local);
+    let upstream_files = manifest_hashes(upstream);
+
+    let paths: BTreeSet<&str> = stored_files
+        .keys()
+        .chain(local_files.keys())
+        .chain(upstream_files.keys())
+        .copied()
+        .collect();
+
+    let mut resolutions = Vec::new();
+    let mut local_modified_files = Vec::new();
+
+    for path in paths {
+        let stored_hash = stored_files.get(path);
+        let local_hash = local_files.get(path);
+        let upstream_hash = upstream_files.get(path);
+
+        let local_changed = local_hash != stored_hash;
+        let upstream_changed = upstream_hash != stored_hash;
+
+        if local_changed {
+            local_modified_files.push(path.to_string());
+        }
+
+        let resolution = if !local_changed {
+            FileResolution::Upstream
+        } else if !upstream_changed || local_hash == upstream_hash {
+            FileResolution::Local
+        } else if local_hash.is_none() {
+            FileResolution::Conflict { reason: "deleted-locally", markers: None }
+        } else if upstream_hash.is_none() {
+            FileResolution::Conflict { reason: "deleted-upstream", markers: None }
+        } else {
+            merge_file(local_dir, upstream_dir, path, stored_hash.is_some(), base, work_dir)
+        };
+
+        resolutions.push((path.to_string(), resolution));
+    }
+
+    SkillMergePlan {
+        resolutions,
+        local_modified_files,
+    }
+}
+
+/// 两边都修改了同一文件时尝试三方合并
+fn merge_file(
+    local_dir: &Path,
+    upstream_dir: &Path,
+    path: &str,
+    tracked: bool,
+    base: Option<&SkillMergeBase>,
+    work_dir: &Path,
+) -> FileResolution {
+    let (Ok(local), Ok(upstream)) = (fs::read(local_dir.join(path)), fs::read(upstream_dir.join(path))) else {
+        return FileResolution::Conflict { reason: "content", markers: None };
+    };
+
+    if !is_text(&local) || !is_text(&upstream) {
+        return FileResolution::Conflict { reason: "binary", markers: None };
+    }
+
+    // 两边新增的同名文件以空内容为基线
+    let base_content = if tracked {
+        match base.and_then(|base| read_base_file(base, path)) {
+            Some(content) => content,
+            None => return FileResolution::Conflict { reason: "missing-base", markers: None },
+        }
+    } else {
+        Vec::new()
+    };
+
+    match merge_text(work_dir, &local, &base_content, &upstream) {
+        Ok((false, merged)) => FileResolution::Merged(merged),
+        Ok((true, markers)) => FileResolution::Conflict {
+            reason: if tracked { "content" } else { "added-both" },
+            markers: Some(markers),
+        },
+        Err(e) => {
+            eprintln!("⚠️  [Backend] 合并 {} 失败: {}", path, e);
+            FileResolution::Conflict { reason: "content", markers: None }
+        }
+    }
+}
+
+/// 在输出目录（已包含上游版本）上应用合并计划
+pub fn apply_skill_merge(
+    plan: &SkillMergePlan,
+    local_dir: &Path,
+    output_dir: &Path,
+    strategy: SkillConflictStrategy,
+) -> Result<(), String> {
+    for (path, resolution) in &plan.resolutions {
+        let target: PathBuf = output_dir.join(path);
+
+        match resolution {
+            FileResolution::Upstream => {}
+            FileResolution::Local => restore_local_file(local_dir, output_dir, path)?,
+            FileResolution::Merged(content) => write_file(&target, content)?,
+            FileResolution::Conflict { markers, .. } => match strategy {
+                SkillConflictStrategy::Abort => {
+                    return Err(format!("文件 {} 存在冲突", path));
+                }
+                SkillConflictStrategy::TakeUpstream => {}
+                SkillConflictStrategy::KeepLocal => restore_local_file(local_dir, output_dir, path)?,
+                SkillConflictStrategy::Markers => match markers {
+                    Some(content) => write_file(&target, content)?,
+                    None => restore_local_file(local_dir, output_dir, path)?,
+                },
+            },
+        }
+    }
+
+    Ok(())
+}
+
+fn write_file(target: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    fs::write(target, content).map_err(|e| format!("写入合并结果失败: {}", e))
+}
+
+/// 用本地版本覆盖输出目录中的文件（本地已删除时同步删除）
+fn restore_local_file(local_dir: &Path, output_dir: &Path, path: &str) -> Result<(), String> {
+    let source = local_dir.join(path);
+    let target = output_dir.join(path);
+
+    if source.is_file() {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+        }
+        fs::copy(&source, &target).map_err(|e| format!("保留本地文件 {} 失败: {}", path, e))?;
+    } else if target.exists() {
+        fs::remove_file(&target).map_err(|e| format!("删除文件 {} 失败: {}", path, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,519 @@
+// Skill 签名验证：skill 仓库发布用 minisign 签名的文件清单，安装和更新时用本地信任的发布者公钥验证
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skills::{calculate_file_hash, generate_skill_manifest, SkillFileInfo, SkillManifest};
+
+/// 发布者随 skill 发布的文件清单（与 .manifest.json 格式相同）
+pub const SIGNED_MANIFEST_FILE_NAME: &str = ".skill-manifest.json";
+/// 对清单文件的 minisign 签名
+pub const SIGNATURE_FILE_NAME: &str = ".skill-manifest.json.minisig";
+
+const TRUST_STORE_FILE_NAME: &str = "skill-trust.json";
+
+fn get_cobalt_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".cobalt"))
+        .ok_or_else(|| "无法获取用户主目录".to_string())
+}
+
+/// 签名验证结果
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureStatus {
+    /// 签名有效且文件与清单一致
+    Verified,
+    /// 没有签名文件
+    Unsigned,
+    /// 签名使用的公钥不在信任列表中
+    UntrustedKey,
+    /// 签名无效，或文件与签名的清单不一致
+    Invalid,
+}
+
+impl SignatureStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Verified => "已验证",
+            Self::Unsigned => "未签名",
+            Self::UntrustedKey => "公钥不受信任",
+            Self::Invalid => "签名无效",
+        }
+    }
+}
+
+/// Skill 的签名信息，记录在注册表的 metadata 中
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillSignatureInfo {
+    pub status: SignatureStatus,
+    /// 签名使用的公钥 ID（十六进制）
+    #[serde(default)]
+    pub key_id: Option<String>,
+    /// 信任列表中该公钥对应的发布者
+    #[serde(default)]
+    pub publisher: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    pub checked_at: String,
+}
+
+impl SkillSignatureInfo {
+    fn new(status: SignatureStatus, message: Option<String>) -> Self {
+        Self {
+            status,
+            key_id: None,
+            publisher: None,
+            message,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// 信任的发布者公钥
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedKey {
+    pub key_id: String,
+    pub name: String,
+    /// minisign 公钥（base64，即 minisign.pub 的第二行）
+    pub public_key: String,
+    pub added_at: String,
+}
+
+/// 签名策略
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignaturePolicy {
+    /// 安装或更新非官方来源的 Skill 时要求签名验证通过
+    #[serde(default)]
+    pub require_signatures: bool,
+    /// 官方来源仓库，不要求签名
+    #[serde(default = "default_official_sources")]
+    pub official_sources: Vec<String>,
+}
+
+fn default_official_sources() -> Vec<String> {
+    vec!["https://github.com/anthropics/skills".to_string()]
+}
+
+impl Default for SignaturePolicy {
+    fn default() -> Self {
+        Self {
+            require_signatures: false,
+            official_sources: default_official_sources(),
+        }
+    }
+}
+
+/// 本地信任库：~/.cobalt/skill-trust.json
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillTrustStore {
+    #[serde(default)]
+    pub keys: Vec<TrustedKey>,
+    #[serde(default)]
+    pub policy: SignaturePolicy,
+}
+
+fn get_trust_store_path() -> Result<PathBuf, String> {
+    Ok(get_cobalt_dir()?.join(TRUST_STORE_FILE_NAME))
+}
+
+pub fn read_trust_store() -> Result<SkillTrustStore, String> {
+    let path = get_trust_store_path()?;
+    if !path.exists() {
+        return Ok(SkillTrustStore::default());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取信任库失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析信任库失败: {}", e))
+}
+
+fn write_trust_store(store: &SkillTrustStore) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(store).map_err(|e| format!("序列化信任库失败: {}", e))?;
+    super::skill_transaction::write_file_atomic(&get_trust_store_path()?, content.as_bytes())
+}
+
+/// 从 base64 编码的公钥或签名中取出 key ID（第 2-10 字节，按 minisign 的习惯以小端十六进制显示）
+fn decode_key_id(encoded: &str) -> Option<String> {
+    let bytes = base64::engine::general_purpose::STANDARD.decode(encoded.trim()).ok()?;
+    let id = bytes.get(2..10)?;
+    Some(id.iter().rev().map(|b| format!("{:02X}", b)).collect())
+}
+
+/// 接受完整的 minisign.pub 内容或只有 base64 的一行
+fn parse_public_key_input(input: &str) -> Result<String, String> {
+    let line = input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+        .ok_or_else(|| "公钥不能为空".to_string())?;
+
+    minisign_verify::PublicKey::from_base64(line).map_err(|e| format!("无效的 minisign 公钥: {}", e))?;
+    Ok(line.to_string())
+}
+
+/// 统一仓库地址写法，便于比较 https 与 ssh 地址
+pub fn normalize_repo_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/').trim_end_matches(".git").to_lowercase();
+    let without_scheme = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("ssh://");
+    let without_user = without_scheme.strip_prefix("git@").unwrap_or(without_scheme);
+    without_user.replacen(':', "/", 1)
+}
+
+/// 签名覆盖的文件：与安装时复制的内容一致，包括隐藏文件；
+/// 只排除 .git、签名本身以及 Cobalt 安装后写入的状态文件；包含符号链接时报错
+fn collect_signed_files(skill_dir: &Path) -> Result<Vec<SkillFileInfo>, String> {
+    const EXCLUDED_ROOT_FILES: &[&str] = &[
+        SIGNED_MANIFEST_FILE_NAME,
+        SIGNATURE_FILE_NAME,
+        ".manifest.json",
+        ".cobalt-state.json",
+        super::skill_adapters::ADAPTER_STATE_FILE_NAME,
+    ];
+
+    let generated_files = super::skill_adapters::read_generated_files(skill_dir);
+    let mut files = Vec::new();
+    let mut pending = vec![skill_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("读取目录失败: {}", e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_name = entry.file_name();
+            if file_name == ".git" {
+                continue;
+            }
+            if dir == skill_dir && EXCLUDED_ROOT_FILES.iter().any(|name| file_name == *name) {
+                continue;
+            }
+
+            let relative = path
+                .strip_prefix(skill_dir)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            // 不跟随符号链接：链接可以指向 skill 之外、签名之后仍可随意修改的文件
+            let metadata = fs::symlink_metadata(&path).map_err(|e| format!("获取文件元数据失败: {}", e))?;
+            if metadata.file_type().is_symlink() {
+                return Err(format!("签名不支持符号链接: {}", relative));
+            }
+            if metadata.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !metadata.is_file() || generated_files.contains(&relative) {
+                continue;
+            }
+            files.push(SkillFileInfo {
+                path: relative,
+                hash: calculate_file_hash(&path)?,
+                size: metadata.len(),
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn manifest_file_hashes(manifest: &SkillManifest) -> BTreeMap<String, &str> {
+    manifest
+        .files
+        .iter()
+        .map(|f| (f.path.replace('\\', "/"), f.hash.as_str()))
+        .collect()
+}
+
+/// 验证 skill 目录中的签名清单
+pub fn verify_skill_dir(skill_dir: &Path) -> SkillSignatureInfo {
+    let signature_path = skill_dir.join(SIGNATURE_FILE_NAME);
+    let manifest_path = skill_dir.join(SIGNED_MANIFEST_FILE_NAME);
+    if !signature_path.exists() {
+        return SkillSignatureInfo::new(SignatureStatus::Unsigned, None);
+    }
+
+    let invalid = |message: String| SkillSignatureInfo::new(SignatureStatus::Invalid, Some(message));
+
+    let signature_text = match fs::read_to_string(&signature_path) {
+        Ok(text) => text,
+        Err(e) => return invalid(format!("读取签名文件失败: {}", e)),
+    };
+    let signature = match minisign_verify::Signature::decode(&signature_text) {
+        Ok(signature) => signature,
+        Err(e) => return invalid(format!("签名格式无效: {}", e)),
+    };
+    let manifest_bytes = match fs::read(&manifest_path) {
+        Ok(bytes) => bytes,
+        Err(_) => return invalid(format!("缺少签名对应的 {}", SIGNED_MANIFEST_FILE_NAME)),
+    };
+
+    // 签名文件第二行是签名本体，其中包含 key ID
+    let key_id = signature_text.lines().nth(1).and_then(decode_key_id);
+    let store = read_trust_store().unwrap_or_default();
+    let Some(trusted) = store.keys.iter().find(|k| Some(&k.key_id) == key_id.as_ref()) else {
+        let mut info = SkillSignatureInfo::new(
+            SignatureStatus::UntrustedKey,
+            Some("签名使用的公钥不在信任列表中".to_string()),
+        );
+        info.key_id = key_id;
+        return info;
+    };
+
+    let mut info = SkillSignatureInfo::new(SignatureStatus::Verified, None);
+    info.key_id = key_id;
+    info.publisher = Some(trusted.name.clone());
+
+    let verified = minisign_verify::PublicKey::from_base64(&trusted.public_key)
+        .and_then(|key| key.verify(&manifest_bytes, &signature, false));
+    if let Err(e) = verified {
+        info.status = SignatureStatus::Invalid;
+        info.message = Some(format!("签名验证失败: {}", e));
+        return info;
+    }
+
+    // 签名只保证清单未被篡改，还要确认文件与清单一致
+    let signed_manifest: SkillManifest = match serde_json::from_slice(&manifest_bytes) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            info.status = SignatureStatus::Invalid;
+            info.message = Some(format!("解析签名清单失败: {}", e));
+            return info;
+        }
+    };
+    let actual_manifest = match collect_signed_files(skill_dir) {
+        Ok(files) => SkillManifest { files, ..Default::default() },
+        Err(e) => {
+            info.status = SignatureStatus::Invalid;
+            info.message = Some(e);
+            return info;
+        }
+    };
+
+    // 清单之外的文件（包括隐藏文件）同样视为不一致
+    let signed_files = manifest_file_hashes(&signed_manifest);
+    let actual_files = manifest_file_hashes(&actual_manifest);
+    let mismatched: Vec<&String> = signed_files
+        .keys()
+        .chain(actual_files.keys())
+        .filter(|path| signed_files.get(*path) != actual_files.get(*path))
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if !mismatched.is_empty() {
+        info.status = SignatureStatus::Invalid;
+        info.message = Some(format!(
+            "{} 个文件与签名清单不一致: {}",
+            mismatched.len(),
+            mismatched.iter().take(5).map(|p| p.as_str()).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    info
+}
+
+/// 按签名策略检查是否允许安装
+pub fn enforce_signature_policy(
+    info: &SkillSignatureInfo,
+    repo_url: &str,
+    skill_name: &str,
+) -> Result<(), String> {
+    // 签名无效说明内容被篡改，无论策略如何都拒绝
+    if info.status == SignatureStatus::Invalid {
+        return Err(format!(
+            "Skill '{}' 签名无效: {}",
+            skill_name,
+            info.message.as_deref().unwrap_or_default()
+        ));
+    }
+
+    let policy = read_trust_store()?.policy;
+    if !policy.require_signatures || info.status == SignatureStatus::Verified {
+        return Ok(());
+    }
+
+    let repo = normalize_repo_url(repo_url);
+    if policy.official_sources.iter().any(|source| normalize_repo_url(source) == repo) {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Skill '{}' {}，当前策略要求非官方来源的 Skill 必须由信任的发布者签名",
+        skill_name,
+        info.status.label()
+    ))
+}
+
+/// 验证签名并按策略检查，返回要记录到注册表的签名信息
+pub fn check_skill_signature(skill_dir: &Path, repo_url: &str, skill_name: &str) -> Result<SkillSignatureInfo, String> {
+    let info = verify_skill_dir(skill_dir);
+    match info.status {
+        SignatureStatus::Verified => println!(
+            "🔏 [Backend] Skill '{}' 签名已验证（{}）",
+            skill_name,
+            info.publisher.as_deref().unwrap_or_default()
+        ),
+        status => println!("🔓 [Backend] Skill '{}' {}", skill_name, status.label()),
+    }
+    enforce_signature_policy(&info, repo_url, skill_name)?;
+    Ok(info)
+}
+
+/// 获取信任库（公钥列表和签名策略）
+#[tauri::command]
+pub fn get_skill_trust_store() -> Result<SkillTrustStore, String> {
+    read_trust_store()
+}
+
+/// 添加信任的发布者公钥
+#[tauri::command]
+pub fn add_trusted_key(name: String, public_key: String) -> Result<TrustedKey, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("发布者名称不能为空".to_string());
+    }
+
+    let public_key = parse_public_key_input(&public_key)?;
+    let key_id = decode_key_id(&public_key).ok_or_else(|| "无效的 minisign 公钥".to_string())?;
+
+    let mut store = read_trust_store()?;
+    if store.keys.iter().any(|k| k.key_id == key_id) {
+        return Err(format!("公钥 {} 已在信任列表中", key_id));
+    }
+
+    let key = TrustedKey {
+        key_id,
+        name,
+        public_key,
+        added_at: chrono::Utc::now().to_rfc3339(),
+    };
+    store.keys.push(key.clone());
+    write_trust_store(&store)?;
+
+    println!("✅ [Backend] 已信任发布者 {} 的公钥 {}", key.name, key.key_id);
+    Ok(key)
+}
+
+/// 移除信任的公钥
+#[tauri::command]
+pub fn remove_trusted_key(key_id: String) -> Result<(), String> {
+    let mut store = read_trust_store()?;
+    let before = store.keys.len();
+    store.keys.retain(|k| k.key_id != key_id);
+    if store.keys.len() == before {
+        return Err(format!("公钥 {} 不在信任列表中", key_id));
+    }
+    write_trust_store(&store)
+}
+
+/// 修改签名策略
+#[tauri::command]
+pub fn update_signature_policy(policy: SignaturePolicy) -> Result<(), String> {
+    let mut store = read_trust_store()?;
+    store.policy = SignaturePolicy {
+        require_signatures: policy.require_signatures,
+        official_sources: policy
+            .official_sources
+            .into_iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+    };
+    write_trust_store(&store)
+}
+
+/// 为发布者生成待签名的清单，之后用 `minisign -Sm .skill-manifest.json` 签名
+#[tauri::command]
+pub fn generate_signing_manifest(skill_path: String) -> Result<String, String> {
+    let skill_dir = PathBuf::from(&skill_path);
+    if !skill_dir.join("SKILL.md").exists() {
+        return Err(format!("{} 中没有 SKILL.md", skill_path));
+    }
+
+    let mut manifest = generate_skill_manifest(&skill_dir, None)?;
+    manifest.files = collect_signed_files(&skill_dir)?;
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| format!("序列化清单失败: {}", e))?;
+    let manifest_path = skill_dir.join(SIGNED_MANIFEST_FILE_NAME);
+    super::skill_transaction::write_file_atomic(&manifest_path, content.as_bytes())?;
+
+    // 旧签名已经和新清单不匹配
+    let signature_path = skill_dir.join(SIGNATURE_FILE_NAME);
+    if signature_path.exists() {
+        fs::remove_file(&signature_path).map_err(|e| format!("删除旧签名失败: {}", e))?;
+    }
+
+    println!("✅ [Backend] 已生成签名清单: {:?}", manifest_path);
+    Ok(manifest_path.to_string_lossy().to_string())
+}
+
+/// 重新验证已安装 Skill 的签名（本地修改过的文件会导致验证失败）
+#[tauri::command]
+pub fn verify_skill_signature(
+    skill_name: String,
+    workspace_path: Option<String>,
+) -> Result<SkillSignatureInfo, String> {
+    let tool_dirs = super::skills::resolve_local_skill_tool_dirs(&skill_name, workspace_path.as_deref())?;
+    let (_, skill_dir) = tool_dirs
+        .first()
+        .ok_or_else(|| format!("Skill '{}' 不存在", skill_name))?;
+    let info = verify_skill_dir(skill_dir);
+
+    // 只在全局模式下更新注册表
+    if workspace_path.is_none() {
+        let mut registry = super::skills::read_skill_registry()?;
+        if let Some(meta) = registry
+            .skills
+            .iter_mut()
+            .find(|s| s.name == skill_name)
+            .and_then(|entry| entry.metadata.as_mut())
+        {
+            meta.signature = Some(info.clone());
+            super::skills::write_skill_registry(registry)?;
+        }
+    }
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_files_include_hidden_files() {
+        let dir = std::env::temp_dir().join(format!("cobalt-signature-test-{}", uuid::Uuid::new_v4()));
+        for (name, content) in [
+            ("SKILL.md", "---\nname: demo\n---\n"),
+            (".scripts/payload.sh", "echo hi\n"),
+            (".git/HEAD", "ref: refs/heads/main\n"),
+            (".manifest.json", "{}"),
+            (SIGNED_MANIFEST_FILE_NAME, "{}"),
+            (SIGNATURE_FILE_NAME, "sig"),
+        ] {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let paths: Vec<String> = collect_signed_files(&dir).unwrap().into_iter().map(|f| f.path).collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(paths, vec![".scripts/payload.sh", "SKILL.md"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signed_files_reject_symlinks() {
+        let dir = std::env::temp_dir().join(format!("cobalt-signature-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(dir.join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        std::os::unix::fs::symlink("/etc/hosts", dir.join("scripts/run.sh")).unwrap();
+
+        let result = collect_signed_files(&dir);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(result.unwrap_err(), "签名不支持符号链接: scripts/run.sh");
+    }
+}
@@ -0,0 +1,116 @@
+// 稀疏获取 Skill：部分克隆（只下载提交和目录结构）后只检出需要的 skill 目录
+// 大型 monorepo 中只需要一两个 skill 时，不必下载整个仓库的文件内容
+use std::fs;
+use std::path::Path;
+
+use super::skills::{
+    clone_repo_at_ref, clone_repo_without_checkout, is_commit_sha, run_git, skill_dir_matches, GitAuthInput,
+    AUTH_REQUIRED_PREFIX,
+};
+
+/// 克隆仓库，只检出 skills/ 下指定的 skill 目录（以及所有 SKILL.md，供扫描和解析依赖）
+/// 仓库没有 skills/ 目录时检出整个仓库；部分克隆或稀疏检出失败时回退到完整克隆
+pub fn clone_repo_skills(
+    url: &str,
+    target_dir: &Path,
+    shallow: bool,
+    git_ref: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    skill_names: &[String],
+) -> Result<(), String> {
+    let target = target_dir.to_str().ok_or_else(|| "临时目录路径无效".to_string())?;
+
+    match sparse_clone(url, target_dir, shallow, git_ref, auth, skill_names) {
+        Ok(()) => Ok(()),
+        // 认证问题换成完整克隆也无法解决，直接交给前端处理
+        Err(e) if e.starts_with(AUTH_REQUIRED_PREFIX) || e.starts_with("认证失败") => Err(e),
+        Err(e) => {
+            eprintln!("⚠️  [Backend] 稀疏检出失败，改为完整克隆: {}", e);
+            let _ = fs::remove_dir_all(target_dir);
+            clone_repo_at_ref(url, target, shallow, git_ref, auth)
+        }
+    }
+}
+
+fn sparse_clone(
+    url: &str,
+    target_dir: &Path,
+    shallow: bool,
+    git_ref: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    skill_names: &[String],
+) -> Result<(), String> {
+    let target = target_dir.to_str().ok_or_else(|| "临时目录路径无效".to_string())?;
+    clone_repo_without_checkout(url, target, shallow, git_ref, auth)?;
+
+    let commit = git_ref.map(str::trim).filter(|r| is_commit_sha(r));
+    match skill_subdir_patterns(target_dir, commit.unwrap_or("HEAD"), skill_names, auth) {
+        Some(patterns) => {
+            let mut args = vec!["sparse-checkout", "set", "--no-cone"];
+            args.extend(patterns.iter().map(String::as_str));
+            git(target_dir, &args, auth)?;
+        }
+        None => println!("📝 [Backend] 仓库没有 skills/ 目录，检出整个仓库"),
+    }
+
+    match commit {
+        Some(commit) => {
+            println!("📌 [Backend] 固定到提交: {}", commit);
+            git(target_dir, &["checkout", "--quiet", commit], auth)?;
+        }
+        None => {
+            git(target_dir, &["checkout", "--quiet"], auth)?;
+        }
+    }
+    Ok(())
+}
+
+/// 在已稀疏检出的仓库中追加检出 skill 目录（例如同一仓库中的依赖），完整检出的仓库不做处理
+pub fn add_repo_skills(repo_dir: &Path, skill_names: &[String], auth: Option<&GitAuthInput>) -> Result<(), String> {
+    if !repo_dir.join(".git").join("info").join("sparse-checkout").exists() {
+        return Ok(());
+    }
+
+    let Some(patterns) = skill_subdir_patterns(repo_dir, "HEAD", skill_names, auth) else {
+        return Ok(());
+    };
+    let mut args = vec!["sparse-checkout", "add"];
+    args.extend(patterns.iter().map(String::as_str));
+    git(repo_dir, &args, auth).map(|_| ())
+}
+
+/// 按目录结构（不下载文件内容）生成稀疏检出规则；仓库没有 skills/ 目录时返回 None
+fn skill_subdir_patterns(
+    repo_dir: &Path,
+    rev: &str,
+    skill_names: &[String],
+    auth: Option<&GitAuthInput>,
+) -> Option<Vec<String>> {
+    let listing = git(repo_dir, &["ls-tree", "--name-only", "-d", &format!("{}:skills", rev)], auth).ok()?;
+    let dirs: Vec<&str> = listing.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+
+    let mut patterns = vec!["/skills/*/SKILL.md".to_string()];
+    for skill_name in skill_names {
+        let matched = dirs
+            .iter()
+            .find(|dir| **dir == skill_name.as_str())
+            .or_else(|| dirs.iter().find(|dir| skill_dir_matches(dir, skill_name)));
+        if let Some(dir) = matched {
+            println!("📦 [Backend] 稀疏检出: skills/{}", dir);
+            patterns.push(format!("/skills/{}/", dir));
+        }
+    }
+    Some(patterns)
+}
+
+fn git(repo_dir: &Path, args: &[&str], auth: Option<&GitAuthInput>) -> Result<String, String> {
+    let output = run_git(Some(repo_dir), args, auth)?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} 失败: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -0,0 +1,179 @@
+// Skill 操作事务：记录安装、更新、启用/禁用、卸载过程中的每个副作用，
+// 失败或提前返回时按相反顺序全部撤销
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skills::move_or_copy_skill_dir;
+
+enum UndoAction {
+    /// 事务中新建的路径，撤销时删除
+    Created(PathBuf),
+    /// 被删除或替换的路径已移到事务目录，撤销时移回
+    Removed { original: PathBuf, backup: PathBuf },
+    /// 被移动的路径，撤销时移回原位置
+    Moved { from: PathBuf, to: PathBuf },
+    /// 写入前的文件内容（None 表示原来不存在）
+    File { path: PathBuf, content: Option<Vec<u8>> },
+}
+
+/// Skill 操作事务
+/// 未调用 commit 就被丢弃（包括 ? 提前返回）时自动回滚
+pub struct SkillTransaction {
+    label: String,
+    work_dir: PathBuf,
+    actions: Vec<UndoAction>,
+    committed: bool,
+}
+
+impl SkillTransaction {
+    pub fn begin(label: &str) -> Result<Self, String> {
+        let work_dir = dirs::home_dir()
+            .map(|home| home.join(".cobalt"))
+            .ok_or_else(|| "无法获取用户主目录".to_string())?
+            .join("skills")
+            .join("transactions")
+            .join(uuid::Uuid::new_v4().to_string());
+
+        Ok(Self {
+            label: label.to_string(),
+            work_dir,
+            actions: Vec::new(),
+            committed: false,
+        })
+    }
+
+    /// 记录即将创建的路径（在创建前调用，创建到一半失败也能清理）
+    pub fn track_new_path(&mut self, path: &Path) {
+        self.actions.push(UndoAction::Created(path.to_path_buf()));
+    }
+
+    /// 删除路径：先移到事务目录，提交后才真正删除
+    pub fn remove_path(&mut self, path: &Path) -> Result<(), String> {
+        if fs::symlink_metadata(path).is_err() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.work_dir).map_err(|e| format!("创建事务目录失败: {}", e))?;
+        let backup = self.work_dir.join(self.actions.len().to_string());
+        move_or_copy_skill_dir(&path.to_path_buf(), &backup)
+            .map_err(|e| format!("移除 {} 失败: {}", path.display(), e))?;
+
+        self.actions.push(UndoAction::Removed {
+            original: path.to_path_buf(),
+            backup,
+        });
+        Ok(())
+    }
+
+    /// 移动路径（目标位置必须不存在）
+    pub fn move_path(&mut self, from: &Path, to: &Path) -> Result<(), String> {
+        move_or_copy_skill_dir(&from.to_path_buf(), &to.to_path_buf())?;
+        self.actions.push(UndoAction::Moved {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    /// 写入文件前保存原内容（同一文件只保存第一次）
+    pub fn snapshot_file(&mut self, path: &Path) -> Result<(), String> {
+        let already_saved = self
+            .actions
+            .iter()
+            .any(|action| matches!(action, UndoAction::File { path: saved, .. } if saved == path));
+        if already_saved {
+            return Ok(());
+        }
+
+        let content = if path.exists() {
+            Some(fs::read(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?)
+        } else {
+            None
+        };
+        self.actions.push(UndoAction::File {
+            path: path.to_path_buf(),
+            content,
+        });
+        Ok(())
+    }
+
+    /// 提交事务，清理被移走的旧内容
+    pub fn commit(mut self) {
+        self.committed = true;
+        if self.work_dir.exists() {
+            let _ = fs::remove_dir_all(&self.work_dir);
+        }
+    }
+
+    fn rollback(&mut self) {
+        if self.actions.is_empty() {
+            return;
+        }
+        eprintln!("↩️  [Backend] {}失败，回滚 {} 个操作", self.label, self.actions.len());
+
+        while let Some(action) = self.actions.pop() {
+            let result = match &action {
+                UndoAction::Created(path) => remove_any(path),
+                UndoAction::Removed { original, backup } => {
+                    remove_any(original).and_then(|_| move_or_copy_skill_dir(backup, original))
+                }
+                UndoAction::Moved { from, to } => {
+                    remove_any(from).and_then(|_| move_or_copy_skill_dir(to, from))
+                }
+                UndoAction::File { path, content: Some(content) } => {
+                    write_file_atomic(path, content)
+                }
+                UndoAction::File { path, content: None } => remove_any(path),
+            };
+
+            if let Err(e) = result {
+                eprintln!("⚠️  [Backend] 回滚失败: {}", e);
+            }
+        }
+
+        if self.work_dir.exists() {
+            let _ = fs::remove_dir_all(&self.work_dir);
+        }
+    }
+}
+
+impl Drop for SkillTransaction {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.rollback();
+        }
+    }
+}
+
+/// 删除文件、目录或链接（不存在时忽略）
+fn remove_any(path: &Path) -> Result<(), String> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    let result = if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        // 指向目录的链接在 Windows 上需要用 remove_dir 删除
+        fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+    };
+    result.map_err(|e| format!("删除 {} 失败: {}", path.display(), e))
+}
+
+/// 先写入临时文件再重命名，避免写到一半时留下损坏的文件
+pub fn write_file_atomic(path: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("无效的文件路径: {}", path.display()))?;
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    fs::write(&temp_path, content).map_err(|e| format!("写入 {} 失败: {}", file_name, e))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("写入 {} 失败: {}", file_name, e)
+    })
+}
@@ -1,15 +1,28 @@
 // Skills 管理命令
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::cell::RefCell;
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
-
-const AUTH_REQUIRED_PREFIX: &str = "COBALT_AUTH_REQUIRED:";
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
+use tokio::sync::{mpsc, Notify, Semaphore};
+
+pub const AUTH_REQUIRED_PREFIX: &str = "COBALT_AUTH_REQUIRED:";
+/// 批量检查更新时每检查完一个 skill 推送一次结果（payload 为 SkillUpdateSummary）
+pub const SKILL_UPDATE_CHECKED_EVENT: &str = "skill-update-checked";
+/// 批量检查更新的默认并发数（按仓库计）
+const UPDATE_CHECK_CONCURRENCY: usize = 4;
+/// 批量检查更新时单个 skill 的默认超时时间
+const UPDATE_CHECK_TIMEOUT_SECS: u64 = 120;
+/// 安全扫描拦截安装时的错误前缀，后接被拦截 skill 的扫描报告（JSON）
+pub const SECURITY_BLOCKED_PREFIX: &str = "COBALT_SECURITY_BLOCKED:";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -109,7 +122,7 @@ echo %prompt% | findstr /i "sername" >nul && (
     Ok(path)
 }
 
-fn is_auth_related_error(stderr: &str) -> bool {
+pub fn is_auth_related_error(stderr: &str) -> bool {
     let s = stderr.to_lowercase();
     s.contains("authentication failed")
         || s.contains("could not read username")
@@ -119,7 +132,7 @@ fn is_auth_related_error(stderr: &str) -> bool {
         || s.contains("permission denied")
 }
 
-fn build_auth_required_error(url: &str, stderr: &str) -> String {
+pub fn build_auth_required_error(url: &str, stderr: &str) -> String {
     let is_ssh_issue = stderr.to_lowercase().contains("publickey") || stderr.to_lowercase().contains("passphrase");
     let challenge = GitAuthChallenge {
         message: "仓库需要认证，请输入凭据后重试".to_string(),
@@ -132,17 +145,141 @@ fn build_auth_required_error(url: &str, stderr: &str) -> String {
     format!("{}{}", AUTH_REQUIRED_PREFIX, payload)
 }
 
-fn run_git_clone(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitAuthInput>) -> Result<std::process::Output, String> {
+/// git clone 的方式
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CloneKind {
+    /// 普通克隆并检出
+    Checkout,
+    /// 部分克隆：只下载提交和目录结构，不检出，文件内容在检出时按需获取
+    Blobless,
+    /// 裸镜像，用于 Git 缓存
+    Mirror,
+}
+
+fn run_git_clone(
+    url: &str,
+    target_dir: &str,
+    shallow: bool,
+    branch: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    kind: CloneKind,
+) -> Result<std::process::Output, String> {
     let mut args = vec!["clone"];
     if shallow {
         args.push("--depth");
         args.push("1");
     }
+    if let Some(branch) = branch {
+        args.push("--branch");
+        args.push(branch);
+    }
+    match kind {
+        CloneKind::Checkout => {}
+        CloneKind::Blobless => {
+            args.push("--filter=blob:none");
+            args.push("--no-checkout");
+        }
+        CloneKind::Mirror => args.push("--mirror"),
+    }
     args.push(url);
     args.push(target_dir);
 
+    run_git(None, &args, auth)
+}
+
+thread_local! {
+    /// 当前线程上 git 命令的取消标记，见 with_git_cancellation
+    static GIT_CANCEL_FLAG: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// 在当前线程执行 f，期间 cancel 置位后 run_git 会结束正在运行的 git 进程并不再启动新的
+/// 只用于同步执行的代码（例如 spawn_blocking 中的批量检查），标记不会跟随 async 任务切换线程
+pub fn with_git_cancellation<T>(cancel: Arc<AtomicBool>, f: impl FnOnce() -> T) -> T {
+    let previous = GIT_CANCEL_FLAG.with(|flag| flag.replace(Some(cancel)));
+    let result = f();
+    GIT_CANCEL_FLAG.with(|flag| *flag.borrow_mut() = previous);
+    result
+}
+
+fn is_git_cancelled(cancel: &AtomicBool) -> bool {
+    cancel.load(Ordering::Relaxed)
+}
+
+/// 执行命令直到结束或被取消；取消时结束进程
+fn output_until_cancelled(cmd: &mut Command, cancel: &AtomicBool) -> std::io::Result<std::process::Output> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // 在单独的线程中读取输出，避免管道写满后进程阻塞
+    let mut stdout_pipe = child.stdout.take();
+    let stdout_reader = std::thread::spawn(move || {
+        let mut stdout = Vec::new();
+        if let Some(pipe) = stdout_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut stdout);
+        }
+        stdout
+    });
+    let mut stderr_pipe = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut stderr = Vec::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = pipe.read_to_end(&mut stderr);
+        }
+        stderr
+    });
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if is_git_cancelled(cancel) {
+            let _ = child.kill();
+            let _ = child.wait();
+            // git 启动的子进程（如 ssh）可能仍持有管道，不等待读取线程
+            return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "已取消"));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    Ok(std::process::Output { status, stdout, stderr })
+}
+
+/// 执行 git 命令；提供凭据时通过 askpass 脚本应答，否则禁止交互式提示
+/// 部分克隆的仓库在检出时还会按需下载文件内容，同样需要凭据
+pub fn run_git(
+    repo_dir: Option<&Path>,
+    args: &[&str],
+    auth: Option<&GitAuthInput>,
+) -> Result<std::process::Output, String> {
     let mut cmd = Command::new("git");
-    cmd.args(&args);
+    if let Some(repo_dir) = repo_dir {
+        cmd.arg("-C").arg(repo_dir);
+    }
+    // 有进度订阅时让 git 输出进度（stderr 非终端时默认不输出），--quiet 会屏蔽进度，一并去掉
+    let with_progress =
+        super::progress::is_active() && matches!(args.first(), Some(&"clone") | Some(&"fetch") | Some(&"checkout"));
+    if let Some((subcommand, rest)) = args.split_first() {
+        cmd.arg(subcommand);
+        if with_progress {
+            cmd.arg("--progress");
+            cmd.args(rest.iter().filter(|arg| **arg != "--quiet"));
+        } else {
+            cmd.args(rest);
+        }
+    }
+
+    let cancel = GIT_CANCEL_FLAG.with(|flag| flag.borrow().clone());
+    if cancel.as_deref().is_some_and(is_git_cancelled) {
+        return Err(format!("git {} 已取消", args.first().copied().unwrap_or_default()));
+    }
 
     let mut askpass_path: Option<PathBuf> = None;
     if let Some(auth_input) = auth {
@@ -163,7 +300,14 @@ fn run_git_clone(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitAu
         cmd.env("GIT_TERMINAL_PROMPT", "0");
     }
 
-    let output = cmd.output().map_err(|e| format!("执行 git clone 失败: {}", e));
+    let output = if with_progress {
+        super::progress::run_git_with_progress(&mut cmd, cancel.as_deref())
+    } else if let Some(cancel) = cancel.as_deref() {
+        output_until_cancelled(&mut cmd, cancel)
+    } else {
+        cmd.output()
+    }
+    .map_err(|e| format!("执行 git {} 失败: {}", args.first().copied().unwrap_or_default(), e));
     if let Some(path) = askpass_path {
         let _ = fs::remove_file(path);
     }
@@ -171,7 +315,92 @@ fn run_git_clone(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitAu
 }
 
 pub fn clone_repo(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitAuthInput>) -> Result<(), String> {
+    clone_repo_with_branch(url, target_dir, shallow, None, auth, CloneKind::Checkout)
+}
+
+/// 部分克隆仓库且不检出文件，由调用方设置稀疏检出后再检出
+/// git_ref 为提交 SHA 时完整获取历史（不含文件内容），检出由调用方完成
+pub fn clone_repo_without_checkout(
+    url: &str,
+    target_dir: &str,
+    shallow: bool,
+    git_ref: Option<&str>,
+    auth: Option<&GitAuthInput>,
+) -> Result<(), String> {
+    match git_ref.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(commit) if is_commit_sha(commit) => {
+            clone_repo_with_branch(url, target_dir, false, None, auth, CloneKind::Blobless)
+        }
+        branch => clone_repo_with_branch(url, target_dir, shallow, branch, auth, CloneKind::Blobless),
+    }
+}
+
+/// 判断 ref 是否为提交 SHA（7~40 位十六进制）
+pub fn is_commit_sha(git_ref: &str) -> bool {
+    (7..=40).contains(&git_ref.len()) && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 克隆仓库并切换到指定的分支、标签或提交
+/// git_ref 为空时等同于 clone_repo（默认分支）；提交 SHA 需要完整克隆后再 checkout
+pub fn clone_repo_at_ref(
+    url: &str,
+    target_dir: &str,
+    shallow: bool,
+    git_ref: Option<&str>,
+    auth: Option<&GitAuthInput>,
+) -> Result<(), String> {
+    let git_ref = git_ref.map(str::trim).filter(|r| !r.is_empty());
+
+    match git_ref {
+        None => clone_repo(url, target_dir, shallow, auth),
+        Some(commit) if is_commit_sha(commit) => {
+            println!("📌 [Backend] 固定到提交: {}", commit);
+            clone_repo_with_branch(url, target_dir, false, None, auth, CloneKind::Checkout)?;
+            checkout_repo_commit(Path::new(target_dir), commit, auth)
+        }
+        Some(branch) => {
+            println!("🏷️  [Backend] 使用分支/标签: {}", branch);
+            clone_repo_with_branch(url, target_dir, shallow, Some(branch), auth, CloneKind::Checkout)
+        }
+    }
+}
+
+/// 远程仓库先同步到 Git 缓存中的镜像，再从镜像本地克隆；缓存不可用时直接从远程克隆
+/// 镜像是完整克隆，浅克隆和部分克隆走镜像反而要下载全部历史和文件，因此直接从远程克隆
+fn clone_repo_with_branch(
+    url: &str,
+    target_dir: &str,
+    shallow: bool,
+    branch: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    kind: CloneKind,
+) -> Result<(), String> {
+    if !shallow && kind != CloneKind::Blobless && super::git_cache::is_cacheable_url(url) {
+        match super::git_cache::sync_mirror(url, auth) {
+            Ok(mirror_dir) => return super::git_cache::clone_from_mirror(&mirror_dir, target_dir, branch, kind),
+            // 认证问题直接克隆也无法解决，交给前端处理
+            Err(e) if e.starts_with(AUTH_REQUIRED_PREFIX) || e.starts_with("认证失败") => return Err(e),
+            Err(e) => {
+                eprintln!("⚠️  [Backend] Git 缓存不可用，直接克隆: {}", e);
+                let _ = fs::remove_dir_all(target_dir);
+            }
+        }
+    }
+
+    clone_from_remote(url, target_dir, shallow, branch, auth, kind)
+}
+
+/// 从远程克隆仓库：先用原始 URL，失败时尝试 SSH；需要认证时返回结构化错误
+pub fn clone_from_remote(
+    url: &str,
+    target_dir: &str,
+    shallow: bool,
+    branch: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    kind: CloneKind,
+) -> Result<(), String> {
     println!("⏳ [Backend] 开始克隆仓库...");
+    super::progress::phase(super::progress::ProgressPhase::Clone, "克隆仓库");
 
     if let Some(auth_input) = auth {
         let auth_url = if auth_input.method == "ssh" && !url.starts_with("git@") {
@@ -180,7 +409,7 @@ pub fn clone_repo(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitA
             url.to_string()
         };
 
-        let output = run_git_clone(&auth_url, target_dir, shallow, Some(auth_input))?;
+        let output = run_git_clone(&auth_url, target_dir, shallow, branch, Some(auth_input), kind)?;
         if output.status.success() {
             println!("✅ [Backend] 认证克隆成功");
             return Ok(());
@@ -194,7 +423,7 @@ pub fn clone_repo(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitA
     }
 
     // 尝试原始 URL 克隆
-    let output = run_git_clone(url, target_dir, shallow, None)?;
+    let output = run_git_clone(url, target_dir, shallow, branch, None, kind)?;
 
     if output.status.success() {
         println!("✅ [Backend] 克隆成功");
@@ -211,7 +440,7 @@ pub fn clone_repo(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitA
         // 清理可能创建的空目录
         let _ = fs::remove_dir_all(target_dir);
 
-        let ssh_output = run_git_clone(&ssh_url, target_dir, shallow, None)?;
+        let ssh_output = run_git_clone(&ssh_url, target_dir, shallow, branch, None, kind)?;
 
         if ssh_output.status.success() {
             println!("✅ [Backend] SSH 克隆成功");
@@ -239,8 +468,77 @@ pub fn clone_repo(url: &str, target_dir: &str, shallow: bool, auth: Option<&GitA
     Err(parse_git_clone_error(&https_error))
 }
 
+/// 读取仓库当前 HEAD 的提交 SHA
+pub fn get_repo_head_commit(repo_dir: &Path) -> Option<String> {
+    let output = run_git(Some(repo_dir), &["rev-parse", "HEAD"], None).ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if commit.is_empty() {
+        None
+    } else {
+        Some(commit)
+    }
+}
+
+/// 用 git ls-remote 列出远程仓库的 ref，返回 (提交 SHA, ref 名称)
+/// 只查询 ref，不下载任何对象；不提示输入凭据
+pub fn list_remote_refs(url: &str, auth: Option<&GitAuthInput>) -> Result<Vec<(String, String)>, String> {
+    let output = run_git(None, &["ls-remote", url], auth)?;
+    if !output.status.success() {
+        return Err(format!(
+            "查询远程仓库失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (sha, name) = line.split_once('\t')?;
+            Some((sha.trim().to_string(), name.trim().to_string()))
+        })
+        .collect())
+}
+
+/// 在 ls-remote 结果中查找 ref 指向的提交；为空时取 HEAD，附注标签取其指向的提交
+fn find_remote_ref_commit(refs: &[(String, String)], git_ref: Option<&str>) -> Option<String> {
+    let candidates: Vec<String> = match git_ref.map(str::trim).filter(|r| !r.is_empty()) {
+        None => vec!["HEAD".to_string()],
+        Some(git_ref) => vec![
+            format!("refs/tags/{}^{{}}", git_ref),
+            format!("refs/heads/{}", git_ref),
+            format!("refs/tags/{}", git_ref),
+            git_ref.to_string(),
+        ],
+    };
+
+    candidates
+        .iter()
+        .find_map(|candidate| refs.iter().find(|(_, name)| name == candidate))
+        .map(|(sha, _)| sha.clone())
+}
+
+/// 将克隆的仓库切换到指定提交
+fn checkout_repo_commit(repo_dir: &Path, commit: &str, auth: Option<&GitAuthInput>) -> Result<(), String> {
+    let output = run_git(Some(repo_dir), &["checkout", "--quiet", commit], auth)?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "切换到提交 {} 失败: {}",
+            commit,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
 /// 解析 git clone 错误，返回用户友好的错误信息
-fn parse_git_clone_error(stderr: &str) -> String {
+pub fn parse_git_clone_error(stderr: &str) -> String {
     let stderr_lower = stderr.to_lowercase();
 
     if stderr_lower.contains("authentication failed")
@@ -386,52 +684,25 @@ fn get_skill_updates_cache_path(workspace_path: Option<&str>) -> Result<PathBuf,
     Ok(cache_dir.join(file_name))
 }
 
-/// 获取工具的相对路径配置
-/// 返回 Vec<(tool_name, path_segments)>
-pub fn get_tool_relative_paths() -> Vec<(&'static str, &'static [&'static str])> {
-    vec![
-        ("claude-code", &[".claude", "skills"] as &[&str]),
-        ("antigravity", &[".gemini", "antigravity", "global_skills"]),
-        ("opencode", &[".config", "opencode", "skills"]),
-        ("codex", &[".codex", "skills"]),
-        ("cursor", &[".cursor", "skills"]),
-        ("droid", &[".factory", "skills"]),
-        ("copilot", &[".agents", "skills"]),
-    ]
-}
-
-/// 获取所有 AI Tools 的全局 skills 目录映射
-fn get_all_tool_skills_dirs() -> Vec<(&'static str, PathBuf)> {
-    let mut dirs = Vec::new();
-
-    if let Some(home) = dirs::home_dir() {
-        for (tool_name, path_parts) in get_tool_relative_paths() {
-            let mut full_path = home.clone();
-            for part in path_parts {
-                full_path = full_path.join(part);
-            }
-            dirs.push((tool_name, full_path));
-        }
-    }
-
-    dirs
+/// 获取所有 AI Tools 的全局 skills 目录映射（来自 AI 工具注册表）
+pub fn get_all_tool_skills_dirs() -> Vec<(String, PathBuf)> {
+    super::ai_tools::load_ai_tools()
+        .into_iter()
+        .filter_map(|tool| tool.global_dir().map(|dir| (tool.id, dir)))
+        .collect()
 }
 
 /// 获取所有 AI Tools 的项目级别 skills 目录映射
 /// workspace_path: 工作区路径
 /// 返回 Vec<(tool_name, directory_path)>
-fn get_all_tool_workspace_skills_dirs(workspace_path: &PathBuf) -> Vec<(&'static str, PathBuf)> {
-    let mut dirs = Vec::new();
-
-    for (tool_name, path_parts) in get_tool_relative_paths() {
-        let mut full_path = workspace_path.clone();
-        for part in path_parts {
-            full_path = full_path.join(part);
-        }
-        dirs.push((tool_name, full_path));
-    }
-
-    dirs
+pub fn get_all_tool_workspace_skills_dirs(workspace_path: &PathBuf) -> Vec<(String, PathBuf)> {
+    super::ai_tools::load_ai_tools()
+        .into_iter()
+        .map(|tool| {
+            let dir = tool.workspace_dir(workspace_path);
+            (tool.id, dir)
+        })
+        .collect()
 }
 
 fn get_tool_skill_dir(tool_name: &str, workspace_path: Option<&str>) -> Result<PathBuf, String> {
@@ -488,73 +759,6 @@ fn get_target_tool_workspace_dirs(tool_names: &Vec<String>, workspace_path: &Pat
     Ok(target_dirs)
 }
 
-/// AI 工具信息
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct AiToolInfo {
-    pub id: String,
-    pub name: String,
-    pub display_name: String,
-    pub icon: String,
-    pub relative_path: String,  // 相对路径，如 ".claude/skills/"
-}
-
-/// 获取所有支持的 AI 工具信息
-#[tauri::command]
-pub fn get_supported_ai_tools() -> Vec<AiToolInfo> {
-    vec![
-        AiToolInfo {
-            id: "claude-code".to_string(),
-            name: "claude-code".to_string(),
-            display_name: "Claude Code".to_string(),
-            icon: "🤖".to_string(),
-            relative_path: ".claude/skills/".to_string(),
-        },
-        AiToolInfo {
-            id: "cursor".to_string(),
-            name: "cursor".to_string(),
-            display_name: "Cursor".to_string(),
-            icon: "⚡".to_string(),
-            relative_path: ".cursor/skills/".to_string(),
-        },
-        AiToolInfo {
-            id: "codex".to_string(),
-            name: "codex".to_string(),
-            display_name: "Codex".to_string(),
-            icon: "🔮".to_string(),
-            relative_path: ".codex/skills/".to_string(),
-        },
-        AiToolInfo {
-            id: "opencode".to_string(),
-            name: "opencode".to_string(),
-            display_name: "OpenCode".to_string(),
-            icon: "🌟".to_string(),
-            relative_path: ".config/opencode/skills/".to_string(),
-        },
-        AiToolInfo {
-            id: "antigravity".to_string(),
-            name: "antigravity".to_string(),
-            display_name: "Antigravity".to_string(),
-            icon: "🚀".to_string(),
-            relative_path: ".gemini/antigravity/global_skills/".to_string(),
-        },
-        AiToolInfo {
-            id: "droid".to_string(),
-            name: "droid".to_string(),
-            display_name: "Droid".to_string(),
-            icon: "🦾".to_string(),
-            relative_path: ".factory/skills/".to_string(),
-        },
-        AiToolInfo {
-            id: "copilot".to_string(),
-            name: "copilot".to_string(),
-            display_name: "Copilot".to_string(),
-            icon: "🐙".to_string(),
-            relative_path: ".agents/skills/".to_string(),
-        },
-    ]
-}
-
 /// 扫描指定目录获取所有 skill 名称
 fn scan_skills_in_dir(dir: &PathBuf) -> Vec<String> {
     let mut skills = Vec::new();
@@ -566,23 +770,103 @@ fn scan_skills_in_dir(dir: &PathBuf) -> Vec<String> {
     if let Ok(entries) = fs::read_dir(dir) {
         for entry in entries.flatten() {
             let path = entry.path();
+            // is_dir 会跟随符号链接，手动链接进来的 skill 也能被发现
             if path.is_dir() {
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                // 跳过隐藏目录和更新时遗留的备份目录
+                if name.starts_with('.') || name.ends_with(".backup") {
+                    continue;
+                }
                 // 检查是否包含 SKILL.md
                 if path.join("SKILL.md").exists() {
-                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                        skills.push(name.to_string());
-                    }
+                    skills.push(name.to_string());
                 }
             }
         }
     }
 
+    skills.sort();
     skills
 }
 
-/// Skill 元数据
+/// Skill 在单个工具中的副本
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
+pub struct SkillToolCopy {
+    pub tool: String,
+    pub path: String,
+    /// 副本当前内容的清单 hash
+    pub manifest_hash: String,
+    /// 是否与其他副本不一致（以 claude-code 副本为准，没有时取多数）
+    pub is_divergent: bool,
+    /// 是否为指向规范副本的链接
+    #[serde(default)]
+    pub linked: bool,
+}
+
+/// 收集 skill 在各工具目录中的副本，并按内容 hash 标记不一致的副本
+fn collect_skill_tool_copies(
+    skill_name: &str,
+    tool_dirs: &[(String, PathBuf)],
+    workspace_path: Option<&str>,
+) -> Vec<SkillToolCopy> {
+    let mut copies: Vec<(String, PathBuf)> = tool_dirs
+        .iter()
+        .map(|(tool_name, tool_dir)| (tool_name.to_string(), tool_dir.join(skill_name)))
+        .filter(|(_, dir)| dir.join("SKILL.md").exists())
+        .collect();
+
+    if !copies.iter().any(|(tool_name, _)| tool_name == "claude-code") {
+        if let Ok(Some(disabled_dir)) = find_existing_disabled_skill_dir(skill_name, workspace_path) {
+            copies.push(("claude-code".to_string(), disabled_dir));
+        }
+    }
+
+    let mut copies: Vec<SkillToolCopy> = copies
+        .into_iter()
+        .filter_map(|(tool, dir)| {
+            let manifest_hash = compute_skill_content_hash(&dir).ok()?;
+            Some(SkillToolCopy {
+                tool,
+                linked: super::skill_links::linked_store_dir(&dir, workspace_path).is_some(),
+                path: dir.to_string_lossy().to_string(),
+                manifest_hash,
+                is_divergent: false,
+            })
+        })
+        .collect();
+    copies.sort_by(|a, b| a.tool.cmp(&b.tool));
+
+    let reference_hash = copies
+        .iter()
+        .find(|copy| copy.tool == "claude-code")
+        .map(|copy| copy.manifest_hash.clone())
+        .or_else(|| {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for copy in &copies {
+                *counts.entry(copy.manifest_hash.as_str()).or_default() += 1;
+            }
+            // 数量相同时取排序靠前的工具，保证结果稳定
+            copies
+                .iter()
+                .max_by_key(|copy| (counts[copy.manifest_hash.as_str()], std::cmp::Reverse(copy.tool.clone())))
+                .map(|copy| copy.manifest_hash.clone())
+        });
+
+    if let Some(reference_hash) = reference_hash {
+        for copy in &mut copies {
+            copy.is_divergent = copy.manifest_hash != reference_hash;
+        }
+    }
+
+    copies
+}
+
+/// Skill 元数据
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct SkillMetadata {
     pub name: String,
     #[serde(default)]
@@ -597,6 +881,56 @@ pub struct SkillMetadata {
     pub repository: Option<String>,
     #[serde(default)]
     pub source_id: Option<String>,
+    /// 安装时仓库所在的提交 SHA
+    #[serde(default)]
+    pub commit_hash: Option<String>,
+    /// 安装时指定的分支、标签或提交，为空表示跟随默认分支
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// 以别名安装时来源仓库中的原名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_name: Option<String>,
+    /// Claude Code 权限配置，允许 Skill 使用的工具
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// 是否可以通过 /skill-name 手动调用
+    #[serde(default)]
+    pub user_invocable: Option<bool>,
+    /// 是否禁止模型自动调用
+    #[serde(default)]
+    pub disable_model_invocation: Option<bool>,
+    #[serde(default)]
+    pub argument_hint: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    /// 依赖的其他 Skill（frontmatter 的 requires 字段）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<super::skill_deps::SkillRequirement>,
+    /// 未识别的 frontmatter 字段，原样保留
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra_frontmatter: serde_json::Map<String, serde_json::Value>,
+    /// 安装或更新时的签名验证结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<super::skill_signature::SkillSignatureInfo>,
+}
+
+impl SkillMetadata {
+    /// 用重新解析的 SKILL.md 覆盖 frontmatter 字段，保留仓库、提交等来源信息
+    pub fn apply_frontmatter(&mut self, parsed: SkillMetadata) {
+        self.description = parsed.description;
+        self.tags = parsed.tags;
+        self.target_tools = parsed.target_tools;
+        self.allowed_tools = parsed.allowed_tools;
+        self.user_invocable = parsed.user_invocable;
+        self.disable_model_invocation = parsed.disable_model_invocation;
+        self.argument_hint = parsed.argument_hint;
+        self.model = parsed.model;
+        self.license = parsed.license;
+        self.requires = parsed.requires;
+        self.extra_frontmatter = parsed.extra_frontmatter;
+    }
 }
 
 /// Skill 注册表条目
@@ -615,6 +949,12 @@ pub struct SkillRegistryEntry {
     pub installed_at: Option<String>,
     #[serde(default)]
     pub metadata: Option<SkillMetadata>,
+    /// 各工具中的副本（列出时实时计算，不写入注册表）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_copies: Vec<SkillToolCopy>,
+    /// 所属的套件（列出时实时计算，不写入注册表）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bundles: Vec<String>,
 }
 
 /// Skill 注册表
@@ -623,6 +963,9 @@ pub struct SkillRegistryEntry {
 pub struct SkillRegistry {
     #[serde(default)]
     pub skills: Vec<SkillRegistryEntry>,
+    /// 已安装的套件
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bundles: Vec<super::skill_bundle::InstalledSkillBundle>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -671,11 +1014,18 @@ fn get_installed_tool_skill_paths(
         .collect()
 }
 
-fn move_or_copy_skill_dir(source_dir: &PathBuf, target_dir: &PathBuf) -> Result<(), String> {
+pub fn move_or_copy_skill_dir(source_dir: &PathBuf, target_dir: &PathBuf) -> Result<(), String> {
     if let Some(parent) = target_dir.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
     }
 
+    // 链接模式下只移动链接本身，规范副本保持不变
+    if fs::symlink_metadata(source_dir).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        let link_target = fs::read_link(source_dir).map_err(|e| format!("读取链接失败: {}", e))?;
+        super::skill_links::link_skill_dir(&link_target, target_dir)?;
+        return fs::remove_dir_all(source_dir).map_err(|e| format!("清理源链接失败: {}", e));
+    }
+
     match fs::rename(source_dir, target_dir) {
         Ok(_) => Ok(()),
         Err(_) => {
@@ -699,7 +1049,7 @@ fn update_registry_installation_state(
     Ok(())
 }
 
-fn get_skill_registry_path() -> Result<PathBuf, String> {
+pub fn get_skill_registry_path() -> Result<PathBuf, String> {
     Ok(get_cobalt_dir()?.join("skills").join("skill-registry.json"))
 }
 
@@ -819,6 +1169,7 @@ fn reconcile_disabled_skill_copies(workspace_path: Option<&str>) -> Result<(), S
                 .map(|(_, path)| path.clone())
                 .unwrap_or_else(|| installed_tool_paths[0].1.clone());
 
+            super::skill_adapters::remove_external_generated_files(&source_dir, workspace_path)?;
             move_or_copy_skill_dir(&source_dir, &target_dir)?;
         }
 
@@ -830,6 +1181,7 @@ fn reconcile_disabled_skill_copies(workspace_path: Option<&str>) -> Result<(), S
             }
 
             if tool_path.exists() {
+                super::skill_adapters::remove_external_generated_files(&tool_path, workspace_path)?;
                 fs::remove_dir_all(&tool_path)
                     .map_err(|e| format!("移除已安装目录失败: {}", e))?;
             }
@@ -868,9 +1220,15 @@ pub fn read_skill_registry() -> Result<SkillRegistry, String> {
 
 /// 写入 skill-registry.json
 #[tauri::command]
-pub fn write_skill_registry(registry: SkillRegistry) -> Result<(), String> {
+pub fn write_skill_registry(mut registry: SkillRegistry) -> Result<(), String> {
     let registry_path = get_skill_registry_path()?;
 
+    // 副本和套件归属是实时计算的，不持久化
+    for entry in &mut registry.skills {
+        entry.tool_copies.clear();
+        entry.bundles.clear();
+    }
+
     if let Some(parent) = registry_path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("创建注册表目录失败: {}", e))?;
     }
@@ -878,7 +1236,7 @@ pub fn write_skill_registry(registry: SkillRegistry) -> Result<(), String> {
     let content = serde_json::to_string_pretty(&registry)
         .map_err(|e| format!("序列化 registry 失败: {}", e))?;
 
-    fs::write(&registry_path, content).map_err(|e| format!("写入 skill-registry.json 失败: {}", e))
+    super::skill_transaction::write_file_atomic(&registry_path, content.as_bytes())
 }
 
 /// Skill 详情
@@ -982,6 +1340,9 @@ pub fn read_skill_md(skill_name: String, workspace_path: Option<String>) -> Resu
                             if meta.source_id.is_none() {
                                 meta.source_id = registry_meta.source_id.clone();
                             }
+                            if meta.signature.is_none() {
+                                meta.signature = registry_meta.signature.clone();
+                            }
                         }
                         None => {
                             metadata = Some(registry_meta.clone());
@@ -1026,8 +1387,8 @@ pub fn read_skill_md(skill_name: String, workspace_path: Option<String>) -> Resu
         // 检查其他工具目录
         for (tool_name, tool_dir) in get_all_tool_workspace_skills_dirs(&ws_path_buf) {
             if tool_dir.join(&skill_name).exists() {
-                if !installed.iter().any(|t| t == tool_name) {
-                    installed.push(tool_name.to_string());
+                if !installed.contains(&tool_name) {
+                    installed.push(tool_name);
                 }
             }
         }
@@ -1148,8 +1509,18 @@ pub fn read_skill_file(skill_name: String, file_path: String, workspace_path: Op
 }
 
 /// 启用/禁用 Skill（通过移动文件实现）
+/// 禁用被其他 Skill 依赖的 Skill 时返回 COBALT_DEPENDENTS_WARNING，force 为 true 时仍然禁用
 #[tauri::command]
-pub fn toggle_skill(skill_name: String, enabled: bool, workspace_path: Option<String>) -> Result<(), String> {
+pub fn toggle_skill(
+    skill_name: String,
+    enabled: bool,
+    workspace_path: Option<String>,
+    force: Option<bool>,
+) -> Result<(), String> {
+    if !enabled {
+        super::skill_deps::check_dependents(&skill_name, workspace_path.as_deref(), force.unwrap_or(false))?;
+    }
+
     let disabled_skills_dir = get_disabled_skills_dir(workspace_path.as_deref())?;
 
     // 确保禁用目录存在
@@ -1157,6 +1528,8 @@ pub fn toggle_skill(skill_name: String, enabled: bool, workspace_path: Option<St
         .map_err(|e| format!("创建禁用 skills 目录失败: {}", e))?;
 
     let target_dir = disabled_skills_dir.join(&skill_name);
+    let label = if enabled { "启用" } else { "禁用" };
+    let mut txn = super::skill_transaction::SkillTransaction::begin(&format!("{} Skill '{}' ", label, skill_name))?;
 
     if enabled {
         let source_dir = find_existing_disabled_skill_dir(&skill_name, workspace_path.as_deref())?
@@ -1185,15 +1558,31 @@ pub fn toggle_skill(skill_name: String, enabled: bool, workspace_path: Option<St
             }
         }
 
-        for (_, tool_dir) in &target_dirs {
+        // 链接模式下为每个工具重新创建链接
+        let store_dir = super::skill_links::linked_store_dir(&source_dir, workspace_path.as_deref());
+        for (tool_name, tool_dir) in &target_dirs {
             let skill_dir = tool_dir.join(&skill_name);
-            copy_dir_recursive(&source_dir, &skill_dir)?;
+            txn.track_new_path(&skill_dir);
+            match store_dir {
+                Some(ref store_dir) => {
+                    if !super::skill_links::link_skill_dir(store_dir, &skill_dir)? {
+                        super::skill_adapters::apply_skill_format(tool_name, &skill_dir, &skill_name)?;
+                    }
+                }
+                None => {
+                    copy_dir_recursive(&source_dir, &skill_dir)?;
+                    super::skill_adapters::apply_skill_format(tool_name, &skill_dir, &skill_name)?;
+                }
+            }
         }
 
-        fs::remove_dir_all(&source_dir)
-            .map_err(|e| format!("清理禁用目录失败: {}", e))?;
+        txn.remove_path(&source_dir)?;
+        if store_dir.is_some() {
+            super::skill_links::sync_linked_skill_formats(&skill_name, workspace_path.as_deref())?;
+        }
 
         if workspace_path.is_none() {
+            txn.snapshot_file(&get_skill_registry_path()?)?;
             update_registry_installation_state(&skill_name, true, installed_tools)?;
         }
     } else {
@@ -1217,32 +1606,41 @@ pub fn toggle_skill(skill_name: String, enabled: bool, workspace_path: Option<St
             .map(|(_, path)| path.clone())
             .unwrap_or_else(|| installed_tool_paths[0].1.clone());
 
-        move_or_copy_skill_dir(&source_dir, &target_dir)?;
+        // 禁用后工具不再加载这个 skill，生成在 skill 目录之外的文件（如 Cursor 规则）一并移除
+        for (_, tool_path) in &installed_tool_paths {
+            for path in super::skill_adapters::external_generated_files(tool_path, workspace_path.as_deref()) {
+                txn.remove_path(&path)?;
+            }
+        }
+
+        txn.move_path(&source_dir, &target_dir)?;
 
         for (_, tool_path) in installed_tool_paths {
             if tool_path == source_dir {
                 continue;
             }
-            if tool_path.exists() {
-                fs::remove_dir_all(&tool_path)
-                    .map_err(|e| format!("移除已安装目录失败: {}", e))?;
-            }
+            txn.remove_path(&tool_path)?;
         }
 
         if workspace_path.is_none() {
+            txn.snapshot_file(&get_skill_registry_path()?)?;
             update_registry_installation_state(&skill_name, false, installed_tools)?;
         }
     }
 
+    txn.commit();
     Ok(())
 }
 
 /// 完全卸载 Skill（从所有 AI 工具中删除）
+/// 卸载被其他 Skill 依赖的 Skill 时返回 COBALT_DEPENDENTS_WARNING，force 为 true 时仍然卸载
 #[tauri::command]
-pub fn uninstall_skill(skill_name: String, workspace_path: Option<String>) -> Result<(), String> {
+pub fn uninstall_skill(skill_name: String, workspace_path: Option<String>, force: Option<bool>) -> Result<(), String> {
     println!("🗑️  [Backend] 完全卸载 Skill: {}", skill_name);
+    super::skill_deps::check_dependents(&skill_name, workspace_path.as_deref(), force.unwrap_or(false))?;
 
     let mut deleted_from_tools = Vec::new();
+    let mut txn = super::skill_transaction::SkillTransaction::begin(&format!("卸载 Skill '{}' ", skill_name))?;
 
     // 根据工作区路径决定使用哪些工具目录
     let all_tool_dirs = if let Some(ref ws_path) = workspace_path {
@@ -1256,15 +1654,13 @@ pub fn uninstall_skill(skill_name: String, workspace_path: Option<String>) -> Re
     for (tool_name, tool_dir) in &all_tool_dirs {
         let skill_path = tool_dir.join(&skill_name);
         if skill_path.exists() {
-            match fs::remove_dir_all(&skill_path) {
-                Ok(_) => {
-                    println!("✅ [Backend] 从 {} 中删除成功", tool_name);
-                    deleted_from_tools.push(tool_name.to_string());
-                }
-                Err(e) => {
-                    eprintln!("⚠️  [Backend] 从 {} 中删除失败: {}", tool_name, e);
-                }
+            for path in super::skill_adapters::external_generated_files(&skill_path, workspace_path.as_deref()) {
+                txn.remove_path(&path)?;
             }
+            txn.remove_path(&skill_path)
+                .map_err(|e| format!("从 {} 中删除失败: {}", tool_name, e))?;
+            println!("✅ [Backend] 从 {} 中删除成功", tool_name);
+            deleted_from_tools.push(tool_name.to_string());
         }
     }
 
@@ -1272,7 +1668,7 @@ pub fn uninstall_skill(skill_name: String, workspace_path: Option<String>) -> Re
     for disabled_dir in get_disabled_skills_dir_candidates(workspace_path.as_deref())? {
         let disabled_path = disabled_dir.join(&skill_name);
         if disabled_path.exists() {
-            fs::remove_dir_all(&disabled_path)
+            txn.remove_path(&disabled_path)
                 .map_err(|e| format!("删除禁用的 skill 目录失败: {}", e))?;
             deleted_from_tools.push("disabled".to_string());
         }
@@ -1282,13 +1678,26 @@ pub fn uninstall_skill(skill_name: String, workspace_path: Option<String>) -> Re
         return Err(format!("Skill '{}' 不存在于任何工具中", skill_name));
     }
 
+    // 所有链接都已删除，链接模式下的规范副本及其生成的文件一并删除
+    let store_skill_dir = super::skill_links::get_skill_store_dir(workspace_path.as_deref())?.join(&skill_name);
+    for path in super::skill_adapters::external_generated_files(&store_skill_dir, workspace_path.as_deref()) {
+        txn.remove_path(&path)?;
+    }
+    txn.remove_path(&store_skill_dir)?;
+
     // 只在全局模式下从注册表中移除
     if workspace_path.is_none() {
+        txn.snapshot_file(&get_skill_registry_path()?)?;
         let mut registry = read_skill_registry()?;
         registry.skills.retain(|s| s.name != skill_name);
         write_skill_registry(registry)?;
     }
 
+    txn.snapshot_file(&super::skill_lock::get_skill_lockfile_path(workspace_path.as_deref())?)?;
+    super::skill_lock::remove_skill_lock_entry(workspace_path.as_deref(), &skill_name)?;
+    txn.remove_path(&super::skill_history::get_skill_history_dir(&skill_name, workspace_path.as_deref())?)?;
+
+    txn.commit();
     println!("🎉 [Backend] Skill '{}' 已从 {} 个位置删除", skill_name, deleted_from_tools.len());
     Ok(())
 }
@@ -1321,7 +1730,12 @@ pub fn remove_skill_from_tools(
             let skill_path = tool_dir.join(&skill_name);
 
             if skill_path.exists() {
-                match fs::remove_dir_all(&skill_path) {
+                let removed = super::skill_adapters::remove_external_generated_files(
+                    &skill_path,
+                    workspace_path.as_deref(),
+                )
+                .and_then(|_| fs::remove_dir_all(&skill_path).map_err(|e| e.to_string()));
+                match removed {
                     Ok(_) => {
                         println!("✅ [Backend] 成功从 {} 中移除 {}", tool_name, skill_name);
                         removed_tools.push(tool_name.clone());
@@ -1341,6 +1755,22 @@ pub fn remove_skill_from_tools(
         return Err(format!("Skill '{}' 在指定的工具中都不存在", skill_name));
     }
 
+    // 所有链接都移除后清理规范副本，否则重新生成剩余工具需要的格式文件
+    super::skill_links::prune_skill_store(&skill_name, workspace_path.as_deref())?;
+
+    if let Some(entry) = super::skill_lock::load_skill_lockfile(workspace_path.as_deref())?
+        .skills
+        .into_iter()
+        .find(|s| s.name == skill_name)
+    {
+        let remaining_tools = entry
+            .target_tools
+            .into_iter()
+            .filter(|tool| !removed_tools.contains(tool))
+            .collect();
+        super::skill_lock::set_skill_lock_target_tools(workspace_path.as_deref(), &skill_name, remaining_tools)?;
+    }
+
     // 只在全局模式下更新注册表
     if workspace_path.is_none() {
         let mut registry = read_skill_registry()
@@ -1467,6 +1897,8 @@ pub fn list_installed_skills(workspace_path: Option<String>) -> Result<Vec<Skill
                                 installed_by,
                                 installed_at: None,
                                 metadata,
+                                tool_copies: Vec::new(),
+                                bundles: Vec::new(),
                             });
                         }
                     }
@@ -1530,6 +1962,8 @@ pub fn list_installed_skills(workspace_path: Option<String>) -> Result<Vec<Skill
                                 installed_by,
                                 installed_at: None,
                                 metadata,
+                                tool_copies: Vec::new(),
+                                bundles: Vec::new(),
                             });
                         }
                     }
@@ -1550,7 +1984,16 @@ pub fn list_installed_skills(workspace_path: Option<String>) -> Result<Vec<Skill
             continue;
         }
 
-        // 从其他工具目录读取 metadata
+        // 通过 Cobalt 安装到其他工具的 skill 在注册表中有记录
+        if let Some(entry) = registry.skills.iter().find(|s| s.name == *skill_name) {
+            let mut skill_entry = entry.clone();
+            skill_entry.enabled = true;
+            skill_entry.installed_by = tools.clone();
+            skills.push(skill_entry);
+            continue;
+        }
+
+        // 手动安装的 skill，从其他工具目录读取 metadata
         let mut metadata: Option<SkillMetadata> = None;
         for (tool_name, tool_dir) in &tool_dirs {
             if tools.contains(&tool_name.to_string()) {
@@ -1569,9 +2012,16 @@ pub fn list_installed_skills(workspace_path: Option<String>) -> Result<Vec<Skill
             installed_by: tools.clone(),
             installed_at: None,
             metadata,
+            tool_copies: Vec::new(),
+            bundles: Vec::new(),
         });
     }
 
+    for skill in &mut skills {
+        skill.tool_copies = collect_skill_tool_copies(&skill.name, &tool_dirs, workspace_path.as_deref());
+    }
+    super::skill_bundle::apply_bundle_membership(&mut skills, workspace_path.as_deref());
+
     skills.sort_by(|a, b| a.name.cmp(&b.name));
     Ok(skills)
 }
@@ -1616,12 +2066,28 @@ pub struct ScannedSkillInfo {
     pub description: Option<String>,
     pub version: Option<String>,
     pub already_installed: bool,
+    /// 脚本安全扫描结果
+    pub security: super::skill_security::SkillSecurityReport,
+    /// 签名验证结果
+    pub signature: super::skill_signature::SkillSignatureInfo,
+    /// 依赖的其他 Skill
+    pub requires: Vec<super::skill_deps::SkillRequirement>,
 }
 
+/// operation_id: 前端生成的操作 ID，提供时通过 operation-progress 事件推送克隆和扫描进度
 #[tauri::command]
-pub async fn scan_repo_skills(repo_url: String, git_auth: Option<GitAuthInput>) -> Result<Vec<ScannedSkillInfo>, String> {
+pub async fn scan_repo_skills(
+    repo_url: String,
+    git_ref: Option<String>,
+    git_auth: Option<GitAuthInput>,
+    operation_id: Option<String>,
+) -> Result<Vec<ScannedSkillInfo>, String> {
+    let _progress = super::progress::ProgressScope::begin(operation_id);
     println!("🔍 [Backend] 开始扫描仓库中的 Skills");
     println!("📦 [Backend] 仓库 URL: {}", repo_url);
+    if let Some(ref git_ref) = git_ref {
+        println!("🏷️  [Backend] 指定 ref: {}", git_ref);
+    }
 
     // 从 URL 提取仓库名称
     let repo_name = repo_url
@@ -1638,7 +2104,8 @@ pub async fn scan_repo_skills(repo_url: String, git_auth: Option<GitAuthInput>)
     }
 
     // 克隆仓库（浅克隆，HTTPS 失败会自动尝试 SSH）
-    clone_repo(&repo_url, temp_dir.to_str().unwrap(), true, git_auth.as_ref())?;
+    clone_repo_at_ref(&repo_url, temp_dir.to_str().unwrap(), true, git_ref.as_deref(), git_auth.as_ref())?;
+    super::progress::phase(super::progress::ProgressPhase::Scan, "扫描 skills");
 
     // 检查是否有 skills 子目录
     let skills_subdir = temp_dir.join("skills");
@@ -1698,11 +2165,15 @@ fn scan_skills_in_directory(source_dir: &PathBuf, fallback_name: Option<&str>) -
             || disabled_skills_dir.join(&skill_name).exists()
             || get_legacy_disabled_skills_dir(None)?.join(&skill_name).exists();
 
+        let security = super::skill_security::scan_skill_dir(source_dir, &skill_name);
         skills.push(ScannedSkillInfo {
             name: skill_name,
             description: metadata.as_ref().and_then(|m| m.description.clone()),
             version: metadata.as_ref().and_then(|m| m.version.clone()),
             already_installed,
+            security,
+            signature: super::skill_signature::verify_skill_dir(source_dir),
+            requires: metadata.map(|m| m.requires).unwrap_or_default(),
         });
         return Ok(skills);
     }
@@ -1735,6 +2206,9 @@ fn scan_skills_in_directory(source_dir: &PathBuf, fallback_name: Option<&str>) -
                             description: metadata.as_ref().and_then(|m| m.description.clone()),
                             version: metadata.as_ref().and_then(|m| m.version.clone()),
                             already_installed,
+                            security: super::skill_security::scan_skill_dir(&path, skill_name),
+                            signature: super::skill_signature::verify_skill_dir(&path),
+                            requires: metadata.map(|m| m.requires).unwrap_or_default(),
                         });
                     }
                 }
@@ -1745,39 +2219,21 @@ fn scan_skills_in_directory(source_dir: &PathBuf, fallback_name: Option<&str>) -
     Ok(skills)
 }
 
-/// 从远程仓库安装 Skill（支持选择性安装）
-#[tauri::command]
-pub async fn install_skill_from_repo(
-    repo_url: String,
-    skill_names: Option<Vec<String>>,
-    target_tools: Option<Vec<String>>,
-    workspace_path: Option<String>,
-    git_auth: Option<GitAuthInput>,
-) -> Result<String, String> {
-
-    println!("🔧 [Backend] 开始安装 Skill");
-    println!("📦 [Backend] 仓库 URL: {}", repo_url);
-    if let Some(ref names) = skill_names {
-        println!("📝 [Backend] 指定安装: {:?}", names);
-    }
-    if let Some(ref tools) = target_tools {
-        println!("🎯 [Backend] 目标工具: {:?}", tools);
-    }
-    if let Some(ref ws) = workspace_path {
-        println!("📁 [Backend] 工作区路径: {:?}", ws);
-    }
-
-    // 获取目标工具的目录列表（根据是否提供工作区路径决定）
-    let target_dirs = if let Some(ref ws_path) = workspace_path {
+/// 获取目标工具的 skills 目录列表（根据是否提供工作区路径决定），并确保目录存在
+fn resolve_install_target_dirs(
+    target_tools: Option<&Vec<String>>,
+    workspace_path: Option<&str>,
+) -> Result<Vec<(String, PathBuf)>, String> {
+    let target_dirs = if let Some(ws_path) = workspace_path {
         let ws_path_buf = PathBuf::from(ws_path);
-        if let Some(tools) = target_tools.as_ref() {
+        if let Some(tools) = target_tools {
             get_target_tool_workspace_dirs(tools, &ws_path_buf)?
         } else {
             // 默认安装到工作区的 claude-code
             vec![("claude-code".to_string(), ws_path_buf.join(".claude").join("skills"))]
         }
     } else {
-        if let Some(tools) = target_tools.as_ref() {
+        if let Some(tools) = target_tools {
             get_target_tool_dirs(tools)?
         } else {
             // 默认只安装到全局 claude-code
@@ -1796,21 +2252,113 @@ pub async fn install_skill_from_repo(
         })?;
     }
 
-    // 从 URL 提取仓库名称
-    let repo_name = repo_url
-        .trim_end_matches('/')
-        .split('/')
-        .last()
-        .ok_or_else(|| {
-            let err = "无效的仓库 URL".to_string();
-            eprintln!("❌ [Backend] {}", err);
-            err
-        })?
-        .trim_end_matches(".git");
-
-    println!("📝 [Backend] 仓库名称: {}", repo_name);
+    Ok(target_dirs)
+}
 
-    // 创建临时目录用于克隆
+/// 从远程仓库安装 Skill（支持选择性安装）
+/// install_mode: copy（默认，每个工具一份副本）| link（一份规范副本，工具目录为符号链接）
+/// security_threshold: low | medium | high | critical，脚本扫描达到该级别时拒绝安装（默认不拦截）
+/// collisions: 按名称指定重名的处理方式（skip / replace / alias），未指定时重名返回 COBALT_SKILL_CONFLICT 错误
+/// operation_id: 前端生成的操作 ID，提供时通过 operation-progress 事件推送克隆和安装进度
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn install_skill_from_repo(
+    repo_url: String,
+    git_ref: Option<String>,
+    skill_names: Option<Vec<String>>,
+    target_tools: Option<Vec<String>>,
+    workspace_path: Option<String>,
+    git_auth: Option<GitAuthInput>,
+    install_mode: Option<String>,
+    security_threshold: Option<String>,
+    collisions: Option<super::skill_collision::SkillCollisionResolutions>,
+    operation_id: Option<String>,
+) -> Result<String, String> {
+    let _progress = super::progress::ProgressScope::begin(operation_id);
+    let install_mode = super::skill_links::SkillInstallMode::parse(install_mode.as_deref())?;
+    let security_threshold = super::skill_security::SecuritySeverity::parse(security_threshold.as_deref())?;
+
+    println!("🔧 [Backend] 开始安装 Skill");
+    println!("📦 [Backend] 仓库 URL: {}", repo_url);
+    if let Some(ref git_ref) = git_ref {
+        println!("🏷️  [Backend] 指定 ref: {}", git_ref);
+    }
+    if let Some(ref names) = skill_names {
+        println!("📝 [Backend] 指定安装: {:?}", names);
+    }
+    if let Some(ref tools) = target_tools {
+        println!("🎯 [Backend] 目标工具: {:?}", tools);
+    }
+    if let Some(ref ws) = workspace_path {
+        println!("📁 [Backend] 工作区路径: {:?}", ws);
+    }
+
+    let target_dirs = resolve_install_target_dirs(target_tools.as_ref(), workspace_path.as_deref())?;
+
+    let (installed_skills, external) = install_skills_from_repo_source(
+        &repo_url,
+        git_ref,
+        skill_names.as_ref(),
+        &target_dirs,
+        workspace_path.as_deref(),
+        git_auth.as_ref(),
+        install_mode,
+        security_threshold,
+        collisions.as_ref(),
+    )?;
+
+    if installed_skills.is_empty() {
+        return Err("未找到可安装的 skills".to_string());
+    }
+
+    // 不在同一仓库中的依赖，从指定仓库或市场源安装
+    let dependencies = super::skill_deps::install_external_dependencies(
+        external,
+        &target_dirs,
+        workspace_path.as_deref(),
+        install_mode,
+        security_threshold,
+    );
+
+    println!("🎉 [Backend] 成功安装 {} 个 skill(s)", installed_skills.len());
+    Ok(format!(
+        "成功安装 {} 个 skill: {}{}",
+        installed_skills.len(),
+        installed_skills.join(", "),
+        dependencies.describe()
+    ))
+}
+
+/// 克隆仓库并安装其中的 skills，同一仓库中的依赖一起安装
+/// 返回已安装的 skill 和需要从其他来源安装的依赖
+#[allow(clippy::too_many_arguments)]
+pub fn install_skills_from_repo_source(
+    repo_url: &str,
+    git_ref: Option<String>,
+    skill_names: Option<&Vec<String>>,
+    target_dirs: &Vec<(String, PathBuf)>,
+    workspace_path: Option<&str>,
+    git_auth: Option<&GitAuthInput>,
+    install_mode: super::skill_links::SkillInstallMode,
+    security_threshold: Option<super::skill_security::SecuritySeverity>,
+    collisions: Option<&super::skill_collision::SkillCollisionResolutions>,
+) -> Result<(Vec<String>, Vec<super::skill_deps::SkillRequirement>), String> {
+
+    // 从 URL 提取仓库名称
+    let repo_name = repo_url
+        .trim_end_matches('/')
+        .split('/')
+        .last()
+        .ok_or_else(|| {
+            let err = "无效的仓库 URL".to_string();
+            eprintln!("❌ [Backend] {}", err);
+            err
+        })?
+        .trim_end_matches(".git");
+
+    println!("📝 [Backend] 仓库名称: {}", repo_name);
+
+    // 创建临时目录用于克隆
     let temp_dir = std::env::temp_dir().join(format!("cobalt-skill-{}", repo_name));
     if temp_dir.exists() {
         fs::remove_dir_all(&temp_dir).map_err(|e| format!("删除临时目录失败: {}", e))?;
@@ -1818,8 +2366,19 @@ pub async fn install_skill_from_repo(
 
     println!("📂 [Backend] 临时目录: {:?}", temp_dir);
 
-    // 克隆仓库（完整克隆，HTTPS 失败会自动尝试 SSH）
-    clone_repo(&repo_url, temp_dir.to_str().unwrap(), false, git_auth.as_ref())?;
+    // 克隆仓库（HTTPS 失败会自动尝试 SSH）；指定了 skill 时只检出这些目录
+    match skill_names {
+        Some(names) => super::skill_sparse::clone_repo_skills(
+            repo_url,
+            &temp_dir,
+            false,
+            git_ref.as_deref(),
+            git_auth,
+            names,
+        )?,
+        None => clone_repo_at_ref(repo_url, temp_dir.to_str().unwrap(), false, git_ref.as_deref(), git_auth)?,
+    }
+    super::progress::phase(super::progress::ProgressPhase::Scan, "扫描 skills");
 
     // 检查是否有 skills 子目录
     let skills_subdir = temp_dir.join("skills");
@@ -1831,36 +2390,427 @@ pub async fn install_skill_from_repo(
         temp_dir.clone()
     };
 
+    let install_source = SkillInstallSource {
+        repo_url: repo_url.to_string(),
+        git_ref: git_ref.map(|r| r.trim().to_string()).filter(|r| !r.is_empty()),
+        commit: get_repo_head_commit(&temp_dir),
+        repo_dir: temp_dir.clone(),
+    };
+
     // 扫描并安装 skills（单技能仓库时用 repo_name 作为备选名称）
-    let installed_skills = install_skills_from_dir(&source_dir, &target_dirs, &repo_url, skill_names.as_ref(), Some(repo_name))?;
+    let plan = super::skill_deps::plan_dependencies(&source_dir, repo_url, skill_names, Some(repo_name), workspace_path);
+    // 同一仓库中的依赖也需要检出
+    if let (Some(selected), Some(planned)) = (skill_names, plan.skill_names.as_ref()) {
+        let dependencies: Vec<String> = planned.iter().filter(|n| !selected.contains(n)).cloned().collect();
+        if !dependencies.is_empty() {
+            if let Err(e) = super::skill_sparse::add_repo_skills(&temp_dir, &dependencies, git_auth) {
+                eprintln!("⚠️  [Backend] 检出依赖目录失败: {}", e);
+            }
+        }
+    }
+    let install_result = install_skills_from_dir(
+        &source_dir,
+        target_dirs,
+        &install_source,
+        plan.skill_names.as_ref(),
+        Some(repo_name),
+        workspace_path,
+        install_mode,
+        security_threshold,
+        collisions,
+    );
 
     // 清理临时目录
     if temp_dir.exists() {
         let _ = fs::remove_dir_all(&temp_dir);
     }
 
+    Ok((install_result?, plan.external))
+}
+
+/// 准备好的本地来源
+struct LocalSkillSource {
+    /// 解压归档用的临时目录，来源是目录时不会创建
+    temp_dir: PathBuf,
+    /// 来源根目录，锁文件中的 subpath 相对该目录
+    root: PathBuf,
+    /// 扫描 skills 的目录（根目录或其中的 skills/ 子目录）
+    source_dir: PathBuf,
+    /// 单技能来源时，SKILL.md 无 name 字段时的备选名称
+    fallback_name: String,
+}
+
+fn prepare_local_skill_source(source: &Path, temp_prefix: &str) -> Result<LocalSkillSource, String> {
+    let temp_dir = repo_temp_dir(temp_prefix, &source.to_string_lossy());
+    let root = super::skill_local::prepare_local_source(source, &temp_dir)?;
+
+    // 直接解压出 skill 文件时用归档名作为备选名称，否则用目录名
+    let fallback_name = if root == temp_dir {
+        super::skill_local::local_source_name(source)
+    } else {
+        super::skill_local::local_source_name(&root)
+    };
+
+    let skills_subdir = root.join("skills");
+    let source_dir = if skills_subdir.is_dir() {
+        println!("✅ [Backend] 发现 skills/ 子目录");
+        skills_subdir
+    } else {
+        root.clone()
+    };
+
+    Ok(LocalSkillSource { temp_dir, root, source_dir, fallback_name })
+}
+
+/// 扫描本地目录或 .zip / .tar.gz / .tgz / .tar 归档中的 Skills
+#[tauri::command]
+pub async fn scan_local_skills(path: String) -> Result<Vec<ScannedSkillInfo>, String> {
+    println!("🔍 [Backend] 开始扫描本地 Skills: {}", path);
+
+    let source = super::skill_local::resolve_local_source(&path)?;
+    let local = prepare_local_skill_source(&source, "cobalt-skill-local-scan")?;
+
+    let scan_result = scan_skills_in_directory(&local.source_dir, Some(&local.fallback_name));
+
+    if local.temp_dir.exists() {
+        let _ = fs::remove_dir_all(&local.temp_dir);
+    }
+    let scanned_skills = scan_result?;
+
+    if scanned_skills.is_empty() {
+        return Err("未找到可安装的 skills".to_string());
+    }
+
+    println!("🎉 [Backend] 扫描到 {} 个 skill(s)", scanned_skills.len());
+    Ok(scanned_skills)
+}
+
+/// 从本地目录或归档安装 Skill（支持选择性安装）
+/// 来源以 file:// 地址记录，检查更新时与该路径当前的内容对比
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn install_skill_from_local(
+    path: String,
+    skill_names: Option<Vec<String>>,
+    target_tools: Option<Vec<String>>,
+    workspace_path: Option<String>,
+    install_mode: Option<String>,
+    security_threshold: Option<String>,
+    collisions: Option<super::skill_collision::SkillCollisionResolutions>,
+) -> Result<String, String> {
+    let install_mode = super::skill_links::SkillInstallMode::parse(install_mode.as_deref())?;
+    let security_threshold = super::skill_security::SecuritySeverity::parse(security_threshold.as_deref())?;
+
+    println!("🔧 [Backend] 开始从本地安装 Skill: {}", path);
+    if let Some(ref names) = skill_names {
+        println!("📝 [Backend] 指定安装: {:?}", names);
+    }
+
+    let source = super::skill_local::resolve_local_source(&path)?;
+    let target_dirs = resolve_install_target_dirs(target_tools.as_ref(), workspace_path.as_deref())?;
+    let local = prepare_local_skill_source(&source, "cobalt-skill-local")?;
+
+    let install_source = SkillInstallSource {
+        repo_url: super::skill_local::local_source_url(&source),
+        repo_dir: local.root.clone(),
+        git_ref: None,
+        commit: None,
+    };
+
+    let plan = super::skill_deps::plan_dependencies(
+        &local.source_dir,
+        &install_source.repo_url,
+        skill_names.as_ref(),
+        Some(&local.fallback_name),
+        workspace_path.as_deref(),
+    );
+    let install_result = install_skills_from_dir(
+        &local.source_dir,
+        &target_dirs,
+        &install_source,
+        plan.skill_names.as_ref(),
+        Some(&local.fallback_name),
+        workspace_path.as_deref(),
+        install_mode,
+        security_threshold,
+        collisions.as_ref(),
+    );
+
+    if local.temp_dir.exists() {
+        let _ = fs::remove_dir_all(&local.temp_dir);
+    }
+    let installed_skills = install_result?;
+
     if installed_skills.is_empty() {
         return Err("未找到可安装的 skills".to_string());
     }
 
+    let dependencies = super::skill_deps::install_external_dependencies(
+        plan.external,
+        &target_dirs,
+        workspace_path.as_deref(),
+        install_mode,
+        security_threshold,
+    );
+
     println!("🎉 [Backend] 成功安装 {} 个 skill(s)", installed_skills.len());
-    Ok(format!("成功安装 {} 个 skill: {}", installed_skills.len(), installed_skills.join(", ")))
+    Ok(format!(
+        "成功安装 {} 个 skill: {}{}",
+        installed_skills.len(),
+        installed_skills.join(", "),
+        dependencies.describe()
+    ))
+}
+
+/// 按 skills.lock 重新安装所有 Skills（切换到记录的提交，保证每台机器内容一致）
+#[tauri::command]
+pub async fn install_skills_from_lockfile(
+    workspace_path: Option<String>,
+    git_auth: Option<GitAuthInput>,
+) -> Result<String, String> {
+    println!("🔒 [Backend] 按 skills.lock 重新安装 Skills");
+
+    let lockfile = super::skill_lock::load_skill_lockfile(workspace_path.as_deref())?;
+    if lockfile.skills.is_empty() {
+        return Err("skills.lock 中没有可安装的 skill".to_string());
+    }
+
+    // 按 仓库 + ref + 提交 分组，同一提交只克隆一次
+    type LockGroupKey = (String, Option<String>, Option<String>);
+    let mut repo_groups: HashMap<LockGroupKey, Vec<super::skill_lock::SkillLockEntry>> = HashMap::new();
+    for entry in lockfile.skills {
+        repo_groups
+            .entry((entry.repository.clone(), entry.git_ref.clone(), entry.commit.clone()))
+            .or_default()
+            .push(entry);
+    }
+
+    let mut installed = Vec::new();
+    let mut failed = Vec::new();
+
+    for ((repo_url, git_ref, commit), entries) in repo_groups {
+        let temp_dir = repo_temp_dir(
+            "cobalt-skill-lock",
+            &format!("{}@{}", repo_url, commit.as_deref().unwrap_or("HEAD")),
+        );
+        if temp_dir.exists() {
+            let _ = fs::remove_dir_all(&temp_dir);
+        }
+
+        // 锁定了提交时直接切换到该提交，否则使用记录的 ref
+        println!("📡 [Backend] 克隆 {} @ {}", repo_url, commit.as_deref().unwrap_or("HEAD"));
+        let checkout_ref = commit.as_deref().or(git_ref.as_deref());
+        let prepared = match prepare_skill_source(
+            &repo_url,
+            &temp_dir,
+            commit.is_none(),
+            checkout_ref,
+            git_auth.as_ref(),
+            None,
+        ) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                for entry in entries {
+                    failed.push(format!("{}（{}）", entry.name, e));
+                }
+                let _ = fs::remove_dir_all(&temp_dir);
+                continue;
+            }
+        };
+
+        let install_source = SkillInstallSource {
+            repo_url: repo_url.clone(),
+            git_ref: git_ref.clone(),
+            commit: commit.clone().or(prepared.commit),
+            repo_dir: prepared.root,
+        };
+
+        for entry in entries {
+            match reinstall_locked_skill(&entry, &install_source, workspace_path.as_deref()) {
+                Ok(_) => installed.push(entry.name),
+                Err(e) => {
+                    eprintln!("⚠️  [Backend] 重新安装 {} 失败: {}", entry.name, e);
+                    failed.push(format!("{}（{}）", entry.name, e));
+                }
+            }
+        }
+
+        if temp_dir.exists() {
+            let _ = fs::remove_dir_all(&temp_dir);
+        }
+    }
+
+    clear_skill_update_cache(workspace_path.as_deref())?;
+
+    if installed.is_empty() {
+        return Err(format!("按锁文件安装失败: {}", failed.join(", ")));
+    }
+
+    installed.sort();
+    let mut message = format!("已按锁文件安装 {} 个 skill: {}", installed.len(), installed.join(", "));
+    if !failed.is_empty() {
+        message.push_str(&format!("；{} 个失败: {}", failed.len(), failed.join(", ")));
+    }
+    println!("🎉 [Backend] {}", message);
+    Ok(message)
+}
+
+/// 根据锁文件条目重新安装单个 skill，覆盖已有副本
+fn reinstall_locked_skill(
+    entry: &super::skill_lock::SkillLockEntry,
+    source: &SkillInstallSource,
+    workspace_path: Option<&str>,
+) -> Result<(), String> {
+    let skill_dir = if entry.subpath.is_empty() {
+        source.repo_dir.clone()
+    } else {
+        source.repo_dir.join(&entry.subpath)
+    };
+
+    if !skill_dir.join("SKILL.md").exists() {
+        return Err(format!("仓库中找不到路径 '{}'", entry.subpath));
+    }
+
+    // 以别名安装的 skill 按改写 name 后的内容校验和安装
+    let candidate = match entry.upstream_name {
+        Some(ref upstream_name) => super::skill_collision::SkillInstallCandidate::aliased(
+            skill_dir,
+            upstream_name.clone(),
+            entry.name.clone(),
+        )?,
+        None => super::skill_collision::SkillInstallCandidate::new(skill_dir, entry.name.clone()),
+    };
+    let result = reinstall_locked_candidate(entry, &candidate, source, workspace_path);
+    candidate.cleanup();
+    result
+}
+
+fn reinstall_locked_candidate(
+    entry: &super::skill_lock::SkillLockEntry,
+    candidate: &super::skill_collision::SkillInstallCandidate,
+    source: &SkillInstallSource,
+    workspace_path: Option<&str>,
+) -> Result<(), String> {
+    // 校验内容与锁文件记录一致
+    if !entry.manifest_hash.is_empty() {
+        let manifest = generate_skill_manifest(&candidate.source_path, Some(&source.repo_url))?;
+        if compute_manifest_hash(&manifest) != entry.manifest_hash {
+            return Err("内容 hash 与锁文件记录不一致".to_string());
+        }
+    }
+
+    let tools = if entry.target_tools.is_empty() {
+        vec!["claude-code".to_string()]
+    } else {
+        entry.target_tools.clone()
+    };
+
+    let target_dirs = if let Some(ws_path) = workspace_path {
+        get_target_tool_workspace_dirs(&tools, &PathBuf::from(ws_path))?
+    } else {
+        get_target_tool_dirs(&tools)?
+    };
+
+    // 已有副本是链接时保持链接模式
+    let install_mode = if target_dirs.iter().any(|(_, tool_dir)| {
+        super::skill_links::linked_store_dir(&tool_dir.join(&entry.name), workspace_path).is_some()
+    }) {
+        super::skill_links::SkillInstallMode::Link
+    } else {
+        super::skill_links::SkillInstallMode::Copy
+    };
+
+    // 清理已有副本（包括禁用目录中的），保证重新安装后内容与锁文件完全一致
+    for (tool_name, tool_dir) in &target_dirs {
+        fs::create_dir_all(tool_dir)
+            .map_err(|e| format!("创建 {} skills 目录失败: {}", tool_name, e))?;
+        let existing_dir = tool_dir.join(&entry.name);
+        if existing_dir.exists() {
+            fs::remove_dir_all(&existing_dir)
+                .map_err(|e| format!("清理 {} 中的旧版本失败: {}", tool_name, e))?;
+        }
+    }
+    if let Some(disabled_dir) = find_existing_disabled_skill_dir(&entry.name, workspace_path)? {
+        fs::remove_dir_all(&disabled_dir).map_err(|e| format!("清理禁用目录失败: {}", e))?;
+    }
+
+    install_single_skill(candidate, &target_dirs, source, workspace_path, install_mode)
+}
+
+/// 准备好的 Skill 来源：远程仓库的克隆，或本地目录、解压后的归档
+struct PreparedSkillSource {
+    root: PathBuf,
+    commit: Option<String>,
+}
+
+/// 按记录的来源地址准备 skill 源目录
+/// file:// 来源直接读取本地目录或解压归档到 temp_dir，其余来源克隆到 temp_dir
+/// skill_names: 只需要其中几个 skill 时稀疏检出这些目录，为 None 时完整克隆
+fn prepare_skill_source(
+    repo_url: &str,
+    temp_dir: &Path,
+    shallow: bool,
+    git_ref: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    skill_names: Option<&[String]>,
+) -> Result<PreparedSkillSource, String> {
+    if let Some(local_path) = super::skill_local::local_source_path(repo_url) {
+        println!("📂 [Backend] 读取本地来源: {:?}", local_path);
+        let root = super::skill_local::prepare_local_source(&local_path, temp_dir)?;
+        return Ok(PreparedSkillSource { root, commit: None });
+    }
+
+    match skill_names {
+        Some(names) => super::skill_sparse::clone_repo_skills(repo_url, temp_dir, shallow, git_ref, auth, names)?,
+        None => clone_repo_at_ref(repo_url, temp_dir.to_str().unwrap(), shallow, git_ref, auth)?,
+    }
+    Ok(PreparedSkillSource {
+        root: temp_dir.to_path_buf(),
+        commit: get_repo_head_commit(temp_dir),
+    })
+}
+
+/// Skill 安装来源：仓库地址、本地克隆目录、指定的 ref 及所在提交
+pub struct SkillInstallSource {
+    pub repo_url: String,
+    pub repo_dir: PathBuf,
+    pub git_ref: Option<String>,
+    pub commit: Option<String>,
+}
+
+impl SkillInstallSource {
+    /// 计算 skill 目录相对仓库根目录的路径（统一使用 / 分隔）
+    fn subpath_of(&self, skill_dir: &Path) -> String {
+        skill_dir
+            .strip_prefix(&self.repo_dir)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default()
+    }
 }
 
 /// 从目录中扫描并安装 skills（支持选择性安装和多目标工具）
 /// fallback_name: 单技能仓库时，当 SKILL.md 无 name 字段时用作备选（通常传 repo 名）
+/// security_threshold: 任一选中的 skill 扫描结果达到该级别时，不安装任何 skill
+/// collisions: 与已安装的、来自其他来源的 skill 重名时的处理方式，未指定时返回重名错误
+#[allow(clippy::too_many_arguments)]
 fn install_skills_from_dir(
     source_dir: &PathBuf,
     target_dirs: &Vec<(String, PathBuf)>,
-    repo_url: &str,
+    source: &SkillInstallSource,
     selected_skills: Option<&Vec<String>>,
     fallback_name: Option<&str>,
+    workspace_path: Option<&str>,
+    install_mode: super::skill_links::SkillInstallMode,
+    security_threshold: Option<super::skill_security::SecuritySeverity>,
+    collisions: Option<&super::skill_collision::SkillCollisionResolutions>,
 ) -> Result<Vec<String>, String> {
     let mut installed = Vec::new();
+    let mut candidates: Vec<(PathBuf, String)> = Vec::new();
 
     // 检查是否是单个 skill（包含 SKILL.md）
     let skill_md = source_dir.join("SKILL.md");
-    if skill_md.exists() {
+    let single_skill = skill_md.exists();
+    if single_skill {
         println!("📖 [Backend] 发现 SKILL.md，作为单个 skill 安装");
 
         // 名称优先级: frontmatter name > fallback_name (repo 名) > 目录名
@@ -1882,58 +2832,143 @@ fn install_skills_from_dir(
             }
         }
 
-        install_single_skill(source_dir, target_dirs, &skill_name, repo_url)?;
-        installed.push(skill_name);
-        return Ok(installed);
-    }
-
-    // 否则扫描子目录
-    println!("🔍 [Backend] 扫描子目录中的 skills...");
-    let entries = fs::read_dir(source_dir)
-        .map_err(|e| format!("读取目录失败: {}", e))?;
+        candidates.push((source_dir.clone(), skill_name));
+    } else {
+        // 否则扫描子目录
+        println!("🔍 [Backend] 扫描子目录中的 skills...");
+        let entries = fs::read_dir(source_dir)
+            .map_err(|e| format!("读取目录失败: {}", e))?;
 
-    for entry in entries {
-        if let Ok(entry) = entry {
+        for entry in entries.flatten() {
             let path = entry.path();
-            if path.is_dir() {
-                let skill_md = path.join("SKILL.md");
-                if skill_md.exists() {
-                    if let Some(skill_name) = path.file_name().and_then(|n| n.to_str()) {
-                        // 如果指定了要安装的 skills，检查是否包含当前 skill
-                        if let Some(selected) = selected_skills {
-                            if !selected.contains(&skill_name.to_string()) {
-                                println!("⏭️  [Backend] 跳过未选中的 skill: {}", skill_name);
-                                continue;
-                            }
-                        }
-
-                        println!("📦 [Backend] 发现 skill: {}", skill_name);
-                        match install_single_skill(&path, target_dirs, skill_name, repo_url) {
-                            Ok(_) => {
-                                installed.push(skill_name.to_string());
-                            }
-                            Err(e) => {
-                                eprintln!("⚠️  [Backend] 安装 {} 失败: {}", skill_name, e);
-                            }
-                        }
+            if !path.is_dir() || !path.join("SKILL.md").exists() {
+                continue;
+            }
+            if let Some(skill_name) = path.file_name().and_then(|n| n.to_str()) {
+                // 如果指定了要安装的 skills，检查是否包含当前 skill
+                if let Some(selected) = selected_skills {
+                    if !selected.contains(&skill_name.to_string()) {
+                        println!("⏭️  [Backend] 跳过未选中的 skill: {}", skill_name);
+                        continue;
                     }
                 }
+
+                println!("📦 [Backend] 发现 skill: {}", skill_name);
+                let skill_name = skill_name.to_string();
+                candidates.push((path, skill_name));
             }
         }
     }
 
+    // 与已安装的其他来源 skill 重名时，按用户选择跳过、替换或以别名安装
+    let candidates = super::skill_collision::resolve_collisions(
+        candidates,
+        &source.repo_url,
+        collisions,
+        workspace_path,
+    )?;
+
+    // 安装前扫描脚本，达到阈值时整批拒绝，不留下部分安装的结果
+    if let Some(threshold) = security_threshold {
+        let blocked: Vec<super::skill_security::SkillSecurityReport> = candidates
+            .iter()
+            .map(|c| super::skill_security::scan_skill_dir(&c.origin_path, &c.name))
+            .filter(|report| report.exceeds(threshold))
+            .collect();
+
+        if !blocked.is_empty() {
+            candidates.iter().for_each(super::skill_collision::SkillInstallCandidate::cleanup);
+            let names: Vec<&str> = blocked.iter().map(|r| r.skill_name.as_str()).collect();
+            eprintln!(
+                "🛡️  [Backend] 安全扫描发现 {} 级及以上风险，已拒绝安装: {}",
+                threshold.as_str(),
+                names.join(", ")
+            );
+            let payload = serde_json::to_string(&blocked)
+                .map_err(|e| format!("序列化安全扫描结果失败: {}", e))?;
+            return Err(format!("{}{}", SECURITY_BLOCKED_PREFIX, payload));
+        }
+    }
+
+    let mut failures = Vec::new();
+    for (index, candidate) in candidates.iter().enumerate() {
+        super::progress::skill(&candidate.name, index, candidates.len());
+        let result = install_single_skill(candidate, target_dirs, source, workspace_path, install_mode);
+        candidate.cleanup();
+        match result {
+            Ok(_) => installed.push(candidate.name.clone()),
+            // 单个 skill 仓库直接返回错误，多个 skill 时跳过失败的继续安装
+            Err(e) if single_skill => return Err(e),
+            Err(e) => {
+                eprintln!("⚠️  [Backend] 安装 {} 失败: {}", candidate.name, e);
+                failures.push(e);
+            }
+        }
+    }
+
+    // 全部失败时返回具体原因（如签名验证失败），而不是“未找到可安装的 skills”
+    if installed.is_empty() && !failures.is_empty() {
+        return Err(failures.join("\n"));
+    }
+
     Ok(installed)
 }
 
 /// 安装单个 skill 到多个目标工具
 fn install_single_skill(
-    source_path: &PathBuf,
+    candidate: &super::skill_collision::SkillInstallCandidate,
     target_dirs: &Vec<(String, PathBuf)>,
-    skill_name: &str,
-    repo_url: &str,
+    source: &SkillInstallSource,
+    workspace_path: Option<&str>,
+    install_mode: super::skill_links::SkillInstallMode,
 ) -> Result<(), String> {
+    let repo_url = source.repo_url.as_str();
+    let source_path = &candidate.source_path;
+    let skill_name = candidate.name.as_str();
+    let upstream_name = candidate.upstream_name.clone();
     let mut installed_tools = Vec::new();
 
+    // 签名无效或不满足签名策略时不安装（签名按来源中的原名称和原内容验证）
+    let signature = super::skill_signature::check_skill_signature(
+        &candidate.origin_path,
+        repo_url,
+        upstream_name.as_deref().unwrap_or(skill_name),
+    )?;
+
+    // 任一步失败都会撤销已复制的目录和已写入的注册表、锁文件
+    let mut txn = super::skill_transaction::SkillTransaction::begin(&format!("安装 Skill '{}' ", skill_name))?;
+
+    // 替换来自其他来源的同名 skill：先移除所有副本、store 中的内容和锁文件条目
+    if candidate.replace {
+        if let Ok(existing_dirs) = resolve_local_skill_tool_dirs(skill_name, workspace_path) {
+            for (_, existing_dir) in &existing_dirs {
+                txn.remove_path(existing_dir)?;
+            }
+        }
+        txn.remove_path(&super::skill_links::get_skill_store_dir(workspace_path)?.join(skill_name))?;
+        txn.snapshot_file(&super::skill_lock::get_skill_lockfile_path(workspace_path)?)?;
+        super::skill_lock::remove_skill_lock_entry(workspace_path, skill_name)?;
+    }
+
+    // 链接模式：先把新版本放入 store，所有链接共用这一份
+    // store 中的副本已被其他工具链接时直接复用，新工具链接到已安装的版本，替换内容只在更新时进行
+    let has_free_target = target_dirs.iter().any(|(_, dir)| !dir.join(skill_name).exists());
+    let mut reused_store = false;
+    let store_dir = if install_mode == super::skill_links::SkillInstallMode::Link && has_free_target {
+        let store_skill_dir = super::skill_links::get_skill_store_dir(workspace_path)?.join(skill_name);
+        if store_skill_dir.exists() && super::skill_links::is_store_referenced(skill_name, workspace_path)? {
+            println!("🔗 [Backend] 复用 {} 已有的规范副本", skill_name);
+            reused_store = true;
+            Some(store_skill_dir)
+        } else {
+            txn.remove_path(&store_skill_dir)?;
+            txn.track_new_path(&store_skill_dir);
+            Some(super::skill_links::store_skill(source_path, skill_name, workspace_path)?)
+        }
+    } else {
+        None
+    };
+
     // 安装到所有目标工具目录
     for (tool_name, tool_skills_dir) in target_dirs {
         let target_dir = tool_skills_dir.join(skill_name);
@@ -1944,22 +2979,29 @@ fn install_single_skill(
             continue;
         }
 
-        // 复制目录
-        match copy_dir_recursive(source_path, &target_dir) {
-            Ok(_) => {
-                println!("✅ [Backend] 成功安装 {} 到 {}", skill_name, tool_name);
-                installed_tools.push(tool_name.clone());
-            }
-            Err(e) => {
-                eprintln!("⚠️  [Backend] 安装 {} 到 {} 失败: {}", skill_name, tool_name, e);
-            }
+        // 复制目录（或创建链接）并转换为目标工具的格式
+        txn.track_new_path(&target_dir);
+        let install_result = match store_dir {
+            Some(ref store_dir) => super::skill_links::link_skill_dir(store_dir, &target_dir),
+            None => copy_dir_recursive(source_path, &target_dir).map(|_| false),
+        };
+        let linked = install_result.map_err(|e| format!("安装到 {} 失败: {}", tool_name, e))?;
+        if !linked {
+            super::skill_adapters::apply_skill_format(tool_name, &target_dir, skill_name)
+                .map_err(|e| format!("转换 {} 的 {} 格式失败: {}", skill_name, tool_name, e))?;
         }
+        println!("✅ [Backend] 成功安装 {} 到 {}", skill_name, tool_name);
+        installed_tools.push(tool_name.clone());
     }
 
     if installed_tools.is_empty() {
         return Err(format!("Skill '{}' 在所有目标工具中都已存在", skill_name));
     }
 
+    if store_dir.is_some() {
+        super::skill_links::sync_linked_skill_formats(skill_name, workspace_path)?;
+    }
+
     // 只在第一个成功安装的目录中读取 metadata
     let first_tool_dir = target_dirs
         .iter()
@@ -1983,21 +3025,30 @@ fn install_single_skill(
     // 更新 metadata
     if let Some(ref mut meta) = metadata {
         meta.repository = Some(repo_url.to_string());
+        meta.commit_hash = source.commit.clone();
+        meta.git_ref = source.git_ref.clone();
+        meta.upstream_name = upstream_name.clone();
+        meta.signature = Some(signature);
     } else {
         metadata = Some(SkillMetadata {
             name: skill_name.to_string(),
             version: None,
             description: None,
-            tags: Vec::new(),
-            target_tools: Vec::new(),
             repository: Some(repo_url.to_string()),
-            source_id: None,
+            commit_hash: source.commit.clone(),
+            git_ref: source.git_ref.clone(),
+            upstream_name: upstream_name.clone(),
+            signature: Some(signature),
+            ..Default::default()
         });
     }
 
     // 添加到注册表
     let mut registry = read_skill_registry()
         .map_err(|e| format!("读取注册表失败: {}", e))?;
+    if candidate.replace {
+        registry.skills.retain(|s| s.name != skill_name);
+    }
 
     let now = chrono::Utc::now().to_rfc3339();
 
@@ -2010,7 +3061,8 @@ fn install_single_skill(
             }
         }
         existing.installed_at = Some(now);
-        if metadata.is_some() {
+        // 复用规范副本时内容没有变化，保留原有的来源信息
+        if metadata.is_some() && !reused_store {
             existing.metadata = metadata;
         }
     } else {
@@ -2023,27 +3075,70 @@ fn install_single_skill(
             installed_by: installed_tools.clone(),
             installed_at: Some(now),
             metadata,
+            tool_copies: Vec::new(),
+            bundles: Vec::new(),
         });
     }
 
+    txn.snapshot_file(&get_skill_registry_path()?)?;
     write_skill_registry(registry)
         .map_err(|e| format!("写入注册表失败: {}", e))?;
 
+    // 记录到锁文件；复用规范副本时只添加目标工具，清单沿用 store 中已有的
+    txn.snapshot_file(&super::skill_lock::get_skill_lockfile_path(workspace_path)?)?;
+    if reused_store {
+        let lockfile = super::skill_lock::load_skill_lockfile(workspace_path)?;
+        if let Some(existing) = lockfile.skills.into_iter().find(|s| s.name == skill_name) {
+            let mut target_tools = existing.target_tools;
+            for tool_name in installed_tools {
+                if !target_tools.contains(&tool_name) {
+                    target_tools.push(tool_name);
+                }
+            }
+            super::skill_lock::set_skill_lock_target_tools(workspace_path, skill_name, target_tools)?;
+            txn.commit();
+            println!("✅ [Backend] Skill '{}' 安装成功", skill_name);
+            return Ok(());
+        }
+    }
+
     // 为每个安装的工具生成清单文件
+    let mut manifest_hash = None;
     for (tool_name, tool_skills_dir) in target_dirs {
         if installed_tools.contains(tool_name) {
             let target_dir = tool_skills_dir.join(skill_name);
-            let manifest = generate_skill_manifest(&target_dir, Some(repo_url))?;
+            let mut manifest = generate_skill_manifest(&target_dir, Some(repo_url))?;
+            manifest.commit = source.commit.clone();
+            manifest.git_ref = source.git_ref.clone();
+            manifest.upstream_name = upstream_name.clone();
             write_skill_manifest(&target_dir, &manifest)?;
+            if manifest_hash.is_none() {
+                manifest_hash = Some(compute_manifest_hash(&manifest));
+            }
         }
     }
 
+    super::skill_lock::upsert_skill_lock_entry(
+        workspace_path,
+        super::skill_lock::SkillLockEntry {
+            name: skill_name.to_string(),
+            repository: repo_url.to_string(),
+            git_ref: source.git_ref.clone(),
+            commit: source.commit.clone(),
+            subpath: source.subpath_of(&candidate.origin_path),
+            manifest_hash: manifest_hash.unwrap_or_default(),
+            target_tools: installed_tools.clone(),
+            upstream_name,
+        },
+    )?;
+
+    txn.commit();
     println!("✅ [Backend] Skill '{}' 安装成功", skill_name);
     Ok(())
 }
 
 /// 递归复制目录
-fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> Result<(), String> {
+pub fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> Result<(), String> {
     fs::create_dir_all(dst).map_err(|e| format!("创建目录失败: {}", e))?;
 
     let entries = fs::read_dir(src).map_err(|e| format!("读取源目录失败: {}", e))?;
@@ -2083,39 +3178,30 @@ pub fn parse_skill_frontmatter(content: &str, default_name: &str) -> Option<Skil
     // 解析 YAML
     let yaml: serde_yaml::Value = serde_yaml::from_str(frontmatter).ok()?;
 
-    let name = yaml.get("name")
-        .and_then(|v| v.as_str())
-        .unwrap_or(default_name)
-        .to_string();
-
-    let description = yaml.get("description")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let version = yaml.get("version")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let tags = yaml.get("tags")
-        .and_then(|v| v.as_sequence())
-        .map(|seq| {
-            seq.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect()
-        })
-        .unwrap_or_default();
+    let name = yaml_scalar_string(yaml.get("name")).unwrap_or_else(|| default_name.to_string());
+    let description = yaml_scalar_string(yaml.get("description"));
+    let version = yaml_scalar_string(yaml.get("version"));
+    let tags = yaml_string_list(yaml.get("tags"));
 
     // 解析 ai-tools 字段（指定该 Skill 适用于哪些 AI 工具，如 claude-code、cursor 等）
     // 注意：allowed-tools 是 Claude Code 的权限配置字段，不是 AI 工具类型
-    let target_tools = yaml.get("ai-tools")
-        .and_then(|v| v.as_str())
-        .map(|s| {
-            s.split(',')
-                .map(|t| t.trim().to_string())
-                .filter(|t| !t.is_empty())
-                .collect()
-        })
-        .unwrap_or_default();
+    let target_tools = yaml_string_list(yaml.get("ai-tools"));
+    let allowed_tools = yaml_string_list(yaml.get("allowed-tools"));
+    let requires = super::skill_deps::parse_requirements(yaml.get("requires"));
+
+    // 其余字段原样保留，供界面展示
+    let mut extra_frontmatter = serde_json::Map::new();
+    for (key, value) in yaml.as_mapping().into_iter().flatten() {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        if PARSED_FRONTMATTER_FIELDS.contains(&key) {
+            continue;
+        }
+        if let Ok(value) = serde_json::to_value(value) {
+            extra_frontmatter.insert(key.to_string(), value);
+        }
+    }
 
     Some(SkillMetadata {
         name,
@@ -2123,11 +3209,58 @@ pub fn parse_skill_frontmatter(content: &str, default_name: &str) -> Option<Skil
         description,
         tags,
         target_tools,
-        repository: None,
-        source_id: None,
+        allowed_tools,
+        user_invocable: yaml.get("user-invocable").and_then(|v| v.as_bool()),
+        disable_model_invocation: yaml.get("disable-model-invocation").and_then(|v| v.as_bool()),
+        argument_hint: yaml_scalar_string(yaml.get("argument-hint")),
+        model: yaml_scalar_string(yaml.get("model")),
+        license: yaml_scalar_string(yaml.get("license")),
+        requires,
+        extra_frontmatter,
+        ..Default::default()
     })
 }
 
+/// 已解析到 SkillMetadata 固定字段中的 frontmatter 键
+const PARSED_FRONTMATTER_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "version",
+    "tags",
+    "ai-tools",
+    "allowed-tools",
+    "user-invocable",
+    "disable-model-invocation",
+    "argument-hint",
+    "model",
+    "license",
+    "requires",
+];
+
+/// 读取字符串字段，数字（如 version: 1.0）也按字符串处理
+fn yaml_scalar_string(value: Option<&serde_yaml::Value>) -> Option<String> {
+    match value? {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// 读取列表字段，支持逗号分隔字符串或 YAML 列表
+fn yaml_string_list(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    let items: Vec<String> = match value {
+        Some(serde_yaml::Value::String(s)) => s.split(',').map(str::to_string).collect(),
+        Some(serde_yaml::Value::Sequence(seq)) => seq.iter().filter_map(|v| yaml_scalar_string(Some(v))).collect(),
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
 /// 创建 Skill 的参数
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -2143,6 +3276,8 @@ pub struct CreateSkillParams {
 /// 创建新 Skill
 #[tauri::command]
 pub fn create_skill(params: CreateSkillParams) -> Result<String, String> {
+    super::skill_lint::validate_skill_name(&params.name)?;
+
     let skills_dir = get_skills_dir()?;
     let skill_dir = skills_dir.join(&params.name);
 
@@ -2182,11 +3317,11 @@ pub fn create_skill(params: CreateSkillParams) -> Result<String, String> {
             name: params.name.clone(),
             version: Some("0.1.0".to_string()),
             description: params.description,
-            tags: Vec::new(),
-            target_tools: Vec::new(),
             repository: None,
-            source_id: None,
+            ..Default::default()
         }),
+        tool_copies: Vec::new(),
+        bundles: Vec::new(),
     });
 
     write_skill_registry(registry)?;
@@ -2273,7 +3408,7 @@ fn generate_tool_calling_template(name: &str) -> String {
 }
 
 /// 计算文件的 SHA256 hash
-fn calculate_file_hash(path: &PathBuf) -> Result<String, String> {
+pub fn calculate_file_hash(path: &PathBuf) -> Result<String, String> {
     use sha2::{Sha256, Digest};
     use std::io::Read;
 
@@ -2295,8 +3430,30 @@ fn calculate_file_hash(path: &PathBuf) -> Result<String, String> {
     Ok(format!("{:x}", result))
 }
 
+/// 计算清单的整体 hash（按路径排序后对每个文件的 hash 求 SHA256）
+pub fn compute_manifest_hash(manifest: &SkillManifest) -> String {
+    use sha2::{Digest, Sha256};
+
+    // 统一路径分隔符，保证不同平台生成的 hash 一致
+    let mut files: Vec<(String, &str)> = manifest
+        .files
+        .iter()
+        .map(|f| (f.path.replace('\\', "/"), f.hash.as_str()))
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (path, hash) in files {
+        hasher.update(path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
 /// 为 skill 生成清单文件
-fn generate_skill_manifest(skill_dir: &PathBuf, repo_url: Option<&str>) -> Result<SkillManifest, String> {
+pub fn generate_skill_manifest(skill_dir: &PathBuf, repo_url: Option<&str>) -> Result<SkillManifest, String> {
     let mut manifest = SkillManifest::default();
     let skill_md_path = skill_dir.join("SKILL.md");
 
@@ -2327,19 +3484,60 @@ fn generate_skill_manifest(skill_dir: &PathBuf, repo_url: Option<&str>) -> Resul
     manifest.repository = repo_url.map(|s| s.to_string());
     manifest.generated_at = chrono::Utc::now().to_rfc3339();
 
-    // 计算所有文件的 hash
-    let mut files = Vec::new();
-    collect_file_hashes(skill_dir, skill_dir, &mut files)?;
-    manifest.files = files;
+    // 计算所有文件的 hash（格式适配生成的文件不属于 Skill 内容，不参与比较）
+    manifest.files = collect_skill_files(skill_dir, false)?;
 
     Ok(manifest)
 }
 
+/// 收集 skill 的文件 hash，格式适配生成的文件不属于 Skill 内容，不参与比较
+fn collect_skill_files(skill_dir: &PathBuf, use_cache: bool) -> Result<Vec<SkillFileInfo>, String> {
+    let mut files = Vec::new();
+    collect_file_hashes(skill_dir, skill_dir, &mut files, use_cache)?;
+    let generated_files = super::skill_adapters::read_generated_files(skill_dir);
+    files.retain(|f| !generated_files.contains(&f.path.replace('\\', "/")));
+    Ok(files)
+}
+
+/// 计算 skill 当前内容的清单 hash，文件大小和修改时间未变时复用上次的文件 hash
+/// 仅用于列表中的副本一致性比较；签名校验、更新检查等仍逐个文件重新计算
+fn compute_skill_content_hash(skill_dir: &PathBuf) -> Result<String, String> {
+    let manifest = SkillManifest {
+        files: collect_skill_files(skill_dir, true)?,
+        ..Default::default()
+    };
+    Ok(compute_manifest_hash(&manifest))
+}
+
+/// 文件 hash 缓存：路径 -> (大小, 修改时间, hash)
+fn cached_file_hash(path: &PathBuf, metadata: &fs::Metadata) -> Result<String, String> {
+    type FileHashCache = Mutex<HashMap<PathBuf, (u64, Option<SystemTime>, String)>>;
+    static FILE_HASH_CACHE: std::sync::OnceLock<FileHashCache> = std::sync::OnceLock::new();
+
+    let size = metadata.len();
+    let modified = metadata.modified().ok();
+    let cache = FILE_HASH_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some((cached_size, cached_modified, hash)) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(path) {
+        // 无法获取修改时间的平台上不使用缓存
+        if *cached_size == size && cached_modified.is_some() && *cached_modified == modified {
+            return Ok(hash.clone());
+        }
+    }
+
+    let hash = calculate_file_hash(path)?;
+    cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(path.clone(), (size, modified, hash.clone()));
+    Ok(hash)
+}
+
 /// 递归收集文件 hash
 fn collect_file_hashes(
     base_dir: &PathBuf,
     current_dir: &PathBuf,
     files: &mut Vec<SkillFileInfo>,
+    use_cache: bool,
 ) -> Result<(), String> {
     let entries = fs::read_dir(current_dir)
         .map_err(|e| format!("读取目录失败: {}", e))?;
@@ -2356,13 +3554,18 @@ fn collect_file_hashes(
         }
 
         if path.is_dir() {
-            collect_file_hashes(base_dir, &path, files)?;
+            collect_file_hashes(base_dir, &path, files, use_cache)?;
         } else if path.is_file() {
             if let Ok(relative_path) = path.strip_prefix(base_dir) {
                 let relative = relative_path.to_string_lossy().to_string();
-                if let Ok(hash) = calculate_file_hash(&path) {
-                    let metadata = fs::metadata(&path)
-                        .map_err(|e| format!("获取文件元数据失败: {}", e))?;
+                let metadata = fs::metadata(&path)
+                    .map_err(|e| format!("获取文件元数据失败: {}", e))?;
+                let hash = if use_cache {
+                    cached_file_hash(&path, &metadata)
+                } else {
+                    calculate_file_hash(&path)
+                };
+                if let Ok(hash) = hash {
                     files.push(SkillFileInfo {
                         path: relative,
                         hash,
@@ -2377,7 +3580,7 @@ fn collect_file_hashes(
 }
 
 /// 读取 skill 的清单文件
-fn read_skill_manifest(skill_dir: &PathBuf) -> Option<SkillManifest> {
+pub fn read_skill_manifest(skill_dir: &PathBuf) -> Option<SkillManifest> {
     let manifest_path = skill_dir.join(".manifest.json");
     if manifest_path.exists() {
         fs::read_to_string(&manifest_path)
@@ -2388,7 +3591,7 @@ fn read_skill_manifest(skill_dir: &PathBuf) -> Option<SkillManifest> {
     }
 }
 
-fn read_skill_metadata(skill_dir: &PathBuf, default_name: &str) -> Option<SkillMetadata> {
+pub fn read_skill_metadata(skill_dir: &PathBuf, default_name: &str) -> Option<SkillMetadata> {
     let skill_md_path = skill_dir.join("SKILL.md");
     let mut metadata = if skill_md_path.exists() {
         fs::read_to_string(&skill_md_path)
@@ -2424,10 +3627,8 @@ fn read_skill_metadata(skill_dir: &PathBuf, default_name: &str) -> Option<SkillM
                         Some(manifest.version)
                     },
                     description: manifest.description,
-                    tags: Vec::new(),
-                    target_tools: Vec::new(),
                     repository: manifest.repository,
-                    source_id: None,
+                    ..Default::default()
                 });
             }
         }
@@ -2477,13 +3678,22 @@ pub struct SkillFileInfo {
 }
 
 /// Skill 清单文件
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillManifest {
     pub version: String,
     pub name: String,
     pub description: Option<String>,
     pub repository: Option<String>,
+    /// 生成清单时仓库所在的提交 SHA
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// 安装时指定的分支、标签或提交
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// 以别名安装时来源仓库中的原名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_name: Option<String>,
     pub files: Vec<SkillFileInfo>,
     pub generated_at: String,
 }
@@ -2512,6 +3722,9 @@ pub struct SkillUpdateCheckResult {
     pub removed_files: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outdated_tools: Option<Vec<String>>,
+    /// 相对安装时清单有本地修改的文件（更新时会尝试合并）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_modified_files: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
@@ -2535,6 +3748,8 @@ pub struct SkillUpdateSummary {
     pub removed_files: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outdated_tools: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_modified_files: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub checked_at: String,
@@ -2552,12 +3767,23 @@ struct LocalSkillUpdateContext {
     current_version: Option<String>,
     local_tools: Vec<InstalledToolLocalContext>,
     repo_url: Option<String>,
+    git_ref: Option<String>,
+    /// 以别名安装时来源仓库中的原名称
+    upstream_name: Option<String>,
+}
+
+impl LocalSkillUpdateContext {
+    /// 在来源仓库中查找时使用的名称
+    fn remote_name(&self) -> &str {
+        self.upstream_name.as_deref().unwrap_or(&self.skill_name)
+    }
 }
 
 struct InstalledToolLocalContext {
     tool_name: String,
     has_manifest: bool,
     local_manifest: Option<SkillManifest>,
+    local_modified_files: Vec<String>,
 }
 
 const SKILL_UPDATE_CACHE_TTL_HOURS: i64 = 6;
@@ -2596,7 +3822,7 @@ fn write_skill_update_cache(
     fs::write(&cache_path, content).map_err(|e| format!("写入 Skill 更新缓存失败: {}", e))
 }
 
-fn clear_skill_update_cache(workspace_path: Option<&str>) -> Result<(), String> {
+pub fn clear_skill_update_cache(workspace_path: Option<&str>) -> Result<(), String> {
     let cache_path = get_skill_updates_cache_path(workspace_path)?;
     if cache_path.exists() {
         fs::remove_file(&cache_path).map_err(|e| format!("删除 Skill 更新缓存失败: {}", e))?;
@@ -2615,7 +3841,7 @@ fn is_skill_update_cache_fresh(cache: &SkillUpdateCacheFile) -> bool {
         < SKILL_UPDATE_CACHE_TTL_HOURS
 }
 
-fn resolve_local_skill_tool_dirs(
+pub fn resolve_local_skill_tool_dirs(
     skill_name: &str,
     workspace_path: Option<&str>,
 ) -> Result<Vec<(String, PathBuf)>, String> {
@@ -2655,34 +3881,84 @@ fn resolve_local_skill_tool_dirs(
     Ok(tool_paths)
 }
 
-fn resolve_skill_repository(skill_dir: &PathBuf, skill_name: &str, workspace_path: Option<&str>) -> Option<String> {
-    let entry = if workspace_path.is_none() {
+pub fn resolve_skill_repository(skill_dir: &PathBuf, skill_name: &str, workspace_path: Option<&str>) -> Option<String> {
+    let entry = if workspace_path.is_none() {
+        read_skill_registry().ok().and_then(|registry| {
+            registry
+                .skills
+                .iter()
+                .find(|s| s.name == skill_name)
+                .cloned()
+        })
+    } else {
+        None
+    };
+
+    entry
+        .as_ref()
+        .and_then(|e| e.metadata.as_ref())
+        .and_then(|m| m.repository.clone())
+        .or_else(|| read_skill_manifest(skill_dir).and_then(|m| m.repository))
+        .or_else(|| {
+            let skill_md = skill_dir.join("SKILL.md");
+            if skill_md.exists() {
+                fs::read_to_string(&skill_md)
+                    .ok()
+                    .and_then(|content| parse_skill_frontmatter(&content, ""))
+                    .and_then(|m| m.repository)
+            } else {
+                None
+            }
+        })
+}
+
+/// 获取 Skill 安装时指定的 ref：优先注册表，然后清单文件，最后锁文件
+fn resolve_skill_git_ref(skill_dir: &PathBuf, skill_name: &str, workspace_path: Option<&str>) -> Option<String> {
+    let from_registry = if workspace_path.is_none() {
+        read_skill_registry().ok().and_then(|registry| {
+            registry
+                .skills
+                .iter()
+                .find(|s| s.name == skill_name)
+                .and_then(|e| e.metadata.as_ref())
+                .and_then(|m| m.git_ref.clone())
+        })
+    } else {
+        None
+    };
+
+    from_registry
+        .or_else(|| read_skill_manifest(skill_dir).and_then(|m| m.git_ref))
+        .or_else(|| {
+            super::skill_lock::load_skill_lockfile(workspace_path)
+                .ok()
+                .and_then(|lockfile| lockfile.skills.into_iter().find(|s| s.name == skill_name))
+                .and_then(|entry| entry.git_ref)
+        })
+}
+
+/// 获取以别名安装的 Skill 的上游名称：优先注册表，然后清单文件，最后锁文件
+fn resolve_skill_upstream_name(skill_dir: &PathBuf, skill_name: &str, workspace_path: Option<&str>) -> Option<String> {
+    let from_registry = if workspace_path.is_none() {
         read_skill_registry().ok().and_then(|registry| {
             registry
                 .skills
                 .iter()
                 .find(|s| s.name == skill_name)
-                .cloned()
+                .and_then(|e| e.metadata.as_ref())
+                .and_then(|m| m.upstream_name.clone())
         })
     } else {
         None
     };
 
-    entry
-        .as_ref()
-        .and_then(|e| e.metadata.as_ref())
-        .and_then(|m| m.repository.clone())
-        .or_else(|| read_skill_manifest(skill_dir).and_then(|m| m.repository))
+    from_registry
+        .or_else(|| read_skill_manifest(skill_dir).and_then(|m| m.upstream_name))
         .or_else(|| {
-            let skill_md = skill_dir.join("SKILL.md");
-            if skill_md.exists() {
-                fs::read_to_string(&skill_md)
-                    .ok()
-                    .and_then(|content| parse_skill_frontmatter(&content, ""))
-                    .and_then(|m| m.repository)
-            } else {
-                None
-            }
+            super::skill_lock::load_skill_lockfile(workspace_path)
+                .ok()
+                .and_then(|lockfile| lockfile.skills.into_iter().find(|s| s.name == skill_name))
+                .and_then(|entry| entry.upstream_name)
         })
 }
 
@@ -2694,12 +3970,22 @@ fn resolve_local_skill_update_context(
     let local_tools: Vec<InstalledToolLocalContext> = tool_dirs
         .iter()
         .map(|(tool_name, skill_dir)| {
-            let local_manifest = read_skill_manifest(skill_dir)
+            let stored_manifest = read_skill_manifest(skill_dir);
+            let local_modified_files = stored_manifest
+                .as_ref()
+                .and_then(|stored| {
+                    generate_skill_manifest(skill_dir, None)
+                        .ok()
+                        .map(|current| super::skill_merge::detect_local_modifications(stored, &current))
+                })
+                .unwrap_or_default();
+            let local_manifest = stored_manifest
                 .or_else(|| generate_skill_manifest(skill_dir, None).ok());
             InstalledToolLocalContext {
                 tool_name: tool_name.clone(),
                 has_manifest: local_manifest.is_some(),
                 local_manifest,
+                local_modified_files,
             }
         })
         .collect();
@@ -2743,15 +4029,120 @@ fn resolve_local_skill_update_context(
     let repo_url = tool_dirs
         .iter()
         .find_map(|(_, skill_dir)| resolve_skill_repository(skill_dir, skill_name, workspace_path));
+    let git_ref = tool_dirs
+        .iter()
+        .find_map(|(_, skill_dir)| resolve_skill_git_ref(skill_dir, skill_name, workspace_path));
+    let upstream_name = tool_dirs
+        .iter()
+        .find_map(|(_, skill_dir)| resolve_skill_upstream_name(skill_dir, skill_name, workspace_path));
 
     Ok(LocalSkillUpdateContext {
         skill_name: skill_name.to_string(),
         current_version,
         local_tools,
         repo_url,
+        git_ref,
+        upstream_name,
     })
 }
 
+/// 生成远程清单；以别名安装的 skill 先把 name 改为别名，避免仅因 name 不同被判定为有更新
+fn generate_remote_skill_manifest(
+    remote_skill_dir: &PathBuf,
+    repo_url: &str,
+    context: &LocalSkillUpdateContext,
+) -> Option<SkillManifest> {
+    if context.upstream_name.is_none() {
+        return generate_skill_manifest(remote_skill_dir, Some(repo_url)).ok();
+    }
+
+    let aliased_dir = super::skill_collision::stage_aliased_copy(remote_skill_dir, &context.skill_name).ok()?;
+    let manifest = generate_skill_manifest(&aliased_dir, Some(repo_url)).ok();
+    let _ = fs::remove_dir_all(&aliased_dir);
+    manifest
+}
+
+/// 跟踪的 ref 当前指向的提交：固定到提交时就是该提交，否则用 ls-remote 查询
+/// refs_cache 按仓库缓存 ls-remote 结果，同一仓库的多个 ref 只查询一次；本地来源或查询失败时返回 None
+fn resolve_remote_commit(
+    repo_url: &str,
+    git_ref: Option<&str>,
+    auth: Option<&GitAuthInput>,
+    refs_cache: &mut HashMap<String, Option<Vec<(String, String)>>>,
+) -> Option<String> {
+    if let Some(commit) = git_ref.map(str::trim).filter(|r| is_commit_sha(r)) {
+        return Some(commit.to_string());
+    }
+    if super::skill_local::local_source_path(repo_url).is_some() {
+        return None;
+    }
+
+    let refs = refs_cache.entry(repo_url.to_string()).or_insert_with(|| {
+        list_remote_refs(repo_url, auth)
+            .map_err(|e| eprintln!("⚠️  [Backend] ls-remote {} 失败，改为获取仓库对比: {}", repo_url, e))
+            .ok()
+    });
+    find_remote_ref_commit(refs.as_ref()?, git_ref)
+}
+
+/// 所有副本的清单都记录了与远程一致的提交时，无需获取仓库即可判定没有更新
+/// 只比较完整的 SHA：缺失或被截断的提交无法确认与远程一致
+fn is_at_remote_commit(context: &LocalSkillUpdateContext, remote_commit: &str) -> bool {
+    let is_full_sha = |commit: &str| commit.len() == 40 && is_commit_sha(commit);
+    is_full_sha(remote_commit)
+        && !context.local_tools.is_empty()
+        && context.local_tools.iter().all(|tool| {
+            tool.local_manifest
+                .as_ref()
+                .and_then(|manifest| manifest.commit.as_deref())
+                .is_some_and(|commit| is_full_sha(commit) && commit.eq_ignore_ascii_case(remote_commit))
+        })
+}
+
+/// 远程提交未变化时的检查结果，仍然报告本地修改的文件
+fn create_up_to_date_result(context: &LocalSkillUpdateContext) -> SkillUpdateCheckResult {
+    let mut local_modified_files: Vec<String> = context
+        .local_tools
+        .iter()
+        .flat_map(|tool| tool.local_modified_files.iter().cloned())
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    local_modified_files.sort();
+
+    SkillUpdateCheckResult {
+        has_update: false,
+        current_version: context.current_version.clone(),
+        latest_version: context.current_version.clone(),
+        has_repository: true,
+        has_manifest: context.local_tools.iter().any(|tool| tool.has_manifest),
+        changed_files: Some(Vec::new()),
+        new_files: Some(Vec::new()),
+        removed_files: Some(Vec::new()),
+        outdated_tools: Some(Vec::new()),
+        local_modified_files: Some(local_modified_files),
+        error: None,
+    }
+}
+
+fn summarize_update_result(skill_name: String, result: SkillUpdateCheckResult) -> SkillUpdateSummary {
+    SkillUpdateSummary {
+        skill_name,
+        has_update: result.has_update,
+        current_version: result.current_version,
+        latest_version: result.latest_version,
+        has_repository: result.has_repository,
+        has_manifest: result.has_manifest,
+        changed_files: result.changed_files,
+        new_files: result.new_files,
+        removed_files: result.removed_files,
+        outdated_tools: result.outdated_tools,
+        local_modified_files: result.local_modified_files,
+        error: result.error,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
 fn create_update_result_from_context(
     context: &LocalSkillUpdateContext,
     remote_manifest: Option<&SkillManifest>,
@@ -2768,6 +4159,7 @@ fn create_update_result_from_context(
             new_files: None,
             removed_files: None,
             outdated_tools: None,
+            local_modified_files: None,
             error: Some(error),
         };
     }
@@ -2775,8 +4167,10 @@ fn create_update_result_from_context(
     let mut new_files = std::collections::HashSet::new();
     let mut removed_files = std::collections::HashSet::new();
     let mut outdated_tools = Vec::new();
+    let mut local_modified_files = std::collections::HashSet::new();
 
     for tool in &context.local_tools {
+        local_modified_files.extend(tool.local_modified_files.iter().cloned());
         let comparison_result = compare_manifests(tool.local_manifest.as_ref(), remote_manifest);
         if comparison_result.has_changes {
             outdated_tools.push(tool.tool_name.clone());
@@ -2793,6 +4187,8 @@ fn create_update_result_from_context(
     new_files.sort();
     removed_files.sort();
     outdated_tools.sort();
+    let mut local_modified_files: Vec<String> = local_modified_files.into_iter().collect();
+    local_modified_files.sort();
 
     SkillUpdateCheckResult {
         has_update: !outdated_tools.is_empty(),
@@ -2804,10 +4200,16 @@ fn create_update_result_from_context(
         new_files: Some(new_files),
         removed_files: Some(removed_files),
         outdated_tools: Some(outdated_tools),
+        local_modified_files: Some(local_modified_files),
         error: None,
     }
 }
 
+/// 仓库中的 skill 目录名是否对应该 skill（允许 skill-name 和 skill_name 的差异）
+pub fn skill_dir_matches(dir_name: &str, skill_name: &str) -> bool {
+    dir_name.replace('-', "_") == skill_name.replace('-', "_")
+}
+
 fn find_remote_skill_dir(repo_dir: &PathBuf, skill_name: &str) -> Result<PathBuf, String> {
     if repo_dir.join("skills").exists() {
         let skills_subdir = repo_dir.join("skills");
@@ -2822,7 +4224,7 @@ fn find_remote_skill_dir(repo_dir: &PathBuf, skill_name: &str) -> Result<PathBuf
             let path = entry.path();
             if path.is_dir() && path.join("SKILL.md").exists() {
                 let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                if name.replace("-", "_") == skill_name.replace("-", "_") {
+                if skill_dir_matches(name, skill_name) {
                     return Ok(path);
                 }
             }
@@ -2845,7 +4247,11 @@ fn repo_temp_dir(prefix: &str, key: &str) -> PathBuf {
 
 /// 检查 Skill 是否有更新（基于文件 hash 对比）
 #[tauri::command]
-pub async fn check_skill_update(skill_name: String, workspace_path: Option<String>) -> Result<SkillUpdateCheckResult, String> {
+pub async fn check_skill_update(
+    skill_name: String,
+    workspace_path: Option<String>,
+    git_auth: Option<GitAuthInput>,
+) -> Result<SkillUpdateCheckResult, String> {
     println!("🔍 [Backend] 检查 Skill '{}' 的更新", skill_name);
 
     let context = resolve_local_skill_update_context(&skill_name, workspace_path.as_deref())?;
@@ -2860,18 +4266,35 @@ pub async fn check_skill_update(skill_name: String, workspace_path: Option<Strin
         }
     };
 
+    // 远程 ref 仍指向安装时的提交时不必获取仓库
+    if let Some(remote_commit) = resolve_remote_commit(&repo_url, context.git_ref.as_deref(), git_auth.as_ref(), &mut HashMap::new()) {
+        if is_at_remote_commit(&context, &remote_commit) {
+            println!("⚡ [Backend] {} 仍指向提交 {}，无需获取仓库", repo_url, remote_commit);
+            return Ok(create_up_to_date_result(&context));
+        }
+    }
+
     let temp_dir = repo_temp_dir("cobalt-skill-check", &format!("{}:{}", repo_url, skill_name));
     if temp_dir.exists() {
         let _ = fs::remove_dir_all(&temp_dir);
     }
 
-    // 克隆仓库（浅克隆，HTTPS 失败会自动尝试 SSH）
-    println!("📡 [Backend] 克隆远程仓库: {}", repo_url);
-    if let Err(e) = clone_repo(&repo_url, temp_dir.to_str().unwrap(), true, None) {
-        return Ok(create_update_result_from_context(&context, None, Some(e)));
-    }
+    // 克隆仓库（浅克隆，HTTPS 失败会自动尝试 SSH）；本地来源直接读取
+    println!("📡 [Backend] 获取远程仓库: {}", repo_url);
+    let remote_names = [context.remote_name().to_string()];
+    let prepared = match prepare_skill_source(
+        &repo_url,
+        &temp_dir,
+        true,
+        context.git_ref.as_deref(),
+        git_auth.as_ref(),
+        Some(&remote_names),
+    ) {
+        Ok(prepared) => prepared,
+        Err(e) => return Ok(create_update_result_from_context(&context, None, Some(e))),
+    };
 
-    let remote_skill_dir = match find_remote_skill_dir(&temp_dir, &skill_name) {
+    let remote_skill_dir = match find_remote_skill_dir(&prepared.root, context.remote_name()) {
         Ok(path) => path,
         Err(error) => {
             let _ = fs::remove_dir_all(&temp_dir);
@@ -2881,7 +4304,7 @@ pub async fn check_skill_update(skill_name: String, workspace_path: Option<Strin
 
     // 生成远程清单
     println!("📋 [Backend] 生成远程清单，目录: {:?}", remote_skill_dir);
-    let remote_manifest = generate_skill_manifest(&remote_skill_dir, Some(&repo_url)).ok();
+    let remote_manifest = generate_remote_skill_manifest(&remote_skill_dir, &repo_url, &context);
 
     // 清理临时目录
     if temp_dir.exists() {
@@ -2891,10 +4314,16 @@ pub async fn check_skill_update(skill_name: String, workspace_path: Option<Strin
     Ok(create_update_result_from_context(&context, remote_manifest.as_ref(), None))
 }
 
+/// 批量检查更新：同一仓库的 skill 合并为一个任务，最多 concurrency 个仓库并行检查
+/// 每个 skill 最多等待 timeout_secs 秒（同一仓库的 skill 依次计时），超时后结束该仓库的 git 进程
+/// 每检查完一个 skill 通过 skill-update-checked 事件推送结果，全部完成后返回汇总
 #[tauri::command]
 pub async fn check_all_skill_updates(
+    app: tauri::AppHandle,
     workspace_path: Option<String>,
     force: Option<bool>,
+    concurrency: Option<usize>,
+    timeout_secs: Option<u64>,
 ) -> Result<Vec<SkillUpdateSummary>, String> {
     let skills = list_installed_skills(workspace_path.clone())?;
     let skill_names: Vec<String> = skills.iter().map(|skill| skill.name.clone()).collect();
@@ -2918,82 +4347,218 @@ pub async fn check_all_skill_updates(
     }
 
     let mut results = Vec::with_capacity(skills.len());
-    let mut repo_groups: HashMap<String, Vec<LocalSkillUpdateContext>> = HashMap::new();
+    // 按 仓库 → ref 分组，同一仓库只查询和同步一次，同一 ref 只克隆一次
+    let mut repo_groups: HashMap<String, HashMap<Option<String>, Vec<LocalSkillUpdateContext>>> = HashMap::new();
 
     for skill in skills {
         let context = resolve_local_skill_update_context(&skill.name, workspace_path.as_deref())?;
         if let Some(repo_url) = context.repo_url.clone() {
-            repo_groups.entry(repo_url).or_default().push(context);
+            repo_groups
+                .entry(repo_url)
+                .or_default()
+                .entry(context.git_ref.clone())
+                .or_default()
+                .push(context);
         } else {
             let result = create_update_result_from_context(
                 &context,
                 None,
                 Some("该 Skill 没有配置仓库信息。请创建 .manifest.json 文件并添加 repository 字段。".to_string()),
             );
-            results.push(SkillUpdateSummary {
-                skill_name: context.skill_name,
-                has_update: result.has_update,
-                current_version: result.current_version,
-                latest_version: result.latest_version,
-                has_repository: result.has_repository,
-                has_manifest: result.has_manifest,
-                changed_files: result.changed_files,
-                new_files: result.new_files,
-                removed_files: result.removed_files,
-                outdated_tools: result.outdated_tools,
-                error: result.error,
-                checked_at: chrono::Utc::now().to_rfc3339(),
+            let summary = summarize_update_result(context.skill_name, result);
+            if let Err(e) = app.emit(SKILL_UPDATE_CHECKED_EVENT, &summary) {
+                eprintln!("⚠️  [Backend] 推送检查结果失败: {}", e);
+            }
+            results.push(summary);
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(concurrency.unwrap_or(UPDATE_CHECK_CONCURRENCY).max(1)));
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(UPDATE_CHECK_TIMEOUT_SECS).max(1));
+    let (sender, mut receiver) = mpsc::unbounded_channel::<SkillUpdateSummary>();
+    // 每个 skill 恰好上报一次；超时的任务仍持有 sender，不能等待 channel 关闭
+    let pending: usize = repo_groups.values().flat_map(HashMap::values).map(Vec::len).sum();
+
+    for (repo_url, ref_groups) in repo_groups {
+        // 任务超时或异常退出时，为尚未返回结果的 skill 报告错误
+        let failed_results: Vec<(String, SkillUpdateCheckResult)> = ref_groups
+            .values()
+            .flatten()
+            .map(|context| {
+                let result = create_update_result_from_context(context, None, Some(String::new()));
+                (context.skill_name.clone(), result)
+            })
+            .collect();
+        let reporter = UpdateCheckReporter {
+            sender: sender.clone(),
+            reported: Arc::new(Mutex::new(HashSet::new())),
+            progress: Arc::new(Notify::new()),
+        };
+        let semaphore = semaphore.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let Ok(_permit) = semaphore.acquire_owned().await else {
+                return;
+            };
+
+            let cancel = Arc::new(AtomicBool::new(false));
+            let finished = Arc::new(AtomicBool::new(false));
+            let check_cancel = cancel.clone();
+            let check_reporter = reporter.clone();
+            let check_repo_url = repo_url.clone();
+            let check_finished = UpdateCheckFinished {
+                finished: finished.clone(),
+                progress: reporter.progress.clone(),
+            };
+            let check = tauri::async_runtime::spawn_blocking(move || {
+                let _finished = check_finished;
+                with_git_cancellation(check_cancel.clone(), || {
+                    check_repo_updates(&check_repo_url, ref_groups, &check_reporter, &check_cancel)
+                })
             });
+
+            // 每完成一个 skill 重新计时
+            let mut timed_out = false;
+            while !finished.load(Ordering::Acquire) {
+                if tokio::time::timeout(timeout, reporter.progress.notified()).await.is_err() {
+                    timed_out = true;
+                    break;
+                }
+            }
+            let error = if timed_out {
+                format!("检查更新超时（{} 秒）", timeout.as_secs())
+            } else {
+                match check.await {
+                    Ok(()) => return,
+                    Err(e) => format!("检查更新失败: {}", e),
+                }
+            };
+            // 结束仍在运行的 git 进程，剩余的 skill 不再检查
+            cancel.store(true, Ordering::Relaxed);
+            eprintln!("⚠️  [Backend] {}: {}", repo_url, error);
+            for (skill_name, mut result) in failed_results {
+                result.error = Some(error.clone());
+                reporter.report(skill_name, result);
+            }
+        });
+    }
+    drop(sender);
+
+    for _ in 0..pending {
+        let Some(summary) = receiver.recv().await else {
+            break;
+        };
+        if let Err(e) = app.emit(SKILL_UPDATE_CHECKED_EVENT, &summary) {
+            eprintln!("⚠️  [Backend] 推送检查结果失败: {}", e);
         }
+        results.push(summary);
     }
 
-    for (repo_url, contexts) in repo_groups {
-        let temp_dir = repo_temp_dir("cobalt-skill-batch-check", &repo_url);
-        if temp_dir.exists() {
-            let _ = fs::remove_dir_all(&temp_dir);
+    results.sort_by(|a, b| a.skill_name.cmp(&b.skill_name));
+
+    write_skill_update_cache(workspace_path.as_deref(), &results)?;
+
+    Ok(results)
+}
+
+/// 上报批量检查的结果，每个 skill 只上报一次
+#[derive(Clone)]
+struct UpdateCheckReporter {
+    sender: mpsc::UnboundedSender<SkillUpdateSummary>,
+    reported: Arc<Mutex<HashSet<String>>>,
+    /// 每上报一个 skill 通知一次，用于按 skill 计算超时
+    progress: Arc<Notify>,
+}
+
+impl UpdateCheckReporter {
+    fn report(&self, skill_name: String, result: SkillUpdateCheckResult) {
+        let first = self
+            .reported
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(skill_name.clone());
+        if first {
+            let _ = self.sender.send(summarize_update_result(skill_name, result));
+            self.progress.notify_one();
+        }
+    }
+}
+
+/// 批量检查的任务结束（包括 panic）时标记完成并唤醒等待方
+struct UpdateCheckFinished {
+    finished: Arc<AtomicBool>,
+    progress: Arc<Notify>,
+}
+
+impl Drop for UpdateCheckFinished {
+    fn drop(&mut self) {
+        self.finished.store(true, Ordering::Release);
+        self.progress.notify_one();
+    }
+}
+
+/// 检查同一仓库中各 ref 下 skill 的更新
+fn check_repo_updates(
+    repo_url: &str,
+    ref_groups: HashMap<Option<String>, Vec<LocalSkillUpdateContext>>,
+    reporter: &UpdateCheckReporter,
+    cancel: &AtomicBool,
+) {
+    // 同一仓库只 ls-remote 一次
+    let mut remote_refs: HashMap<String, Option<Vec<(String, String)>>> = HashMap::new();
+
+    for (git_ref, contexts) in ref_groups {
+        // 超时后剩余的 skill 由调用方报告
+        if is_git_cancelled(cancel) {
+            return;
+        }
+        // 远程 ref 仍指向安装时提交的 skill 直接判定没有更新，只为其余 skill 获取仓库
+        let remote_commit = resolve_remote_commit(repo_url, git_ref.as_deref(), None, &mut remote_refs);
+        let (unchanged, contexts): (Vec<_>, Vec<_>) = contexts
+            .into_iter()
+            .partition(|context| remote_commit.as_deref().is_some_and(|commit| is_at_remote_commit(context, commit)));
+        if !unchanged.is_empty() {
+            println!(
+                "⚡ [Backend] {} @ {} 未变化，跳过 {} 个 skill",
+                repo_url,
+                git_ref.as_deref().unwrap_or("HEAD"),
+                unchanged.len()
+            );
+        }
+        for context in unchanged {
+            let result = create_up_to_date_result(&context);
+            reporter.report(context.skill_name, result);
+        }
+        if contexts.is_empty() {
+            continue;
         }
 
-        let clone_result = clone_repo(&repo_url, temp_dir.to_str().unwrap(), true, None);
+        // 超时的检查可能仍在清理，每次检查使用独立的临时目录
+        let temp_dir = std::env::temp_dir().join(format!("cobalt-skill-batch-check-{}", uuid::Uuid::new_v4()));
+
+        // 同一仓库只检出需要检查的 skill 目录
+        let remote_names: Vec<String> = contexts.iter().map(|c| c.remote_name().to_string()).collect();
+        let prepared = prepare_skill_source(repo_url, &temp_dir, true, git_ref.as_deref(), None, Some(&remote_names));
 
         for context in contexts {
-            let result = if let Err(error) = &clone_result {
-                create_update_result_from_context(&context, None, Some(error.clone()))
-            } else {
-                match find_remote_skill_dir(&temp_dir, &context.skill_name) {
+            let result = match &prepared {
+                Err(error) => create_update_result_from_context(&context, None, Some(error.clone())),
+                Ok(prepared) => match find_remote_skill_dir(&prepared.root, context.remote_name()) {
                     Ok(remote_skill_dir) => {
-                        let remote_manifest = generate_skill_manifest(&remote_skill_dir, Some(&repo_url)).ok();
+                        let remote_manifest = generate_remote_skill_manifest(&remote_skill_dir, repo_url, &context);
                         create_update_result_from_context(&context, remote_manifest.as_ref(), None)
                     }
                     Err(error) => create_update_result_from_context(&context, None, Some(error)),
-                }
+                },
             };
 
-            results.push(SkillUpdateSummary {
-                skill_name: context.skill_name,
-                has_update: result.has_update,
-                current_version: result.current_version,
-                latest_version: result.latest_version,
-                has_repository: result.has_repository,
-                has_manifest: result.has_manifest,
-                changed_files: result.changed_files,
-                new_files: result.new_files,
-                removed_files: result.removed_files,
-                outdated_tools: result.outdated_tools,
-                error: result.error,
-                checked_at: chrono::Utc::now().to_rfc3339(),
-            });
+            reporter.report(context.skill_name, result);
         }
 
         if temp_dir.exists() {
             let _ = fs::remove_dir_all(&temp_dir);
         }
     }
-
-    results.sort_by(|a, b| a.skill_name.cmp(&b.skill_name));
-
-    write_skill_update_cache(workspace_path.as_deref(), &results)?;
-
-    Ok(results)
 }
 
 /// 清单对比结果
@@ -3049,9 +4614,110 @@ fn compare_manifests(
     result
 }
 
+/// Skill 更新结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillUpdateOutcome {
+    /// 是否已应用更新（存在冲突且未指定处理策略时为 false）
+    pub applied: bool,
+    pub message: String,
+    /// 相对安装时清单有本地修改的文件
+    pub local_modified_files: Vec<String>,
+    /// 自动合并了本地修改的文件
+    pub merged_files: Vec<String>,
+    /// 上游未改动、保留本地修改的文件
+    pub kept_local_files: Vec<String>,
+    pub conflicts: Vec<super::skill_merge::SkillMergeConflict>,
+}
+
+/// 在事务中写入合并后的新版本并同步到其他工具，返回（已同步工具，未知目录的工具）
+fn apply_skill_update(
+    txn: &mut super::skill_transaction::SkillTransaction,
+    skill_name: &str,
+    skill_dir: &PathBuf,
+    staging_dir: &PathBuf,
+    workspace_path: Option<&str>,
+    installed_tools: &[String],
+    new_manifest: &SkillManifest,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    // 保存旧版本到版本历史，便于回滚
+    txn.snapshot_file(&super::skill_history::get_skill_history_file_path(skill_name, workspace_path)?)?;
+    let version = super::skill_history::record_skill_version(skill_name, skill_dir, workspace_path, "update")?;
+    txn.track_new_path(Path::new(&version.backup_path));
+
+    // 链接模式下直接替换规范副本，所有链接自动指向新版本
+    let store_dir = super::skill_links::linked_store_dir(skill_dir, workspace_path);
+    let write_dir = store_dir.clone().unwrap_or_else(|| skill_dir.clone());
+
+    println!("📋 [Backend] 替换为新版本...");
+    txn.remove_path(&write_dir)?;
+    txn.track_new_path(&write_dir);
+    copy_dir_recursive(staging_dir, &write_dir).map_err(|e| format!("复制新版本失败: {}", e))?;
+
+    // 写入新的清单文件
+    write_skill_manifest(&write_dir, new_manifest)?;
+
+    let all_tool_dirs = if let Some(ws_path) = workspace_path {
+        get_all_tool_workspace_skills_dirs(&PathBuf::from(ws_path))
+    } else {
+        get_all_tool_skills_dirs()
+    };
+
+    let mut synced_tools = Vec::new();
+    let mut failed_tools = Vec::new();
+
+    for tool_name in installed_tools {
+        if tool_name == "claude-code" {
+            continue;
+        }
+
+        let Some((_, tool_skills_dir)) = all_tool_dirs.iter().find(|(name, _)| name == tool_name) else {
+            failed_tools.push(format!("{}（未知工具目录）", tool_name));
+            continue;
+        };
+
+        let target_dir = tool_skills_dir.join(skill_name);
+        if store_dir.is_some() && super::skill_links::linked_store_dir(&target_dir, workspace_path) == store_dir {
+            synced_tools.push(tool_name.clone());
+            continue;
+        }
+
+        fs::create_dir_all(tool_skills_dir)
+            .map_err(|e| format!("同步到 {} 失败: 创建目录失败: {}", tool_name, e))?;
+        txn.remove_path(&target_dir)?;
+        txn.track_new_path(&target_dir);
+        copy_dir_recursive(skill_dir, &target_dir)
+            .and_then(|_| super::skill_adapters::apply_skill_format(tool_name, &target_dir, skill_name))
+            .map_err(|e| format!("同步到 {} 失败: {}", tool_name, e))?;
+        synced_tools.push(tool_name.clone());
+    }
+
+    if store_dir.is_some() {
+        super::skill_links::sync_linked_skill_formats(skill_name, workspace_path)?;
+    }
+
+    Ok((synced_tools, failed_tools))
+}
+
 /// 更新 Skill 到最新版本
+/// 本地修改会与上游版本做三方合并；on_conflict 为 abort（默认）、keep-local、take-upstream 或 markers
+/// security_threshold 与安装时相同，上游版本的扫描结果达到阈值时拒绝更新
+/// operation_id: 前端生成的操作 ID，提供时通过 operation-progress 事件推送克隆进度
 #[tauri::command]
-pub async fn update_skill(skill_name: String, workspace_path: Option<String>) -> Result<String, String> {
+pub async fn update_skill(
+    skill_name: String,
+    workspace_path: Option<String>,
+    on_conflict: Option<String>,
+    security_threshold: Option<String>,
+    git_auth: Option<GitAuthInput>,
+    operation_id: Option<String>,
+) -> Result<SkillUpdateOutcome, String> {
+    use super::skill_merge::{apply_skill_merge, plan_skill_merge, SkillConflictStrategy, SkillMergeBase};
+
+    let _progress = super::progress::ProgressScope::begin(operation_id);
+
+    let strategy = SkillConflictStrategy::parse(on_conflict.as_deref())?;
+    let security_threshold = super::skill_security::SecuritySeverity::parse(security_threshold.as_deref())?;
     println!("🔄 [Backend] 开始更新 Skill '{}'", skill_name);
 
     let skills_dir = if let Some(ref ws_path) = workspace_path {
@@ -3116,16 +4782,10 @@ pub async fn update_skill(skill_name: String, workspace_path: Option<String>) ->
             }
         })
         .ok_or_else(|| format!("Skill '{}' 没有仓库信息", skill_name))?;
-
-    // 备份当前 skill
-    let backup_dir = skill_dir.with_extension(".backup");
-    if backup_dir.exists() {
-        let _ = fs::remove_dir_all(&backup_dir);
-    }
-
-    println!("📦 [Backend] 备份当前版本到: {:?}", backup_dir);
-    copy_dir_recursive(&skill_dir, &backup_dir)
-        .map_err(|e| format!("备份失败: {}", e))?;
+    let git_ref = resolve_skill_git_ref(&skill_dir, &skill_name, workspace_path.as_deref());
+    // 以别名安装时按上游名称在仓库中查找
+    let upstream_name = resolve_skill_upstream_name(&skill_dir, &skill_name, workspace_path.as_deref());
+    let remote_name = upstream_name.clone().unwrap_or_else(|| skill_name.clone());
 
     // 创建临时目录用于克隆
     let temp_dir = std::env::temp_dir().join(format!("cobalt-skill-update-{}", skill_name));
@@ -3133,104 +4793,166 @@ pub async fn update_skill(skill_name: String, workspace_path: Option<String>) ->
         let _ = fs::remove_dir_all(&temp_dir);
     }
 
-    // 克隆仓库（完整克隆，HTTPS 失败会自动尝试 SSH）
-    if let Err(e) = clone_repo(&repo_url, temp_dir.to_str().unwrap(), false, None) {
-        let _ = fs::remove_dir_all(&backup_dir);
-        return Err(e);
-    }
-
-    // 检查是否有 skills 子目录
-    let skills_subdir = temp_dir.join("skills");
-    let source_dir = if skills_subdir.exists() && skills_subdir.is_dir() {
-        // 在 skills/ 子目录中查找
-        let skill_subdir = skills_subdir.join(&skill_name);
-        if skill_subdir.exists() {
-            skill_subdir
-        } else {
-            // 查找包含 SKILL.md 的子目录
-            let entries = fs::read_dir(&skills_subdir)
-                .map_err(|e| format!("读取 skills 子目录失败: {}", e))?;
-            let mut found = None;
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() && path.join("SKILL.md").exists() {
-                    // 检查是否匹配（允许 skill-name 和 skill_name 的差异）
-                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    if name.replace("-", "_") == skill_name.replace("-", "_") {
-                        found = Some(path);
-                        break;
-                    }
-                }
-            }
-            // 如果在 skills/ 子目录中找不到，返回错误而不是回退到整个仓库
-            found.ok_or_else(|| format!("在仓库的 skills/ 目录中找不到 skill '{}'", skill_name))?
+    // 克隆仓库（完整克隆，HTTPS 失败会自动尝试 SSH），保持安装时指定的 ref；本地来源直接读取
+    // 只检出这个 skill 的目录；需要完整历史作为三方合并的基线，文件内容按需获取
+    // 读取合并基线时按需下载文件内容，使用同样的凭据
+    let git_auth = git_auth.as_ref();
+    let prepared = prepare_skill_source(
+        &repo_url,
+        &temp_dir,
+        false,
+        git_ref.as_deref(),
+        git_auth,
+        Some(std::slice::from_ref(&remote_name)),
+    )?;
+    let repo_root = prepared.root;
+    let commit = prepared.commit;
+
+    // 在 skills/ 子目录中查找，没有 skills/ 时整个仓库就是一个 skill
+    let source_dir = match find_remote_skill_dir(&repo_root, &remote_name) {
+        Ok(path) => path,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(e);
         }
-    } else if temp_dir.join("SKILL.md").exists() {
-        // 整个仓库就是一个 skill
-        temp_dir.clone()
-    } else {
-        // 既没有 skills 子目录，也不是单个 skill
-        let _ = fs::remove_dir_all(&backup_dir);
-        let _ = fs::remove_dir_all(&temp_dir);
-        return Err(format!("仓库中找不到 skill '{}'", skill_name));
-    };
-
-    // 删除旧版本
-    println!("🗑️  [Backend] 删除旧版本...");
-    fs::remove_dir_all(&skill_dir)
-        .map_err(|e| format!("删除旧版本失败: {}", e))?;
-
-    // 复制新版本
-    println!("📋 [Backend] 复制新版本...");
-    if let Err(e) = copy_dir_recursive(&source_dir, &skill_dir) {
-        // 恢复备份
-        let _ = copy_dir_recursive(&backup_dir, &skill_dir);
-        let _ = fs::remove_dir_all(&backup_dir);
-        return Err(format!("复制新版本失败: {}", e));
-    }
-
-    // 生成新的清单文件
-    let new_manifest = generate_skill_manifest(&skill_dir, Some(&repo_url))?;
-    write_skill_manifest(&skill_dir, &new_manifest)?;
-
-    let all_tool_dirs = if let Some(ref ws_path) = workspace_path {
-        let ws_path_buf = PathBuf::from(ws_path);
-        get_all_tool_workspace_skills_dirs(&ws_path_buf)
-    } else {
-        get_all_tool_skills_dirs()
     };
 
-    let mut synced_tools = Vec::new();
-    let mut failed_tools = Vec::new();
+    let lock_subpath = source_dir
+        .strip_prefix(&repo_root)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let lock_tools = installed_tools.clone();
 
-    for tool_name in installed_tools {
-        if tool_name == "claude-code" {
-            continue;
+    // 验证上游版本的签名（合并本地修改之前）
+    let signature = match super::skill_signature::check_skill_signature(&source_dir, &repo_url, &remote_name) {
+        Ok(signature) => signature,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(e);
         }
+    };
 
-        let Some((_, tool_skills_dir)) = all_tool_dirs.iter().find(|(name, _)| *name == tool_name.as_str()) else {
-            failed_tools.push(format!("{}（未知工具目录）", tool_name));
-            continue;
-        };
-
-        if let Err(e) = fs::create_dir_all(tool_skills_dir) {
-            failed_tools.push(format!("{}（创建目录失败: {}）", tool_name, e));
-            continue;
+    // 合并前扫描上游版本，达到阈值时拒绝更新
+    if let Some(threshold) = security_threshold {
+        let report = super::skill_security::scan_skill_dir(&source_dir, &skill_name);
+        if report.exceeds(threshold) {
+            let _ = fs::remove_dir_all(&temp_dir);
+            eprintln!(
+                "🛡️  [Backend] 安全扫描发现 {} 级及以上风险，已拒绝更新: {}",
+                threshold.as_str(),
+                skill_name
+            );
+            let payload = serde_json::to_string(&[report])
+                .map_err(|e| format!("序列化安全扫描结果失败: {}", e))?;
+            return Err(format!("{}{}", SECURITY_BLOCKED_PREFIX, payload));
         }
+    }
 
-        let target_dir = tool_skills_dir.join(&skill_name);
-        if target_dir.exists() {
-            if let Err(e) = fs::remove_dir_all(&target_dir) {
-                failed_tools.push(format!("{}（清理旧版本失败: {}）", tool_name, e));
-                continue;
+    // 以别名安装的 skill 在改写 name 后的副本上合并，来源可能是本地目录，不能原地修改
+    let source_dir = match upstream_name {
+        Some(_) => match super::skill_collision::stage_aliased_copy(&source_dir, &skill_name) {
+            Ok(aliased_dir) => aliased_dir,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&temp_dir);
+                return Err(e);
             }
+        },
+        None => source_dir,
+    };
+    let remove_temp_dirs = || {
+        let _ = fs::remove_dir_all(&temp_dir);
+        if upstream_name.is_some() {
+            let _ = fs::remove_dir_all(&source_dir);
         }
+    };
 
-        match copy_dir_recursive(&skill_dir, &target_dir) {
-            Ok(_) => synced_tools.push(tool_name),
-            Err(e) => failed_tools.push(format!("{}（同步失败: {}）", tool_name, e)),
+    // 新清单记录上游文件 hash，作为下次识别本地修改的基线
+    let mut new_manifest = match generate_skill_manifest(&source_dir, Some(&repo_url)) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            remove_temp_dirs();
+            return Err(e);
+        }
+    };
+    new_manifest.commit = commit.clone();
+    new_manifest.git_ref = git_ref.clone();
+    new_manifest.upstream_name = upstream_name.clone();
+
+    // 对比安装时清单识别本地修改，并与上游做三方合并
+    let stored_manifest = read_skill_manifest(&skill_dir);
+    let local_manifest = match generate_skill_manifest(&skill_dir, None) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            remove_temp_dirs();
+            return Err(e);
         }
+    };
+    let base_commit = stored_manifest.as_ref().and_then(|m| m.commit.clone());
+    let merge_base = base_commit.as_deref().map(|commit| SkillMergeBase {
+        repo_dir: &repo_root,
+        commit,
+        subpath: &lock_subpath,
+        auth: git_auth,
+    });
+    // 同一 skill 可能被并发更新，合并临时目录不能共用
+    let merge_work_dir =
+        std::env::temp_dir().join(format!("cobalt-skill-merge-{}-{}", skill_name, uuid::Uuid::new_v4()));
+    let plan = plan_skill_merge(
+        &skill_dir,
+        &source_dir,
+        stored_manifest.as_ref(),
+        &local_manifest,
+        &new_manifest,
+        merge_base.as_ref(),
+        &merge_work_dir,
+    );
+    let _ = fs::remove_dir_all(&merge_work_dir);
+
+    if plan.has_conflicts() && strategy == SkillConflictStrategy::Abort {
+        let conflicts = plan.conflicts();
+        remove_temp_dirs();
+        println!("⚠️  [Backend] Skill '{}' 存在 {} 个冲突，未应用更新", skill_name, conflicts.len());
+        return Ok(SkillUpdateOutcome {
+            applied: false,
+            message: format!(
+                "检测到 {} 个文件与本地修改冲突，未应用更新: {}",
+                conflicts.len(),
+                conflicts.iter().map(|c| c.path.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+            local_modified_files: plan.local_modified_files.clone(),
+            merged_files: plan.merged_files(),
+            kept_local_files: plan.kept_local_files(),
+            conflicts,
+        });
+    }
+
+    // 在上游版本的副本上应用合并结果
+    let staging_dir = std::env::temp_dir().join(format!("cobalt-skill-merged-{}", skill_name));
+    if staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
     }
+    if let Err(e) = copy_dir_recursive(&source_dir, &staging_dir)
+        .and_then(|_| apply_skill_merge(&plan, &skill_dir, &staging_dir, strategy))
+    {
+        let _ = fs::remove_dir_all(&staging_dir);
+        remove_temp_dirs();
+        return Err(format!("合并本地修改失败: {}", e));
+    }
+    remove_temp_dirs();
+
+    // 之后的任一步失败都会恢复旧版本、各工具副本、版本历史、注册表和锁文件
+    let mut txn = super::skill_transaction::SkillTransaction::begin(&format!("更新 Skill '{}' ", skill_name))?;
+    let result = apply_skill_update(
+        &mut txn,
+        &skill_name,
+        &skill_dir,
+        &staging_dir,
+        workspace_path.as_deref(),
+        &installed_tools,
+        &new_manifest,
+    );
+    let _ = fs::remove_dir_all(&staging_dir);
+    let (synced_tools, failed_tools) = result?;
 
     // 只在全局模式下更新注册表
     if workspace_path.is_none() {
@@ -3258,6 +4980,13 @@ pub async fn update_skill(skill_name: String, workspace_path: Option<String>) ->
             if let Some(ref mut meta) = entry.metadata {
                 meta.version = Some(new_manifest.version.clone());
                 meta.repository = Some(repo_url.clone());
+                meta.commit_hash = commit.clone();
+                meta.git_ref = git_ref.clone();
+                meta.upstream_name = upstream_name.clone();
+                meta.signature = Some(signature);
+                if let Some(parsed) = read_skill_metadata(&skill_dir, &skill_name) {
+                    meta.apply_frontmatter(parsed);
+                }
             }
         } else {
             // 添加新条目
@@ -3273,41 +5002,82 @@ pub async fn update_skill(skill_name: String, workspace_path: Option<String>) ->
                     name: skill_name.clone(),
                     version: Some(new_manifest.version.clone()),
                     description: new_manifest.description.clone(),
-                    tags: Vec::new(),
-                    target_tools: Vec::new(),
                     repository: Some(repo_url.clone()),
-                    source_id: None,
+                    commit_hash: commit.clone(),
+                    git_ref: git_ref.clone(),
+                    upstream_name: upstream_name.clone(),
+                    signature: Some(signature),
+                    ..Default::default()
                 }),
+                tool_copies: Vec::new(),
+                bundles: Vec::new(),
             });
         }
+        txn.snapshot_file(&get_skill_registry_path()?)?;
         write_skill_registry(registry)?;
     }
 
-    // 清理临时文件
-    if temp_dir.exists() {
-        let _ = fs::remove_dir_all(&temp_dir);
-    }
-    if backup_dir.exists() {
-        let _ = fs::remove_dir_all(&backup_dir);
-    }
+    txn.snapshot_file(&super::skill_lock::get_skill_lockfile_path(workspace_path.as_deref())?)?;
+    super::skill_lock::upsert_skill_lock_entry(
+        workspace_path.as_deref(),
+        super::skill_lock::SkillLockEntry {
+            name: skill_name.clone(),
+            repository: repo_url.clone(),
+            git_ref,
+            commit,
+            subpath: lock_subpath,
+            manifest_hash: compute_manifest_hash(&new_manifest),
+            target_tools: lock_tools,
+            upstream_name,
+        },
+    )?;
+    txn.commit();
 
+    if let Err(e) = super::skill_history::prune_skill_history(&skill_name, workspace_path.as_deref()) {
+        eprintln!("⚠️  [Backend] 清理历史版本失败: {}", e);
+    }
     clear_skill_update_cache(workspace_path.as_deref())?;
 
     println!("✅ [Backend] Skill '{}' 更新成功", skill_name);
+    let merged_files = plan.merged_files();
+    let kept_local_files = plan.kept_local_files();
+    let conflicts = plan.conflicts();
     let mut message = "成功更新到最新版本".to_string();
+    if !merged_files.is_empty() {
+        message.push_str(&format!("；自动合并 {} 个本地修改的文件", merged_files.len()));
+    }
+    if !kept_local_files.is_empty() {
+        message.push_str(&format!("；保留 {} 个本地修改的文件", kept_local_files.len()));
+    }
+    if !conflicts.is_empty() {
+        message.push_str(&format!("；{} 个冲突已按「{}」处理", conflicts.len(), strategy.label()));
+    }
     if !synced_tools.is_empty() {
         message.push_str(&format!("；已同步到 {} 个工具: {}", synced_tools.len(), synced_tools.join(", ")));
     }
     if !failed_tools.is_empty() {
         message.push_str(&format!("；{} 个工具同步失败: {}", failed_tools.len(), failed_tools.join(", ")));
     }
-    Ok(message)
+    Ok(SkillUpdateOutcome {
+        applied: true,
+        message,
+        local_modified_files: plan.local_modified_files,
+        merged_files,
+        kept_local_files,
+        conflicts,
+    })
 }
 
-/// 设置 Skill 的仓库地址
+/// 设置 Skill 的仓库地址及跟踪的 ref（为空时跟随仓库默认分支）
 #[tauri::command]
-pub fn set_skill_repository(skill_name: String, repository: String, workspace_path: Option<String>) -> Result<(), String> {
+pub fn set_skill_repository(
+    skill_name: String,
+    repository: String,
+    git_ref: Option<String>,
+    workspace_path: Option<String>,
+) -> Result<(), String> {
     println!("📝 [Backend] 设置 Skill '{}' 的仓库地址: {}", skill_name, repository);
+    let git_ref = git_ref.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
 
     let skills_dir = if let Some(ref ws_path) = workspace_path {
         let ws_path_buf = PathBuf::from(ws_path);
@@ -3331,8 +5101,9 @@ pub fn set_skill_repository(skill_name: String, repository: String, workspace_pa
         generate_skill_manifest(&skill_dir, None).unwrap_or_default()
     });
 
-    // 更新仓库地址
+    // 更新仓库地址和 ref
     manifest.repository = Some(repository.clone());
+    manifest.git_ref = git_ref.clone();
 
     // 写入清单文件
     write_skill_manifest(&skill_dir, &manifest)?;
@@ -3344,15 +5115,15 @@ pub fn set_skill_repository(skill_name: String, repository: String, workspace_pa
             // 更新已有条目
             if let Some(ref mut meta) = entry.metadata {
                 meta.repository = Some(repository.clone());
+                meta.git_ref = git_ref.clone();
             } else {
                 entry.metadata = Some(SkillMetadata {
                     name: skill_name.clone(),
                     version: Some(manifest.version.clone()),
                     description: manifest.description.clone(),
-                    tags: Vec::new(),
-                    target_tools: Vec::new(),
                     repository: Some(repository.clone()),
-                    source_id: None,
+                    git_ref: git_ref.clone(),
+                    ..Default::default()
                 });
             }
         } else {
@@ -3369,11 +5140,12 @@ pub fn set_skill_repository(skill_name: String, repository: String, workspace_pa
                     name: skill_name.clone(),
                     version: Some(manifest.version.clone()),
                     description: manifest.description.clone(),
-                    tags: Vec::new(),
-                    target_tools: Vec::new(),
                     repository: Some(repository.clone()),
-                    source_id: None,
+                    git_ref: git_ref.clone(),
+                    ..Default::default()
                 }),
+                tool_copies: Vec::new(),
+                bundles: Vec::new(),
             });
         }
         write_skill_registry(registry)?;
@@ -3420,6 +5192,10 @@ pub fn apply_skill_to_tools(
 
     let mut installed_tools = Vec::new();
     let mut skipped_tools = Vec::new();
+    let mut format_errors = Vec::new();
+
+    // 源目录是链接时，目标工具也链接到同一份规范副本
+    let store_dir = super::skill_links::linked_store_dir(&source_dir, workspace_path.as_deref());
 
     // 复制到所有目标工具目录
     for (tool_name, tool_skills_dir) in &target_dirs {
@@ -3437,9 +5213,19 @@ pub fn apply_skill_to_tools(
             continue;
         }
 
-        // 复制目录
-        match copy_dir_recursive(&source_dir, &target_dir) {
-            Ok(_) => {
+        // 复制目录（或创建链接）并转换为目标工具的格式
+        let apply_result = match store_dir {
+            Some(ref store_dir) => super::skill_links::link_skill_dir(store_dir, &target_dir),
+            None => copy_dir_recursive(&source_dir, &target_dir).map(|_| false),
+        };
+        match apply_result {
+            Ok(linked) => {
+                if !linked {
+                    if let Err(e) = super::skill_adapters::apply_skill_format(tool_name, &target_dir, &skill_name) {
+                        eprintln!("⚠️  [Backend] 转换 {} 的 {} 格式失败: {}", skill_name, tool_name, e);
+                        format_errors.push(format!("{}（{}）", tool_name, e));
+                    }
+                }
                 println!("✅ [Backend] 成功应用 {} 到 {}", skill_name, tool_name);
                 installed_tools.push(tool_name.clone());
             }
@@ -3457,6 +5243,23 @@ pub fn apply_skill_to_tools(
         }
     }
 
+    if store_dir.is_some() {
+        if let Err(e) = super::skill_links::sync_linked_skill_formats(&skill_name, workspace_path.as_deref()) {
+            eprintln!("⚠️  [Backend] 生成 {} 的格式文件失败: {}", skill_name, e);
+            format_errors.push(e);
+        }
+    }
+
+    if let Some(entry) = super::skill_lock::load_skill_lockfile(workspace_path.as_deref())?
+        .skills
+        .into_iter()
+        .find(|s| s.name == skill_name)
+    {
+        let mut lock_tools = entry.target_tools;
+        lock_tools.extend(installed_tools.iter().cloned());
+        super::skill_lock::set_skill_lock_target_tools(workspace_path.as_deref(), &skill_name, lock_tools)?;
+    }
+
     // 只在全局模式下更新注册表
     if workspace_path.is_none() {
         let mut registry = read_skill_registry()
@@ -3475,7 +5278,7 @@ pub fn apply_skill_to_tools(
             .map_err(|e| format!("写入注册表失败: {}", e))?;
     }
 
-    let message = if skipped_tools.is_empty() {
+    let mut message = if skipped_tools.is_empty() {
         format!("成功应用到 {} 个工具: {}", installed_tools.len(), installed_tools.join(", "))
     } else {
         format!(
@@ -3486,6 +5289,9 @@ pub fn apply_skill_to_tools(
             skipped_tools.join(", ")
         )
     };
+    if !format_errors.is_empty() {
+        message.push_str(&format!("；格式转换失败: {}", format_errors.join(", ")));
+    }
 
     println!("🎉 [Backend] {}", message);
     Ok(message)
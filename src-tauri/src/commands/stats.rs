@@ -27,7 +27,7 @@ pub fn read_stats_cache() -> Result<serde_json::Value, String> {
 }
 
 /// 获取用户默认 shell
-fn get_user_shell() -> String {
+pub fn get_user_shell() -> String {
     std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string())
 }
 
@@ -13,7 +13,15 @@ use commands::{
     check_all_skill_updates, check_skill_update, create_skill, install_skill_from_repo, list_installed_skills,
     list_skill_files, open_skill_folder, read_skill_file, read_skill_md, read_skill_registry, scan_repo_skills,
     set_skill_repository, toggle_skill, uninstall_skill, update_skill, write_skill_registry,
-    apply_skill_to_tools, remove_skill_from_tools, get_supported_ai_tools,
+    apply_skill_to_tools, remove_skill_from_tools, get_supported_ai_tools, save_custom_ai_tool,
+    remove_custom_ai_tool,
+    read_skill_lockfile, install_skills_from_lockfile, list_skill_versions, rollback_skill, lint_skill,
+    update_skill_frontmatter,
+    get_skill_trust_store, add_trusted_key, remove_trusted_key, update_signature_policy,
+    generate_signing_manifest, verify_skill_signature,
+    scan_local_skills, install_skill_from_local, export_skill,
+    read_skill_bundle, list_skill_bundles, install_skill_bundle, diff_skill_bundle, update_skill_bundle,
+    remove_skill_bundle,
     // Skill 市场命令
     add_marketplace, get_marketplace_skills, install_skill_from_marketplace, list_marketplace,
     refresh_all_marketplace, refresh_marketplace, remove_marketplace, toggle_marketplace,
@@ -85,6 +93,11 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
+        .setup(|app| {
+            // 长时间操作的进度事件需要 AppHandle
+            commands::progress::init(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // 配置命令
             get_claude_config_dir,
@@ -116,6 +129,15 @@ pub fn run() {
             open_skill_folder,
             scan_repo_skills,
             install_skill_from_repo,
+            scan_local_skills,
+            install_skill_from_local,
+            export_skill,
+            read_skill_bundle,
+            list_skill_bundles,
+            install_skill_bundle,
+            diff_skill_bundle,
+            update_skill_bundle,
+            remove_skill_bundle,
             create_skill,
             check_all_skill_updates,
             check_skill_update,
@@ -124,6 +146,20 @@ pub fn run() {
             apply_skill_to_tools,
             remove_skill_from_tools,
             get_supported_ai_tools,
+            save_custom_ai_tool,
+            remove_custom_ai_tool,
+            read_skill_lockfile,
+            install_skills_from_lockfile,
+            list_skill_versions,
+            rollback_skill,
+            lint_skill,
+            update_skill_frontmatter,
+            get_skill_trust_store,
+            add_trusted_key,
+            remove_trusted_key,
+            update_signature_policy,
+            generate_signing_manifest,
+            verify_skill_signature,
             // Skill 市场命令
             list_marketplace,
             add_marketplace,
@@ -0,0 +1,45 @@
+import { Progress } from '@/components/ui/progress';
+import type { OperationProgress } from '@/services/progress';
+
+const phaseLabels: Record<OperationProgress['phase'], string> = {
+  clone: '获取仓库',
+  scan: '扫描',
+  install: '安装',
+  done: '完成',
+};
+
+// 格式化字节大小
+const formatBytes = (bytes: number): string => {
+  if (bytes === 0) return '0 B';
+  const k = 1024;
+  const sizes = ['B', 'KB', 'MB', 'GB'];
+  const i = Math.min(Math.floor(Math.log(bytes) / Math.log(k)), sizes.length - 1);
+  return `${(bytes / Math.pow(k, i)).toFixed(2)} ${sizes[i]}`;
+};
+
+interface OperationProgressBarProps {
+  progress: OperationProgress | null;
+}
+
+export function OperationProgressBar({ progress }: OperationProgressBarProps) {
+  if (!progress) return null;
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between gap-2 text-sm">
+        <span className="truncate text-muted-foreground">
+          {phaseLabels[progress.phase]} · {progress.message}
+        </span>
+        <span className="shrink-0 font-medium">
+          {progress.bytes !== null && `${formatBytes(progress.bytes)} `}
+          {progress.percent !== null && `${progress.percent}%`}
+        </span>
+      </div>
+      {/* 无法估计百分比时显示满格动画 */}
+      <Progress
+        value={progress.percent ?? 100}
+        className={progress.percent === null ? 'animate-pulse' : undefined}
+      />
+    </div>
+  );
+}
@@ -18,7 +18,7 @@ import {
 } from '@/components/ui/alert-dialog';
 import { cn, toBrowsableRepoUrl } from '@/lib/utils';
 import type { AiToolType, SkillRegistryEntry, SkillUpdateSummary } from '@/types/skills';
-import { AI_TOOL_META } from '@/types/skills';
+import { getAiToolMeta } from '@/types/skills';
 
 interface SkillCardProps {
   skill: SkillRegistryEntry;
@@ -38,12 +38,15 @@ export default function SkillCard({ skill, updateInfo, updating = false, onToggl
     (skill.installedBy && skill.installedBy.length > 0)
   );
   const hasUpdate = Boolean(updateInfo?.hasUpdate);
+  const divergentTools = (skill.toolCopies ?? []).filter((copy) => copy.isDivergent).map((copy) => copy.tool);
+  const linkedTools = (skill.toolCopies ?? []).filter((copy) => copy.linked).map((copy) => copy.tool);
   const updateSummary = hasUpdate
     ? [
         updateInfo?.outdatedTools?.length ? `待同步 ${updateInfo.outdatedTools.length} 个工具` : null,
         updateInfo?.changedFiles?.length ? `${updateInfo.changedFiles.length} 个文件变更` : null,
         updateInfo?.newFiles?.length ? `新增 ${updateInfo.newFiles.length} 个文件` : null,
         updateInfo?.removedFiles?.length ? `删除 ${updateInfo.removedFiles.length} 个文件` : null,
+        updateInfo?.localModifiedFiles?.length ? `本地修改 ${updateInfo.localModifiedFiles.length} 个文件` : null,
       ].filter(Boolean)
     : [];
 
@@ -116,11 +119,38 @@ export default function SkillCard({ skill, updateInfo, updating = false, onToggl
                 有更新
               </Badge>
             )}
+            {divergentTools.length > 0 && (
+              <Badge
+                variant="outline"
+                className="border-orange-500/30 text-xs text-orange-700 dark:text-orange-300"
+                title={`与其他副本内容不一致: ${divergentTools
+                  .map((tool) => getAiToolMeta(tool).displayName)
+                  .join(', ')}`}
+              >
+                {divergentTools.length} 个副本不一致
+              </Badge>
+            )}
+            {skill.bundles?.map((bundle) => (
+              <Badge key={bundle} variant="outline" className="text-xs" title={`属于套件 ${bundle}`}>
+                {bundle}
+              </Badge>
+            ))}
+            {linkedTools.length > 0 && (
+              <Badge
+                variant="outline"
+                className="text-xs"
+                title={`共用同一份副本: ${linkedTools
+                  .map((tool) => getAiToolMeta(tool).displayName)
+                  .join(', ')}`}
+              >
+                链接模式
+              </Badge>
+            )}
             {skill.installedBy && skill.installedBy.length > 0 && (
               <div className="inline-flex flex-wrap items-center gap-1.5 rounded-[14px] border border-border/70 bg-muted/35 px-2.5 py-1.5 text-xs text-muted-foreground">
                 <span className="font-medium text-foreground/80">已安装到</span>
                 {skill.installedBy.map((tool) => {
-                  const toolMeta = AI_TOOL_META[tool as keyof typeof AI_TOOL_META];
+                  const toolMeta = getAiToolMeta(tool);
                   return (
                     <button
                       type="button"
@@ -132,7 +162,7 @@ export default function SkillCard({ skill, updateInfo, updating = false, onToggl
                         onOpenInstalledTool?.(tool);
                       }}
                     >
-                      <span>{toolMeta?.displayName ?? tool}</span>
+                      <span>{toolMeta.displayName}</span>
                     </button>
                   );
                 })}
@@ -155,8 +185,7 @@ export default function SkillCard({ skill, updateInfo, updating = false, onToggl
               </Badge>
             ))}
             {skill.metadata.targetTools?.map((toolType) => {
-              const toolMeta = AI_TOOL_META[toolType as keyof typeof AI_TOOL_META];
-              if (!toolMeta) return null;
+              const toolMeta = getAiToolMeta(toolType);
               return (
                 <Badge
                   key={toolType}
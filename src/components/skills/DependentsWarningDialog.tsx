@@ -0,0 +1,54 @@
+import {
+  AlertDialog,
+  AlertDialogAction,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from '@/components/ui/alert-dialog';
+
+export interface DependentsWarning {
+  skillName: string;
+  action: 'uninstall' | 'disable';
+  dependents: string[];
+}
+
+interface DependentsWarningDialogProps {
+  warning: DependentsWarning | null;
+  onCancel: () => void;
+  // 用户确认后以 force 重新执行
+  onConfirm: (warning: DependentsWarning) => void;
+}
+
+export function DependentsWarningDialog({ warning, onCancel, onConfirm }: DependentsWarningDialogProps) {
+  const label = warning?.action === 'disable' ? '禁用' : '删除';
+
+  return (
+    <AlertDialog open={warning !== null} onOpenChange={(open) => !open && onCancel()}>
+      <AlertDialogContent>
+        <AlertDialogHeader>
+          <AlertDialogTitle>其他 Skill 依赖 "{warning?.skillName}"</AlertDialogTitle>
+          <AlertDialogDescription>
+            以下 Skill 在 requires 中声明了对它的依赖，{label}后可能无法正常工作：
+          </AlertDialogDescription>
+        </AlertDialogHeader>
+        <ul className="space-y-1 text-sm">
+          {warning?.dependents.map((name) => (
+            <li key={name} className="font-mono">{name}</li>
+          ))}
+        </ul>
+        <AlertDialogFooter>
+          <AlertDialogCancel>取消</AlertDialogCancel>
+          <AlertDialogAction
+            onClick={() => warning && onConfirm(warning)}
+            className="bg-destructive text-destructive-foreground hover:bg-destructive/90"
+          >
+            仍然{label}
+          </AlertDialogAction>
+        </AlertDialogFooter>
+      </AlertDialogContent>
+    </AlertDialog>
+  );
+}
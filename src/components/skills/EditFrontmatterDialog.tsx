@@ -0,0 +1,103 @@
+import { useState, useEffect } from 'react';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import type { SkillFrontmatterUpdate, SkillMetadata } from '@/types/skills';
+
+interface EditFrontmatterDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  onConfirm: (update: SkillFrontmatterUpdate) => void;
+  metadata?: SkillMetadata;
+  skillName?: string;
+}
+
+const splitList = (value: string) =>
+  value.split(',').map((item) => item.trim()).filter(Boolean);
+
+export function EditFrontmatterDialog({
+  open,
+  onOpenChange,
+  onConfirm,
+  metadata,
+  skillName,
+}: EditFrontmatterDialogProps) {
+  const [description, setDescription] = useState('');
+  const [tags, setTags] = useState('');
+  const [aiTools, setAiTools] = useState('');
+  const [allowedTools, setAllowedTools] = useState('');
+
+  // 打开时以当前 frontmatter 为初始值
+  useEffect(() => {
+    if (open) {
+      setDescription(metadata?.description ?? '');
+      setTags((metadata?.tags ?? []).join(', '));
+      setAiTools((metadata?.targetTools ?? []).join(', '));
+      setAllowedTools((metadata?.allowedTools ?? []).join(', '));
+    }
+  }, [open, metadata]);
+
+  const handleConfirm = () => {
+    onConfirm({
+      description,
+      tags: splitList(tags),
+      aiTools: splitList(aiTools),
+      allowedTools: splitList(allowedTools),
+    });
+    onOpenChange(false);
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-[540px]">
+        <DialogHeader className="space-y-2">
+          <DialogTitle className="text-base">编辑 Skill 信息</DialogTitle>
+          <DialogDescription>
+            {skillName ? `修改 "${skillName}" 的 SKILL.md frontmatter，会同步到所有工具中的副本` : '修改 SKILL.md frontmatter'}
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-4 py-1">
+          <div className="space-y-2">
+            <Label htmlFor="skill-description">描述</Label>
+            <textarea
+              id="skill-description"
+              value={description}
+              onChange={(e) => setDescription(e.target.value)}
+              rows={4}
+              className="flex w-full rounded-md border border-input bg-transparent px-3 py-2 text-sm shadow-sm placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-1 focus-visible:ring-ring"
+              placeholder="说明这个 Skill 做什么、什么时候使用"
+            />
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="skill-tags">标签</Label>
+            <Input id="skill-tags" value={tags} onChange={(e) => setTags(e.target.value)} placeholder="多个用逗号分隔" />
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="skill-ai-tools">适用工具（ai-tools）</Label>
+            <Input id="skill-ai-tools" value={aiTools} onChange={(e) => setAiTools(e.target.value)} placeholder="如 claude-code, cursor" />
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="skill-allowed-tools">允许的工具（allowed-tools）</Label>
+            <Input id="skill-allowed-tools" value={allowedTools} onChange={(e) => setAllowedTools(e.target.value)} placeholder="如 Read, Grep, Bash(git:*)" />
+          </div>
+        </div>
+
+        <DialogFooter className="border-t border-border/60 pt-3">
+          <Button variant="outline" onClick={() => onOpenChange(false)}>
+            取消
+          </Button>
+          <Button onClick={handleConfirm}>保存</Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -114,6 +114,14 @@ export function MarketplaceSkillPreviewDialog({
                     目标工具：{skill.targetTools.join(', ')}
                   </span>
                 )}
+                {skill.allowedTools.length > 0 && (
+                  <span className="text-sm text-muted-foreground">
+                    允许的工具：{skill.allowedTools.join(', ')}
+                  </span>
+                )}
+                {skill.license && (
+                  <span className="text-sm text-muted-foreground">许可证：{skill.license}</span>
+                )}
                 <Button
                   onClick={() => onInstall(skill.name)}
                   disabled={(skill.installed && !skill.hasUpdate) || installing}
@@ -10,7 +10,7 @@ import {
 } from '@/components/ui/dialog';
 import { Button } from '@/components/ui/button';
 import { Checkbox } from '@/components/ui/checkbox';
-import { AI_TOOL_META, type AiToolType } from '@/types/skills';
+import { getAiToolMeta } from '@/types/skills';
 
 interface RemoveFromToolsDialogProps {
   open: boolean;
@@ -83,8 +83,7 @@ export function RemoveFromToolsDialog({
           ) : (
             <div className="grid gap-2 sm:grid-cols-2">
               {installedTools.map((toolId) => {
-                const tool = AI_TOOL_META[toolId as AiToolType];
-                if (!tool) return null;
+                const tool = getAiToolMeta(toolId);
 
                 return (
                   <div
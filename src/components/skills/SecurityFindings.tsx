@@ -0,0 +1,69 @@
+import { ShieldAlert } from 'lucide-react';
+import { Badge } from '@/components/ui/badge';
+import { cn } from '@/lib/utils';
+import type { SecurityFinding, SecuritySeverity, SkillSecurityReport } from '@/types/skills';
+
+export const securitySeverityLabels: Record<SecuritySeverity, string> = {
+  low: '低风险',
+  medium: '中风险',
+  high: '高风险',
+  critical: '严重',
+};
+
+const severityClassNames: Record<SecuritySeverity, string> = {
+  low: 'border-border text-muted-foreground',
+  medium: 'border-amber-500/50 text-amber-600 dark:text-amber-400',
+  high: 'border-orange-500/50 text-orange-600 dark:text-orange-400',
+  critical: 'border-destructive/60 bg-destructive/10 text-destructive',
+};
+
+// 文件位置；line 为 0 表示针对整个文件（如无法扫描的文件）
+export function formatFindingLocation(finding: SecurityFinding): string {
+  return finding.line > 0 ? `${finding.file}:${finding.line}` : finding.file;
+}
+
+export function SecuritySeverityBadge({ severity }: { severity: SecuritySeverity }) {
+  return (
+    <Badge variant="outline" className={cn('gap-1 text-xs', severityClassNames[severity])}>
+      <ShieldAlert className="h-3 w-3" />
+      {securitySeverityLabels[severity]}
+    </Badge>
+  );
+}
+
+interface SecurityFindingsProps {
+  report: SkillSecurityReport;
+  // 最多显示的条数，其余折叠为提示
+  limit?: number;
+}
+
+export function SecurityFindings({ report, limit = 5 }: SecurityFindingsProps) {
+  if (report.findings.length === 0) return null;
+
+  const shown = report.findings.slice(0, limit);
+  const hidden = report.findings.length - shown.length;
+
+  return (
+    <ul className="mt-2 space-y-1.5">
+      {shown.map((finding, index) => (
+        <li key={`${finding.file}:${finding.line}:${finding.rule}:${index}`} className="text-xs">
+          <div className="flex flex-wrap items-center gap-2">
+            <SecuritySeverityBadge severity={finding.severity} />
+            <span>{finding.message}</span>
+            <span className="font-mono text-muted-foreground">
+              {formatFindingLocation(finding)}
+            </span>
+          </div>
+          {finding.snippet && (
+            <code className="mt-1 block truncate rounded bg-muted px-2 py-1 font-mono text-[11px] text-muted-foreground">
+              {finding.snippet}
+            </code>
+          )}
+        </li>
+      ))}
+      {hidden > 0 && (
+        <li className="text-xs text-muted-foreground">还有 {hidden} 处风险未显示</li>
+      )}
+    </ul>
+  );
+}
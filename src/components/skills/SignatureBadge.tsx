@@ -0,0 +1,43 @@
+import { ShieldCheck, ShieldAlert, ShieldX, Shield } from 'lucide-react';
+import { Badge } from '@/components/ui/badge';
+import { cn } from '@/lib/utils';
+import type { SignatureStatus, SkillSignatureInfo } from '@/types/skills';
+
+export const signatureStatusLabels: Record<SignatureStatus, string> = {
+  verified: '已签名',
+  unsigned: '未签名',
+  'untrusted-key': '公钥不受信任',
+  invalid: '签名无效',
+};
+
+const statusStyles: Record<SignatureStatus, { className: string; icon: typeof Shield }> = {
+  verified: { className: 'border-emerald-500/50 text-emerald-600 dark:text-emerald-400', icon: ShieldCheck },
+  unsigned: { className: 'border-border text-muted-foreground', icon: Shield },
+  'untrusted-key': { className: 'border-amber-500/50 text-amber-600 dark:text-amber-400', icon: ShieldAlert },
+  invalid: { className: 'border-destructive/60 bg-destructive/10 text-destructive', icon: ShieldX },
+};
+
+interface SignatureBadgeProps {
+  signature: SkillSignatureInfo;
+  className?: string;
+}
+
+export function SignatureBadge({ signature, className }: SignatureBadgeProps) {
+  const { className: statusClassName, icon: Icon } = statusStyles[signature.status];
+  const title = [
+    signature.publisher && `发布者: ${signature.publisher}`,
+    signature.keyId && `公钥 ID: ${signature.keyId}`,
+    signature.message,
+  ]
+    .filter(Boolean)
+    .join('\n');
+
+  return (
+    <Badge variant="outline" className={cn('gap-1 text-xs', statusClassName, className)} title={title || undefined}>
+      <Icon className="h-3 w-3" />
+      {signature.status === 'verified' && signature.publisher
+        ? `${signature.publisher} 已签名`
+        : signatureStatusLabels[signature.status]}
+    </Badge>
+  );
+}
@@ -0,0 +1,257 @@
+import { useEffect, useState } from 'react';
+import { toast } from 'sonner';
+import { open as openFile } from '@tauri-apps/plugin-dialog';
+import { FileText, GitCompare, Loader2, RefreshCw, Trash2 } from 'lucide-react';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import {
+  diffSkillBundle,
+  installSkillBundle,
+  listSkillBundles,
+  readSkillBundle,
+  removeSkillBundle,
+  updateSkillBundle,
+} from '@/services/skills';
+import type { InstalledSkillBundle, SkillBundle, SkillBundleDiff, SkillBundleResult } from '@/types/skills';
+
+interface SkillBundlesDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  workspacePath?: string | null;
+  // 安装、更新或移除套件后刷新列表
+  onChanged?: () => void;
+}
+
+const errorMessage = (err: unknown, fallback: string) =>
+  typeof err === 'string' ? err : (err instanceof Error ? err.message : fallback);
+
+const summarizeResult = (result: SkillBundleResult) =>
+  [
+    result.installed.length > 0 && `新安装 ${result.installed.length} 个`,
+    result.updated.length > 0 && `更新 ${result.updated.length} 个`,
+    result.removed.length > 0 && `卸载 ${result.removed.length} 个`,
+    result.unchanged.length > 0 && `无变化 ${result.unchanged.length} 个`,
+  ]
+    .filter(Boolean)
+    .join('，') || '没有需要处理的 Skill';
+
+const diffGroups: { key: keyof Omit<SkillBundleDiff, 'bundleName'>; label: string; className: string }[] = [
+  { key: 'missing', label: '未安装', className: 'border-amber-500/50 text-amber-600 dark:text-amber-400' },
+  { key: 'outdated', label: '有更新', className: 'border-amber-500/50 text-amber-600 dark:text-amber-400' },
+  { key: 'refChanged', label: '来源已变更', className: 'border-amber-500/50 text-amber-600 dark:text-amber-400' },
+  { key: 'extra', label: '已移出套件', className: 'border-orange-500/50 text-orange-600 dark:text-orange-400' },
+  { key: 'upToDate', label: '已是最新', className: 'border-border text-muted-foreground' },
+  { key: 'errors', label: '检查失败', className: 'border-destructive/60 text-destructive' },
+];
+
+export function SkillBundlesDialog({ open, onOpenChange, workspacePath, onChanged }: SkillBundlesDialogProps) {
+  const [bundles, setBundles] = useState<InstalledSkillBundle[]>([]);
+  const [preview, setPreview] = useState<{ path: string; bundle: SkillBundle } | null>(null);
+  const [diffs, setDiffs] = useState<Record<string, SkillBundleDiff>>({});
+  // 正在处理的套件名称，安装新套件时为 preview 的名称
+  const [busy, setBusy] = useState<string | null>(null);
+
+  const loadBundles = async () => {
+    try {
+      setBundles(await listSkillBundles(workspacePath));
+    } catch (err) {
+      toast.error(errorMessage(err, '读取套件失败'));
+    }
+  };
+
+  useEffect(() => {
+    if (!open) return;
+    setPreview(null);
+    setDiffs({});
+    loadBundles();
+  }, [open, workspacePath]);
+
+  const reportResult = (title: string, result: SkillBundleResult) => {
+    if (result.failed.length > 0) {
+      toast.warning(title, { description: `${summarizeResult(result)}\n失败: ${result.failed.join('; ')}` });
+    } else {
+      toast.success(title, { description: summarizeResult(result) });
+    }
+  };
+
+  const handlePickBundle = async () => {
+    const selected = await openFile({
+      multiple: false,
+      title: '选择套件文件',
+      filters: [{ name: 'Skill 套件', extensions: ['yaml', 'yml'] }],
+    });
+    if (typeof selected !== 'string') return;
+    try {
+      setPreview({ path: selected, bundle: await readSkillBundle(selected) });
+    } catch (err) {
+      toast.error(errorMessage(err, '读取套件文件失败'));
+    }
+  };
+
+  const handleInstall = async () => {
+    if (!preview) return;
+    setBusy(preview.bundle.name);
+    try {
+      const result = await installSkillBundle(preview.path, workspacePath);
+      reportResult(`套件 ${result.bundleName} 已安装`, result);
+      setPreview(null);
+      await loadBundles();
+      onChanged?.();
+    } catch (err) {
+      toast.error('安装套件失败', { description: errorMessage(err, '安装失败') });
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleDiff = async (name: string) => {
+    setBusy(name);
+    try {
+      const diff = await diffSkillBundle(name, workspacePath);
+      setDiffs((prev) => ({ ...prev, [name]: diff }));
+    } catch (err) {
+      toast.error('对比失败', { description: errorMessage(err, '对比失败') });
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleUpdate = async (name: string) => {
+    setBusy(name);
+    try {
+      const result = await updateSkillBundle(name, workspacePath);
+      reportResult(`套件 ${name} 已更新`, result);
+      setDiffs(({ [name]: _, ...rest }) => rest);
+      await loadBundles();
+      onChanged?.();
+    } catch (err) {
+      toast.error('更新套件失败', { description: errorMessage(err, '更新失败') });
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleRemove = async (name: string) => {
+    if (!window.confirm(`移除套件 ${name}？不属于其他套件的 Skills 会一并卸载。`)) return;
+    setBusy(name);
+    try {
+      const result = await removeSkillBundle(name, workspacePath);
+      reportResult(`套件 ${name} 已移除`, result);
+      await loadBundles();
+      onChanged?.();
+    } catch (err) {
+      toast.error('移除套件失败', { description: errorMessage(err, '移除失败') });
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-h-[80vh] max-w-2xl overflow-y-auto">
+        <DialogHeader>
+          <DialogTitle>Skill 套件</DialogTitle>
+          <DialogDescription>
+            用一份 YAML 描述团队统一使用的 Skills（仓库、ref、skill 名称和目标工具），整体安装、更新和移除
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-4">
+          <div className="flex justify-end">
+            <Button variant="outline" size="sm" onClick={handlePickBundle} disabled={busy !== null}>
+              <FileText className="mr-2 h-4 w-4" />
+              选择套件文件
+            </Button>
+          </div>
+
+          {preview && (
+            <div className="rounded-lg border p-3">
+              <p className="font-medium">{preview.bundle.name}</p>
+              {preview.bundle.description && (
+                <p className="text-sm text-muted-foreground">{preview.bundle.description}</p>
+              )}
+              <ul className="mt-2 space-y-1 text-xs">
+                {preview.bundle.skills.map((source, index) => (
+                  <li key={`${source.repo}:${index}`} className="font-mono text-muted-foreground">
+                    {source.repo}
+                    {source.ref && ` @ ${source.ref}`}
+                    {' · '}
+                    {source.skills?.length ? source.skills.join(', ') : '全部 skill'}
+                  </li>
+                ))}
+              </ul>
+              <div className="mt-3 flex justify-end gap-2">
+                <Button variant="ghost" size="sm" onClick={() => setPreview(null)} disabled={busy !== null}>
+                  取消
+                </Button>
+                <Button size="sm" onClick={handleInstall} disabled={busy !== null}>
+                  {busy === preview.bundle.name && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+                  安装套件
+                </Button>
+              </div>
+            </div>
+          )}
+
+          {bundles.length === 0 && !preview && (
+            <p className="text-sm text-muted-foreground">尚未安装套件</p>
+          )}
+
+          {bundles.map((bundle) => {
+            const name = bundle.definition.name;
+            const diff = diffs[name];
+            return (
+              <div key={name} className="rounded-lg border p-3">
+                <div className="flex items-start justify-between gap-2">
+                  <div className="min-w-0">
+                    <p className="font-medium">{name}</p>
+                    {bundle.definition.description && (
+                      <p className="text-sm text-muted-foreground">{bundle.definition.description}</p>
+                    )}
+                    <p className="mt-1 text-xs text-muted-foreground">
+                      {bundle.skills.length} 个 Skill：{bundle.skills.join(', ')}
+                    </p>
+                  </div>
+                  <div className="flex shrink-0 gap-1">
+                    {busy === name && <Loader2 className="m-2 h-4 w-4 animate-spin" />}
+                    <Button variant="ghost" size="sm" title="对比" onClick={() => handleDiff(name)} disabled={busy !== null}>
+                      <GitCompare className="h-4 w-4" />
+                    </Button>
+                    <Button variant="ghost" size="sm" title="更新" onClick={() => handleUpdate(name)} disabled={busy !== null}>
+                      <RefreshCw className="h-4 w-4" />
+                    </Button>
+                    <Button variant="ghost" size="sm" title="移除" onClick={() => handleRemove(name)} disabled={busy !== null}>
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                </div>
+                {diff && (
+                  <div className="mt-2 flex flex-wrap gap-1.5">
+                    {diffGroups
+                      .filter((group) => diff[group.key].length > 0)
+                      .map((group) => (
+                        <Badge
+                          key={group.key}
+                          variant="outline"
+                          className={`text-xs ${group.className}`}
+                          title={diff[group.key].join('\n')}
+                        >
+                          {group.label} {diff[group.key].length}
+                        </Badge>
+                      ))}
+                  </div>
+                )}
+              </div>
+            );
+          })}
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,119 @@
+import { useEffect, useMemo, useState } from 'react';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import type { SkillCollision, SkillCollisionResolution, SkillCollisionResolutions } from '@/types/skills';
+
+interface SkillConflictDialogProps {
+  // 为 null 时关闭
+  conflicts: SkillCollision[] | null;
+  onCancel: () => void;
+  // 按原名称返回每个重名 Skill 的处理方式，调用方据此重新安装
+  onConfirm: (resolutions: SkillCollisionResolutions) => void;
+}
+
+type CollisionAction = SkillCollisionResolution['action'];
+
+const actions: { value: CollisionAction; label: string }[] = [
+  { value: 'alias', label: '别名安装' },
+  { value: 'replace', label: '替换' },
+  { value: 'skip', label: '跳过' },
+];
+
+export function SkillConflictDialog({ conflicts, onCancel, onConfirm }: SkillConflictDialogProps) {
+  const [choices, setChoices] = useState<Record<string, CollisionAction>>({});
+  const [aliases, setAliases] = useState<Record<string, string>>({});
+
+  // 默认以建议的别名安装，保留已安装的 Skill
+  useEffect(() => {
+    if (!conflicts) return;
+    setChoices(Object.fromEntries(conflicts.map((c) => [c.name, 'alias' as const])));
+    setAliases(Object.fromEntries(conflicts.map((c) => [c.name, c.suggestedAlias])));
+  }, [conflicts]);
+
+  const canSubmit = useMemo(
+    () => (conflicts ?? []).every((c) => choices[c.name] !== 'alias' || aliases[c.name]?.trim()),
+    [conflicts, choices, aliases]
+  );
+
+  const handleConfirm = () => {
+    if (!conflicts || !canSubmit) return;
+    const resolutions: SkillCollisionResolutions = {};
+    for (const conflict of conflicts) {
+      const action = choices[conflict.name] ?? 'alias';
+      resolutions[conflict.name] = action === 'alias'
+        ? { action, alias: aliases[conflict.name].trim() }
+        : { action };
+    }
+    onConfirm(resolutions);
+  };
+
+  return (
+    <Dialog open={conflicts !== null} onOpenChange={(open) => !open && onCancel()}>
+      <DialogContent className="sm:max-w-[560px]">
+        <DialogHeader>
+          <DialogTitle>Skill 重名</DialogTitle>
+          <DialogDescription>
+            以下 Skill 已从其他来源安装。可以用别名安装、替换已安装的 Skill，或跳过
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-3 py-2">
+          {conflicts?.map((conflict) => (
+            <div key={conflict.name} className="space-y-2 rounded-lg border p-3">
+              <div>
+                <p className="font-mono text-sm font-medium">{conflict.name}</p>
+                <p className="break-all text-xs text-muted-foreground">
+                  已安装：{conflict.existingRepository || '手动添加（无来源）'}
+                </p>
+                <p className="break-all text-xs text-muted-foreground">
+                  将安装：{conflict.incomingRepository}
+                </p>
+              </div>
+              <div className="flex gap-2">
+                {actions.map((action) => (
+                  <Button
+                    key={action.value}
+                    type="button"
+                    size="sm"
+                    variant={choices[conflict.name] === action.value ? 'default' : 'outline'}
+                    onClick={() => setChoices((prev) => ({ ...prev, [conflict.name]: action.value }))}
+                  >
+                    {action.label}
+                  </Button>
+                ))}
+              </div>
+              {choices[conflict.name] === 'alias' && (
+                <Input
+                  value={aliases[conflict.name] ?? ''}
+                  onChange={(e) => setAliases((prev) => ({ ...prev, [conflict.name]: e.target.value }))}
+                  placeholder="别名"
+                  className="font-mono"
+                />
+              )}
+              {choices[conflict.name] === 'replace' && (
+                <p className="text-xs text-destructive">已安装的 {conflict.name} 会被卸载，本地修改将丢失</p>
+              )}
+            </div>
+          ))}
+        </div>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={onCancel}>
+            取消
+          </Button>
+          <Button onClick={handleConfirm} disabled={!canSubmit}>
+            继续安装
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
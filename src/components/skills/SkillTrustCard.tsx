@@ -0,0 +1,155 @@
+import { useEffect, useState } from 'react';
+import { toast } from 'sonner';
+import { Trash2 } from 'lucide-react';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Switch } from '@/components/ui/switch';
+import { Separator } from '@/components/ui/separator';
+import {
+  getSkillTrustStore,
+  addTrustedKey,
+  removeTrustedKey,
+  updateSignaturePolicy,
+} from '@/services/skills';
+import type { SkillTrustStore } from '@/types/skills';
+
+const errorMessage = (err: unknown, fallback: string) =>
+  typeof err === 'string' ? err : (err instanceof Error ? err.message : fallback);
+
+export function SkillTrustCard() {
+  const [store, setStore] = useState<SkillTrustStore | null>(null);
+  const [keyName, setKeyName] = useState('');
+  const [publicKey, setPublicKey] = useState('');
+  const [officialSources, setOfficialSources] = useState('');
+  const [saving, setSaving] = useState(false);
+
+  const loadStore = async () => {
+    try {
+      const result = await getSkillTrustStore();
+      setStore(result);
+      setOfficialSources(result.policy.officialSources.join('\n'));
+    } catch (err) {
+      toast.error(errorMessage(err, '读取信任库失败'));
+    }
+  };
+
+  useEffect(() => {
+    loadStore();
+  }, []);
+
+  const handleAddKey = async () => {
+    setSaving(true);
+    try {
+      const key = await addTrustedKey(keyName, publicKey);
+      toast.success(`已信任 ${key.name} 的公钥 ${key.keyId}`);
+      setKeyName('');
+      setPublicKey('');
+      await loadStore();
+    } catch (err) {
+      toast.error(errorMessage(err, '添加公钥失败'));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleRemoveKey = async (keyId: string) => {
+    try {
+      await removeTrustedKey(keyId);
+      await loadStore();
+    } catch (err) {
+      toast.error(errorMessage(err, '移除公钥失败'));
+    }
+  };
+
+  const savePolicy = async (requireSignatures: boolean) => {
+    try {
+      await updateSignaturePolicy({
+        requireSignatures,
+        officialSources: officialSources.split('\n').map((s) => s.trim()).filter(Boolean),
+      });
+      await loadStore();
+      toast.success('签名策略已保存');
+    } catch (err) {
+      toast.error(errorMessage(err, '保存签名策略失败'));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Skill 签名</CardTitle>
+        <CardDescription>
+          信任发布者的 minisign 公钥，安装和更新时验证 skill 仓库中的 .skill-manifest.json.minisig
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex items-center justify-between">
+          <div>
+            <p className="font-medium">要求签名</p>
+            <p className="text-sm text-muted-foreground">非官方来源的 Skill 必须由信任的发布者签名才能安装或更新</p>
+          </div>
+          <Switch
+            checked={store?.policy.requireSignatures ?? false}
+            onCheckedChange={(checked) => savePolicy(checked)}
+            disabled={!store}
+          />
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="official-sources">官方来源（每行一个仓库地址，不要求签名）</Label>
+          <textarea
+            id="official-sources"
+            value={officialSources}
+            onChange={(e) => setOfficialSources(e.target.value)}
+            onBlur={() => store && savePolicy(store.policy.requireSignatures)}
+            rows={2}
+            className="flex w-full rounded-md border border-input bg-transparent px-3 py-2 font-mono text-xs shadow-sm focus-visible:outline-none focus-visible:ring-1 focus-visible:ring-ring"
+          />
+        </div>
+
+        <Separator />
+
+        <div className="space-y-2">
+          <p className="font-medium">信任的公钥</p>
+          {store && store.keys.length === 0 && (
+            <p className="text-sm text-muted-foreground">尚未添加公钥</p>
+          )}
+          {store?.keys.map((key) => (
+            <div key={key.keyId} className="flex items-center justify-between rounded-lg border px-3 py-2">
+              <div className="min-w-0">
+                <p className="text-sm font-medium">{key.name}</p>
+                <p className="truncate font-mono text-xs text-muted-foreground">{key.keyId} · {key.publicKey}</p>
+              </div>
+              <Button variant="ghost" size="sm" onClick={() => handleRemoveKey(key.keyId)}>
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+        </div>
+
+        <div className="space-y-2">
+          <Label htmlFor="trusted-key-name">添加公钥</Label>
+          <Input
+            id="trusted-key-name"
+            value={keyName}
+            onChange={(e) => setKeyName(e.target.value)}
+            placeholder="发布者名称，如 团队名或组织名"
+          />
+          <textarea
+            value={publicKey}
+            onChange={(e) => setPublicKey(e.target.value)}
+            rows={2}
+            placeholder="粘贴 minisign.pub 的内容或 RW 开头的公钥"
+            className="flex w-full rounded-md border border-input bg-transparent px-3 py-2 font-mono text-xs shadow-sm placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-1 focus-visible:ring-ring"
+          />
+          <div className="flex justify-end">
+            <Button size="sm" onClick={handleAddKey} disabled={saving || !keyName.trim() || !publicKey.trim()}>
+              添加
+            </Button>
+          </div>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -11,7 +11,7 @@ import {
 import { Button } from '@/components/ui/button';
 import { Checkbox } from '@/components/ui/checkbox';
 import { getSupportedAiTools } from '@/services/skills';
-import type { AiToolInfo } from '@/types/skills';
+import { getDefaultTargetTools, type AiToolInfo, type SkillFormat } from '@/types/skills';
 
 interface TargetToolsDialogProps {
   open: boolean;
@@ -24,13 +24,22 @@ interface TargetToolsDialogProps {
   workspaceName?: string; // 当前工作区名称
 }
 
+const SKILL_FORMAT_LABELS: Record<SkillFormat, string> = {
+  claude: 'SKILL.md',
+  'cursor-mdc': '.mdc 规则',
+  'agents-md': 'AGENTS.md',
+};
+
+// 默认值保持同一引用，避免每次渲染都触发初始化选中工具的 effect
+const EMPTY_TOOLS: string[] = [];
+
 export function TargetToolsDialog({
   open,
   onOpenChange,
   onConfirm,
-  defaultTools = [],
+  defaultTools = EMPTY_TOOLS,
   skillName,
-  excludeTools = [],
+  excludeTools = EMPTY_TOOLS,
   workspacePath,
   workspaceName,
 }: TargetToolsDialogProps) {
@@ -53,11 +62,12 @@ export function TargetToolsDialog({
       if (validDefaults.length > 0) {
         setSelectedTools(new Set(validDefaults));
       } else {
-        // 否则默认选中 claude-code
-        setSelectedTools(new Set(['claude-code']));
+        // 否则默认选中本机已安装的工具
+        const available = toolsInfo.filter(t => !excludeTools.includes(t.id));
+        setSelectedTools(new Set(getDefaultTargetTools(available).filter(t => !excludeTools.includes(t))));
       }
     }
-  }, [open, defaultTools, toolsInfo]);
+  }, [open, defaultTools, excludeTools, toolsInfo]);
 
   const toggleTool = (toolId: string) => {
     const newSelected = new Set(selectedTools);
@@ -132,9 +142,15 @@ export function TargetToolsDialog({
                       onClick={(e) => e.stopPropagation()}
                     />
                     <div className="min-w-0 flex-1">
-                      <div className="text-sm font-medium">{tool.displayName}</div>
+                      <div className="flex items-center gap-1.5 text-sm font-medium">
+                        <span className="truncate">{tool.displayName}</span>
+                        {!tool.installed && (
+                          <span className="shrink-0 text-[11px] font-normal text-muted-foreground">未检测到</span>
+                        )}
+                      </div>
                       <div className="mt-0.5 truncate text-[11px] text-muted-foreground">
                           {getInstallPath(tool.relativePath)}
+                          {tool.format !== 'claude' && ` · 转换为 ${SKILL_FORMAT_LABELS[tool.format]}`}
                       </div>
                     </div>
                   </div>
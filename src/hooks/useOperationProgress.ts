@@ -0,0 +1,23 @@
+import { useCallback, useState } from 'react';
+import { createOperationId, listenOperationProgress, type OperationProgress } from '@/services/progress';
+
+/**
+ * 跟踪一次长时间操作的进度
+ * track 为操作生成 ID 并在执行期间监听进度，结束后清空
+ */
+export function useOperationProgress() {
+  const [progress, setProgress] = useState<OperationProgress | null>(null);
+
+  const track = useCallback(async <T>(run: (operationId: string) => Promise<T>): Promise<T> => {
+    const operationId = createOperationId();
+    const unlisten = await listenOperationProgress(operationId, setProgress);
+    try {
+      return await run(operationId);
+    } finally {
+      unlisten();
+      setProgress(null);
+    }
+  }, []);
+
+  return { progress, track };
+}
@@ -1,5 +1,5 @@
 import { useEffect, useState, useCallback, useMemo } from 'react';
-import { HardDrive, Trash2, Loader2, FolderOpen, FileArchive, Database, ChevronDown, ChevronRight, FileText, ArrowUpDown } from 'lucide-react';
+import { HardDrive, Trash2, Loader2, FolderOpen, FileArchive, Database, GitBranch, ChevronDown, ChevronRight, FileText, ArrowUpDown } from 'lucide-react';
 import { toast } from 'sonner';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
 import { Button } from '@/components/ui/button';
@@ -503,11 +503,11 @@ export default function CacheSettings() {
   };
 
   const totalSize = cacheInfo
-    ? cacheInfo.conversations.size_bytes + cacheInfo.backups.size_bytes + cacheInfo.marketplace.size_bytes
+    ? cacheInfo.conversations.size_bytes + cacheInfo.backups.size_bytes + cacheInfo.marketplace.size_bytes + cacheInfo.git.size_bytes
     : 0;
 
   const totalFiles = cacheInfo
-    ? cacheInfo.conversations.file_count + cacheInfo.backups.file_count + cacheInfo.marketplace.file_count
+    ? cacheInfo.conversations.file_count + cacheInfo.backups.file_count + cacheInfo.marketplace.file_count + cacheInfo.git.file_count
     : 0;
 
   if (loading) {
@@ -600,6 +600,17 @@ export default function CacheSettings() {
             onClear={handleClear}
           />
 
+          <CacheCard
+            title="Git 仓库缓存"
+            description="Skill 仓库和市场源的本地镜像，检查更新时只增量获取"
+            icon={GitBranch}
+            sizeBytes={cacheInfo.git.size_bytes}
+            fileCount={cacheInfo.git.file_count}
+            cacheType="git"
+            clearing={clearing}
+            onClear={handleClear}
+          />
+
           <div className="flex justify-end">
             <Button
               variant="destructive"
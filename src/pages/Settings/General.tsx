@@ -10,6 +10,7 @@ import { Switch } from '@/components/ui/switch';
 import { Separator } from '@/components/ui/separator';
 import { settingsAtom } from '@/store/settingsAtoms';
 import { themeAtom } from '@/store/uiAtoms';
+import { SkillTrustCard } from '@/components/skills/SkillTrustCard';
 
 export default function GeneralSettings() {
   const [settings, setSettings] = useAtom(settingsAtom);
@@ -131,6 +132,8 @@ export default function GeneralSettings() {
         </CardContent>
       </Card>
 
+      <SkillTrustCard />
+
       <Card>
         <CardHeader>
           <CardTitle>应用更新</CardTitle>
@@ -1,7 +1,8 @@
 import { useEffect, useState } from 'react';
 import { useAtomValue } from 'jotai';
 import { useParams, Link } from 'react-router-dom';
-import { ArrowLeft, Loader2, Eye, Code, Folder, File, ChevronRight, ChevronDown, FolderOpen, RefreshCw } from 'lucide-react';
+import { save } from '@tauri-apps/plugin-dialog';
+import { ArrowLeft, Loader2, Eye, Code, Folder, File, ChevronRight, ChevronDown, FolderOpen, RefreshCw, ShieldCheck, KeyRound, Download } from 'lucide-react';
 import ReactMarkdown from 'react-markdown';
 import {
   Card,
@@ -17,9 +18,13 @@ import { Skeleton } from '@/components/ui/skeleton';
 import MarkdownEditor from '@/components/common/MarkdownEditor';
 import { TargetToolsDialog } from '@/components/skills/TargetToolsDialog';
 import { RemoveFromToolsDialog } from '@/components/skills/RemoveFromToolsDialog';
-import type { SkillDetail as SkillDetailType, SkillUpdateCheckResult } from '@/types/skills';
-import { AI_TOOL_META, type AiToolType } from '@/types/skills';
-import { getSkillDetail, toggleSkill as toggleSkillApi, readSkillFile, checkSkillUpdate, updateSkill as updateSkillApi, applySkillToTools, removeSkillFromTools } from '@/services/skills';
+import { EditFrontmatterDialog } from '@/components/skills/EditFrontmatterDialog';
+import { SignatureBadge, signatureStatusLabels } from '@/components/skills/SignatureBadge';
+import { DependentsWarningDialog, type DependentsWarning } from '@/components/skills/DependentsWarningDialog';
+import { formatFindingLocation } from '@/components/skills/SecurityFindings';
+import type { SkillDetail as SkillDetailType, SkillUpdateCheckResult, SkillConflictStrategy, SkillLintReport, SkillFrontmatterUpdate } from '@/types/skills';
+import { getAiToolMeta } from '@/types/skills';
+import { getSkillDetail, toggleSkill as toggleSkillApi, readSkillFile, checkSkillUpdate, updateSkill as updateSkillApi, applySkillToTools, removeSkillFromTools, lintSkill, updateSkillFrontmatter, verifySkillSignature, exportSkills, parseDependentsWarning, parseSecurityBlocked } from '@/services/skills';
 import { currentWorkspaceAtom } from '@/store/workspaceAtoms';
 import { logActivity } from '@/lib/activityLogger';
 import { toast } from 'sonner';
@@ -231,6 +236,21 @@ export default function SkillDetail() {
   const [showRemoveDialog, setShowRemoveDialog] = useState(false);
   const [removing, setRemoving] = useState(false);
 
+  // 编辑 frontmatter 状态
+  const [showEditDialog, setShowEditDialog] = useState(false);
+  const [savingFrontmatter, setSavingFrontmatter] = useState(false);
+
+  // 规范检查状态
+  const [lintReport, setLintReport] = useState<SkillLintReport | null>(null);
+  const [linting, setLinting] = useState(false);
+
+  // 签名验证状态
+  const [verifyingSignature, setVerifyingSignature] = useState(false);
+  const [exporting, setExporting] = useState(false);
+
+  // 禁用被其他 Skill 依赖的 Skill 时的确认
+  const [dependentsWarning, setDependentsWarning] = useState<DependentsWarning | null>(null);
+
   // 解析 YAML frontmatter
   const parseFrontmatter = (content: string): { metadata: Record<string, any>; content: string } => {
     const lines = content.split('\n');
@@ -359,14 +379,14 @@ export default function SkillDetail() {
   }, [skillName, selectedFile, currentWorkspace?.path]);
 
   // 切换启用状态
-  const handleToggle = async (enabled: boolean) => {
+  const handleToggle = async (enabled: boolean, force = false) => {
     if (!skill) return;
 
     // 乐观更新
     setSkill((prev) => (prev ? { ...prev, enabled } : null));
 
     try {
-      await toggleSkillApi(skill.name, enabled, currentWorkspace?.path ?? null);
+      await toggleSkillApi(skill.name, enabled, currentWorkspace?.path ?? null, force);
       // 记录活动
       logActivity(
         'skill_toggle',
@@ -376,6 +396,11 @@ export default function SkillDetail() {
     } catch (err) {
       // 回滚
       setSkill((prev) => (prev ? { ...prev, enabled: !enabled } : null));
+      const dependents = parseDependentsWarning(err);
+      if (dependents) {
+        setDependentsWarning({ skillName: skill.name, action: 'disable', dependents });
+        return;
+      }
       console.error('切换 Skill 状态失败:', err);
     }
   };
@@ -433,17 +458,129 @@ export default function SkillDetail() {
     }
   };
 
-  const handleUpdateSkill = async () => {
+  // 修改 frontmatter
+  const handleEditFrontmatter = async (update: SkillFrontmatterUpdate) => {
+    if (!skill) return;
+
+    setSavingFrontmatter(true);
+    try {
+      const result = await updateSkillFrontmatter(skill.name, update, currentWorkspace?.path ?? null);
+      toast.success(result);
+      await loadSkill();
+    } catch (err) {
+      const message = err instanceof Error ? err.message : '保存失败';
+      toast.error(message);
+    } finally {
+      setSavingFrontmatter(false);
+    }
+  };
+
+  // 检查规范
+  const handleLint = async () => {
+    if (!skill) return;
+    setLinting(true);
+    try {
+      const report = await lintSkill(skill.name, currentWorkspace?.path ?? null);
+      setLintReport(report);
+      if (report.diagnostics.length === 0) {
+        toast.success('检查通过', { description: '未发现问题' });
+      }
+    } catch (err) {
+      const message = err instanceof Error ? err.message : '检查失败';
+      toast.error('检查失败', { description: message });
+    } finally {
+      setLinting(false);
+    }
+  };
+
+  const handleExport = async () => {
+    if (!skill) return;
+    const outputPath = await save({
+      title: '导出 Skill',
+      defaultPath: `${skill.name}.zip`,
+      filters: [{ name: 'Skill 归档', extensions: ['zip', 'tar.gz', 'tgz'] }],
+    });
+    if (!outputPath) return;
+
+    setExporting(true);
+    try {
+      const result = await exportSkills([skill.name], outputPath, currentWorkspace?.path ?? null);
+      toast.success('导出成功', { description: `${result.outputPath}（${result.fileCount} 个文件）` });
+    } catch (err) {
+      const message = typeof err === 'string' ? err : (err instanceof Error ? err.message : '导出失败');
+      toast.error('导出失败', { description: message });
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // 重新验证签名（本地修改过的文件会导致验证失败）
+  const handleVerifySignature = async () => {
+    if (!skill) return;
+    setVerifyingSignature(true);
+    try {
+      const signature = await verifySkillSignature(skill.name, currentWorkspace?.path ?? null);
+      setSkill({ ...skill, metadata: { ...skill.metadata, signature } });
+      const label = signatureStatusLabels[signature.status];
+      if (signature.status === 'verified') {
+        toast.success(`签名有效（${signature.publisher}）`);
+      } else if (signature.status === 'invalid') {
+        toast.error(label, { description: signature.message ?? undefined });
+      } else {
+        toast.warning(label, { description: signature.message ?? undefined });
+      }
+    } catch (err) {
+      const message = err instanceof Error ? err.message : '验证失败';
+      toast.error('验证签名失败', { description: message });
+    } finally {
+      setVerifyingSignature(false);
+    }
+  };
+
+  const handleUpdateSkill = async (onConflict?: SkillConflictStrategy, ignoreSecurity = false) => {
     if (!skill) return;
 
     setUpdatingSkill(true);
     try {
-      const message = await updateSkillApi(skill.name, currentWorkspace?.path ?? null);
-      toast.success('更新成功', { description: message });
+      const outcome = await updateSkillApi(
+        skill.name,
+        currentWorkspace?.path ?? null,
+        onConflict,
+        ignoreSecurity ? null : 'high'
+      );
+      if (!outcome.applied) {
+        toast.warning('更新与本地修改冲突', {
+          description: outcome.message,
+          duration: 15000,
+          action: {
+            label: '保留本地修改',
+            onClick: () => handleUpdateSkill('keep-local', ignoreSecurity),
+          },
+          cancel: {
+            label: '使用上游版本',
+            onClick: () => handleUpdateSkill('take-upstream', ignoreSecurity),
+          },
+        });
+        return;
+      }
+      toast.success('更新成功', { description: outcome.message });
       await loadSkill();
       const result = await checkSkillUpdate(skill.name, currentWorkspace?.path ?? null);
       setUpdateCheck(result);
     } catch (err) {
+      const blocked = parseSecurityBlocked(err);
+      if (blocked) {
+        const findings = blocked.flatMap(r => r.findings);
+        toast.warning('安全扫描发现风险，已取消更新', {
+          description: findings.slice(0, 3).map(f => `${formatFindingLocation(f)} ${f.message}`).join('\n'),
+          duration: 15000,
+          action: {
+            label: '仍然更新',
+            onClick: () => handleUpdateSkill(onConflict, true),
+          },
+        });
+        return;
+      }
       const message = err instanceof Error ? err.message : '更新失败';
       toast.error('更新失败', { description: message });
     } finally {
@@ -535,6 +672,22 @@ export default function SkillDetail() {
     : '当前只存在于本工具目录';
   const canManageDistribution = Boolean(skill.name);
 
+  // SKILL.md 中除名称、描述、标签外的 frontmatter 字段
+  const metadata = skill.metadata;
+  const frontmatterFields: [string, string][] = [];
+  if (metadata.allowedTools?.length) frontmatterFields.push(['允许的工具', metadata.allowedTools.join(', ')]);
+  if (metadata.userInvocable === false) frontmatterFields.push(['手动调用', '不可手动调用']);
+  if (metadata.disableModelInvocation) frontmatterFields.push(['自动调用', '仅手动调用']);
+  if (metadata.argumentHint) frontmatterFields.push(['参数提示', metadata.argumentHint]);
+  if (metadata.model) frontmatterFields.push(['模型', metadata.model]);
+  if (metadata.license) frontmatterFields.push(['许可证', metadata.license]);
+  if (metadata.requires?.length) {
+    frontmatterFields.push(['依赖', metadata.requires.map((r) => (r.repo ? `${r.name} (${r.repo})` : r.name)).join(', ')]);
+  }
+  for (const [key, value] of Object.entries(metadata.extraFrontmatter ?? {})) {
+    frontmatterFields.push([key, typeof value === 'string' ? value : JSON.stringify(value)]);
+  }
+
   return (
     <div className="space-y-6 animate-in fade-in duration-300">
       <div className="flex flex-col gap-3 lg:flex-row lg:items-center lg:justify-between">
@@ -552,10 +705,31 @@ export default function SkillDetail() {
               {checkingUpdate ? '检查中...' : updateCheck?.hasUpdate ? '发现更新' : updateCheck ? '已检查' : '检查更新'}
             </span>
           </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => setShowEditDialog(true)}
+            disabled={savingFrontmatter}
+          >
+            {savingFrontmatter ? <Loader2 className="h-4 w-4 animate-spin" /> : null}
+            <span className={savingFrontmatter ? 'ml-2' : ''}>编辑信息</span>
+          </Button>
+          <Button variant="outline" size="sm" onClick={handleLint} disabled={linting}>
+            {linting ? <Loader2 className="h-4 w-4 animate-spin" /> : <ShieldCheck className="h-4 w-4" />}
+            <span className="ml-2">{linting ? '检查中...' : '检查规范'}</span>
+          </Button>
+          <Button variant="outline" size="sm" onClick={handleVerifySignature} disabled={verifyingSignature}>
+            {verifyingSignature ? <Loader2 className="h-4 w-4 animate-spin" /> : <KeyRound className="h-4 w-4" />}
+            <span className="ml-2">{verifyingSignature ? '验证中...' : '验证签名'}</span>
+          </Button>
+          <Button variant="outline" size="sm" onClick={handleExport} disabled={exporting}>
+            {exporting ? <Loader2 className="h-4 w-4 animate-spin" /> : <Download className="h-4 w-4" />}
+            <span className="ml-2">{exporting ? '导出中...' : '导出'}</span>
+          </Button>
           {updateCheck?.hasUpdate && (
             <Button
               size="sm"
-              onClick={handleUpdateSkill}
+              onClick={() => handleUpdateSkill()}
               disabled={updatingSkill}
             >
               {updatingSkill ? <Loader2 className="h-4 w-4 animate-spin" /> : <RefreshCw className="h-4 w-4" />}
@@ -591,6 +765,7 @@ export default function SkillDetail() {
                   {!isCobaltManaged && <Badge variant="outline">来源未知</Badge>}
                   {skill.metadata.sourceId && <Badge variant="secondary">来自 Skill 市场</Badge>}
                   {skill.metadata.version && <Badge variant="outline">v{skill.metadata.version}</Badge>}
+                  {skill.metadata.signature && <SignatureBadge signature={skill.metadata.signature} />}
                 </div>
                 <div>
                   <h1 className="text-3xl font-semibold tracking-[-0.04em]">{displayName}</h1>
@@ -634,11 +809,20 @@ export default function SkillDetail() {
                 <div className="panel-muted px-4 py-3">
                   <p className="text-[11px] uppercase tracking-[0.18em] text-muted-foreground/70">来源</p>
                   <p className="mt-2 text-sm font-medium">
-                    {skill.metadata.sourceId ? '来自 Skill 市场' : skill.metadata.repository ? '来自仓库地址' : '来源未知'}
+                    {skill.metadata.sourceId
+                      ? '来自 Skill 市场'
+                      : skill.metadata.repository?.startsWith('file://')
+                        ? '来自本地目录 / 归档'
+                        : skill.metadata.repository ? '来自仓库地址' : '来源未知'}
                   </p>
                   <p className="mt-1 text-xs text-muted-foreground">
                     {skill.metadata.sourceId || skill.metadata.repository || '可能是手动放入 skills 目录'}
                   </p>
+                  {skill.metadata.upstreamName && (
+                    <p className="mt-1 text-xs text-muted-foreground">
+                      以别名安装，上游名称 <span className="font-mono">{skill.metadata.upstreamName}</span>
+                    </p>
+                  )}
                 </div>
 
                 <div className="panel-muted px-4 py-3">
@@ -646,8 +830,8 @@ export default function SkillDetail() {
                   <p className="mt-2 text-sm font-medium">{distributionSummary}</p>
                   <div className="mt-2 flex flex-wrap gap-1.5">
                     {installedTools.length > 0 ? installedTools.map((toolId) => {
-                      const tool = AI_TOOL_META[toolId as AiToolType];
-                      return tool ? <Badge key={toolId} variant="outline">{tool.displayName}</Badge> : null;
+                      const tool = getAiToolMeta(toolId);
+                      return <Badge key={toolId} variant="outline">{tool.displayName}</Badge>;
                     }) : <span className="text-xs text-muted-foreground">暂无额外工具</span>}
                   </div>
                 </div>
@@ -660,9 +844,49 @@ export default function SkillDetail() {
                   ))}
                 </div>
               )}
+
+              {frontmatterFields.length > 0 && (
+                <div className="grid gap-2 sm:grid-cols-2">
+                  {frontmatterFields.map(([label, value]) => (
+                    <div key={label} className="flex items-baseline gap-3 text-sm">
+                      <span className="shrink-0 text-xs text-muted-foreground">{label}</span>
+                      <span className="break-all font-mono text-xs">{value}</span>
+                    </div>
+                  ))}
+                </div>
+              )}
             </CardContent>
           </Card>
 
+          {lintReport && lintReport.diagnostics.length > 0 && (
+            <Card>
+              <CardHeader className="pb-3">
+                <CardTitle className="text-base">规范检查</CardTitle>
+                <CardDescription className="text-xs">
+                  {lintReport.errorCount} 个错误，{lintReport.warningCount} 个警告
+                </CardDescription>
+              </CardHeader>
+              <CardContent className="space-y-2 pt-0">
+                {lintReport.diagnostics.map((diagnostic, index) => (
+                  <button
+                    key={`${diagnostic.code}-${index}`}
+                    type="button"
+                    className="flex w-full items-start gap-3 rounded-md px-2 py-1.5 text-left text-sm hover:bg-accent"
+                    onClick={() => skill.files?.includes(diagnostic.file) && setSelectedFile(diagnostic.file)}
+                  >
+                    <Badge variant={diagnostic.severity === 'error' ? 'destructive' : 'outline'} className="shrink-0">
+                      {diagnostic.severity === 'error' ? '错误' : '警告'}
+                    </Badge>
+                    <span className="flex-1">{diagnostic.message}</span>
+                    <span className="shrink-0 font-mono text-xs text-muted-foreground">
+                      {diagnostic.file}{diagnostic.line ? `:${diagnostic.line}` : ''}
+                    </span>
+                  </button>
+                ))}
+              </CardContent>
+            </Card>
+          )}
+
           <div className="grid grid-cols-1 gap-6 lg:grid-cols-[240px_minmax(0,1fr)]">
         {/* 左侧文件目录 */}
             {skill.files && skill.files.length > 0 && (
@@ -795,6 +1019,26 @@ export default function SkillDetail() {
           skillName={skill.name}
         />
       )}
+
+      {/* 编辑 frontmatter 对话框 */}
+      {skill && (
+        <EditFrontmatterDialog
+          open={showEditDialog}
+          onOpenChange={setShowEditDialog}
+          onConfirm={handleEditFrontmatter}
+          metadata={skill.metadata}
+          skillName={skill.name}
+        />
+      )}
+
+      <DependentsWarningDialog
+        warning={dependentsWarning}
+        onCancel={() => setDependentsWarning(null)}
+        onConfirm={() => {
+          setDependentsWarning(null);
+          handleToggle(false, true);
+        }}
+      />
     </div>
   );
 }
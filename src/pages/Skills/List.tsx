@@ -1,7 +1,8 @@
 import { useEffect, useState, useCallback } from 'react';
 import { toast } from 'sonner';
 import { useAtom, useAtomValue, useSetAtom } from 'jotai';
-import { Search, Filter, RefreshCw, Plus, Loader2, CheckCircle2 } from 'lucide-react';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { Search, Filter, RefreshCw, Plus, Loader2, CheckCircle2, FolderOpen, FileArchive, Download, Layers } from 'lucide-react';
 import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
 import {
@@ -18,6 +19,13 @@ import { Checkbox } from '@/components/ui/checkbox';
 import { cn } from '@/lib/utils';
 import SkillCard from '@/components/common/SkillCard';
 import { GitAuthDialog } from '@/components/skills/GitAuthDialog';
+import { SecurityFindings, formatFindingLocation, SecuritySeverityBadge, securitySeverityLabels } from '@/components/skills/SecurityFindings';
+import { SignatureBadge } from '@/components/skills/SignatureBadge';
+import { SkillBundlesDialog } from '@/components/skills/SkillBundlesDialog';
+import { DependentsWarningDialog, type DependentsWarning } from '@/components/skills/DependentsWarningDialog';
+import { SkillConflictDialog } from '@/components/skills/SkillConflictDialog';
+import { OperationProgressBar } from '@/components/common/OperationProgressBar';
+import { useOperationProgress } from '@/hooks/useOperationProgress';
 import {
   skillsListAtom,
   filteredSkillsAtom,
@@ -39,13 +47,31 @@ import {
   toggleSkill as toggleSkillApi,
   scanRepoSkills,
   installSkillFromRepo,
+  scanLocalSkills,
+  installSkillFromLocal,
+  exportSkills,
   uninstallSkill,
+  parseDependentsWarning,
   updateSkill as updateSkillApi,
   parseGitAuthChallenge,
+  parseSecurityBlocked,
+  parseSkillConflict,
   checkAllSkillUpdates,
   checkSkillUpdate,
+  getSupportedAiTools,
 } from '@/services/skills';
-import type { ScannedSkillInfo, GitAuthChallenge, GitAuthInput } from '@/types/skills';
+import { getDefaultTargetTools } from '@/types/skills';
+import type {
+  AiToolInfo,
+  ScannedSkillInfo,
+  GitAuthChallenge,
+  GitAuthInput,
+  SkillConflictStrategy,
+  SecuritySeverity,
+  SkillSecurityReport,
+  SkillCollision,
+  SkillCollisionResolutions,
+} from '@/types/skills';
 import type { AiToolType } from '@/types/skills';
 
 export default function SkillsList() {
@@ -62,19 +88,56 @@ export default function SkillsList() {
 
   // 安装对话框状态
   const [installDialogOpen, setInstallDialogOpen] = useState(false);
+  const [installSource, setInstallSource] = useState<'repo' | 'local'>('repo');
   const [repoUrl, setRepoUrl] = useState('');
+  const [repoRef, setRepoRef] = useState('');
+  const [localPath, setLocalPath] = useState('');
   const [scanning, setScanning] = useState(false);
   const [installing, setInstalling] = useState(false);
+  // 扫描和安装仓库时的克隆、安装进度
+  const { progress: operationProgress, track: trackOperation } = useOperationProgress();
   const [installError, setInstallError] = useState<string | null>(null);
   const [scannedSkills, setScannedSkills] = useState<ScannedSkillInfo[]>([]);
   const [selectedSkills, setSelectedSkills] = useState<Set<string>>(new Set());
   const [selectedTools, setSelectedTools] = useState<Set<string>>(new Set(['claude-code']));
+  const [installTools, setInstallTools] = useState<AiToolInfo[]>([]);
+  const [linkMode, setLinkMode] = useState(false);
+  const [securityThreshold, setSecurityThreshold] = useState<SecuritySeverity | 'none'>('high');
+  const [blockedReports, setBlockedReports] = useState<SkillSecurityReport[] | null>(null);
   const [authDialogOpen, setAuthDialogOpen] = useState(false);
   const [authDialogLoading, setAuthDialogLoading] = useState(false);
   const [authChallenge, setAuthChallenge] = useState<GitAuthChallenge | null>(null);
   const [authAction, setAuthAction] = useState<'scan' | 'install' | null>(null);
   const [updatingSkillName, setUpdatingSkillName] = useState<string | null>(null);
 
+  // 导出对话框状态
+  const [exportDialogOpen, setExportDialogOpen] = useState(false);
+  const [exportSelection, setExportSelection] = useState<Set<string>>(new Set());
+  const [exportFormat, setExportFormat] = useState<'zip' | 'tar.gz'>('zip');
+  const [exporting, setExporting] = useState(false);
+  const [bundlesDialogOpen, setBundlesDialogOpen] = useState(false);
+  // 卸载或禁用被其他 Skill 依赖的 Skill 时的确认
+  const [dependentsWarning, setDependentsWarning] = useState<DependentsWarning | null>(null);
+  // 与其他来源的 Skill 重名时待选择的处理方式，以及选择后重试安装所需的参数
+  const [skillConflicts, setSkillConflicts] = useState<{
+    conflicts: SkillCollision[];
+    gitAuth?: GitAuthInput;
+    ignoreSecurity: boolean;
+  } | null>(null);
+  // 已选择的重名处理方式，认证或忽略安全扫描后重试安装时沿用
+  const [collisionResolutions, setCollisionResolutions] = useState<SkillCollisionResolutions | null>(null);
+
+  // 打开安装对话框时检测本机已安装的工具，并默认选中它们
+  useEffect(() => {
+    if (!installDialogOpen) return;
+    getSupportedAiTools()
+      .then(tools => {
+        setInstallTools(tools);
+        setSelectedTools(new Set(getDefaultTargetTools(tools)));
+      })
+      .catch(err => console.error('加载工具信息失败:', err));
+  }, [installDialogOpen]);
+
   // 加载 Skills 数据
   const loadSkills = useCallback(async () => {
     setLoading(true);
@@ -114,7 +177,7 @@ export default function SkillsList() {
     };
   }, [loadSkills]);
 
-  const handleToggleSkill = async (skillId: string, enabled: boolean) => {
+  const handleToggleSkill = async (skillId: string, enabled: boolean, force = false) => {
     const skill = skills.find((s) => s.id === skillId);
     if (!skill) return;
 
@@ -124,12 +187,17 @@ export default function SkillsList() {
     );
 
     try {
-      await toggleSkillApi(skill.name, enabled, currentWorkspace?.path ?? null);
+      await toggleSkillApi(skill.name, enabled, currentWorkspace?.path ?? null, force);
     } catch (err) {
       // 回滚
       setSkills((prev) =>
         prev.map((s) => (s.id === skillId ? { ...s, enabled: !enabled } : s))
       );
+      const dependents = parseDependentsWarning(err);
+      if (dependents) {
+        setDependentsWarning({ skillName: skill.name, action: 'disable', dependents });
+        return;
+      }
       console.error('切换 Skill 状态失败:', err);
     }
   };
@@ -138,18 +206,28 @@ export default function SkillsList() {
     typeof err === 'string' ? err : (err instanceof Error ? err.message : fallback);
 
   const handleScanRepo = async (gitAuth?: GitAuthInput) => {
-    if (!repoUrl.trim()) {
+    if (installSource === 'repo' && !repoUrl.trim()) {
       setInstallError('请输入仓库 URL');
       return;
     }
+    if (installSource === 'local' && !localPath.trim()) {
+      setInstallError('请选择本地目录或归档文件');
+      return;
+    }
 
     setScanning(true);
     setInstallError(null);
+    setBlockedReports(null);
+    setCollisionResolutions(null);
     setScannedSkills([]);
     setSelectedSkills(new Set());
 
     try {
-      const skills = await scanRepoSkills(repoUrl, gitAuth);
+      const skills = installSource === 'local'
+        ? await scanLocalSkills(localPath.trim())
+        : await trackOperation((operationId) =>
+          scanRepoSkills(repoUrl, gitAuth, repoRef.trim() || null, operationId)
+        );
       setScannedSkills(skills);
       setAuthDialogOpen(false);
       setAuthChallenge(null);
@@ -176,7 +254,13 @@ export default function SkillsList() {
     }
   };
 
-  const handleInstallSkill = async (gitAuth?: GitAuthInput) => {
+  // ignoreSecurity: 用户确认风险后跳过安全扫描拦截
+  // collisions: 与其他来源的 Skill 重名时的处理方式
+  const handleInstallSkill = async (
+    gitAuth?: GitAuthInput,
+    ignoreSecurity = false,
+    collisions: SkillCollisionResolutions | null = collisionResolutions
+  ) => {
     if (selectedSkills.size === 0) {
       setInstallError('请至少选择一个 Skill');
       return;
@@ -189,21 +273,41 @@ export default function SkillsList() {
 
     setInstalling(true);
     setInstallError(null);
+    setBlockedReports(null);
 
     try {
-      const result = await installSkillFromRepo(
-        repoUrl,
-        Array.from(selectedSkills),
-        Array.from(selectedTools),
-        currentWorkspace?.path ?? null,
-        gitAuth
-      );
+      const threshold = ignoreSecurity || securityThreshold === 'none' ? null : securityThreshold;
+      const result = installSource === 'local'
+        ? await installSkillFromLocal(
+          localPath.trim(),
+          Array.from(selectedSkills),
+          Array.from(selectedTools),
+          currentWorkspace?.path ?? null,
+          linkMode ? 'link' : 'copy',
+          threshold,
+          collisions
+        )
+        : await trackOperation((operationId) => installSkillFromRepo(
+          repoUrl,
+          Array.from(selectedSkills),
+          Array.from(selectedTools),
+          currentWorkspace?.path ?? null,
+          gitAuth,
+          repoRef.trim() || null,
+          linkMode ? 'link' : 'copy',
+          threshold,
+          collisions,
+          operationId
+        ));
       toast.success('安装成功', { description: result });
+      setCollisionResolutions(null);
       setAuthDialogOpen(false);
       setAuthChallenge(null);
       setAuthAction(null);
       setInstallDialogOpen(false);
       setRepoUrl('');
+      setRepoRef('');
+      setLocalPath('');
       setScannedSkills([]);
       setSelectedSkills(new Set());
       setSelectedTools(new Set(['claude-code']));
@@ -219,12 +323,77 @@ export default function SkillsList() {
         setInstallError(challenge.message);
         return;
       }
+      const conflicts = parseSkillConflict(err);
+      if (conflicts) {
+        setSkillConflicts({ conflicts, gitAuth, ignoreSecurity });
+        setInstallError(`已安装来自其他来源的同名 Skill：${conflicts.map(c => c.name).join(', ')}`);
+        return;
+      }
+      const blocked = parseSecurityBlocked(err);
+      if (blocked) {
+        setBlockedReports(blocked);
+        setInstallError(
+          `安全扫描发现风险，已取消安装：${blocked.map(r => r.skillName).join(', ')}\n确认脚本可信后可以选择仍然安装`
+        );
+        return;
+      }
       setInstallError(extractErrorMessage(err, '安装失败'));
     } finally {
       setInstalling(false);
     }
   };
 
+  const toggleExportSelection = (skillName: string) => {
+    setExportSelection(prev => {
+      const next = new Set(prev);
+      if (next.has(skillName)) {
+        next.delete(skillName);
+      } else {
+        next.add(skillName);
+      }
+      return next;
+    });
+  };
+
+  const handleExportSkills = async () => {
+    const names = Array.from(exportSelection);
+    if (names.length === 0) return;
+
+    const outputPath = await save({
+      title: '导出 Skills',
+      defaultPath: `${names.length === 1 ? names[0] : 'skills'}.${exportFormat}`,
+      filters: [{ name: 'Skill 归档', extensions: exportFormat === 'zip' ? ['zip'] : ['tar.gz', 'tgz'] }],
+    });
+    if (!outputPath) return;
+
+    setExporting(true);
+    try {
+      const result = await exportSkills(names, outputPath, currentWorkspace?.path ?? null);
+      toast.success(`已导出 ${result.skills.length} 个 Skill`, { description: result.outputPath });
+      setExportDialogOpen(false);
+      setExportSelection(new Set());
+    } catch (err) {
+      toast.error('导出失败', { description: extractErrorMessage(err, '导出失败') });
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const handlePickLocalSource = async (directory: boolean) => {
+    const selected = await open({
+      directory,
+      multiple: false,
+      title: directory ? '选择 Skill 目录' : '选择 Skill 归档',
+      filters: directory ? undefined : [{ name: 'Skill 归档', extensions: ['zip', 'gz', 'tgz', 'tar'] }],
+    });
+    if (typeof selected === 'string') {
+      setLocalPath(selected);
+      setScannedSkills([]);
+      setSelectedSkills(new Set());
+      setInstallError(null);
+    }
+  };
+
   const handleAuthConfirm = async (auth: GitAuthInput) => {
     if (!authAction) return;
     setAuthDialogLoading(true);
@@ -263,9 +432,9 @@ export default function SkillsList() {
     });
   };
 
-  const handleDeleteSkill = async (skillName: string) => {
+  const handleDeleteSkill = async (skillName: string, force = false) => {
     try {
-      await uninstallSkill(skillName, currentWorkspace?.path ?? null);
+      await uninstallSkill(skillName, currentWorkspace?.path ?? null, force);
       setSkills((prev) => prev.filter((s) => s.name !== skillName));
       setSkillsOrder((prev) => {
         const next = { ...prev };
@@ -279,16 +448,41 @@ export default function SkillsList() {
       });
       toast.success(`Skill "${skillName}" 已删除`);
     } catch (err) {
+      const dependents = parseDependentsWarning(err);
+      if (dependents) {
+        setDependentsWarning({ skillName, action: 'uninstall', dependents });
+        return;
+      }
       console.error('删除 Skill 失败:', err);
       toast.error('删除失败', { description: err instanceof Error ? err.message : '未知错误' });
     }
   };
 
-  const handleUpdateSkill = async (skillName: string) => {
+  const handleUpdateSkill = async (
+    skillName: string,
+    onConflict?: SkillConflictStrategy,
+    ignoreSecurity = false
+  ) => {
     setUpdatingSkillName(skillName);
     try {
-      const result = await updateSkillApi(skillName, currentWorkspace?.path ?? null);
-      toast.success('更新成功', { description: result });
+      const threshold = ignoreSecurity || securityThreshold === 'none' ? null : securityThreshold;
+      const result = await updateSkillApi(skillName, currentWorkspace?.path ?? null, onConflict, threshold);
+      if (!result.applied) {
+        toast.warning('更新与本地修改冲突', {
+          description: result.message,
+          duration: 15000,
+          action: {
+            label: '保留本地修改',
+            onClick: () => handleUpdateSkill(skillName, 'keep-local', ignoreSecurity),
+          },
+          cancel: {
+            label: '使用上游版本',
+            onClick: () => handleUpdateSkill(skillName, 'take-upstream', ignoreSecurity),
+          },
+        });
+        return;
+      }
+      toast.success('更新成功', { description: result.message });
       const nextSkills = await loadSkills();
       const recheck = await checkSkillUpdate(skillName, currentWorkspace?.path ?? null);
       const checkedAt = new Date().toISOString();
@@ -307,6 +501,7 @@ export default function SkillsList() {
             newFiles: recheck.newFiles ?? [],
             removedFiles: recheck.removedFiles ?? [],
             outdatedTools: recheck.outdatedTools ?? [],
+            localModifiedFiles: recheck.localModifiedFiles ?? [],
             error: recheck.error,
           },
         };
@@ -314,6 +509,19 @@ export default function SkillsList() {
         return nextUpdates;
       });
     } catch (err) {
+      const blocked = parseSecurityBlocked(err);
+      if (blocked) {
+        const findings = blocked.flatMap(r => r.findings);
+        toast.warning('安全扫描发现风险，已取消更新', {
+          description: findings.slice(0, 3).map(f => `${formatFindingLocation(f)} ${f.message}`).join('\n'),
+          duration: 15000,
+          action: {
+            label: '仍然更新',
+            onClick: () => handleUpdateSkill(skillName, onConflict, true),
+          },
+        });
+        return;
+      }
       console.error('更新 Skill 失败:', err);
       toast.error('更新失败', {
         description: err instanceof Error ? err.message : '未知错误',
@@ -338,7 +546,10 @@ export default function SkillsList() {
 
     setCheckingAllUpdates(true);
     try {
-      const results = await checkAllSkillUpdates(currentWorkspace?.path ?? null, !silent);
+      // 每个 Skill 检查完成后立即显示结果，不必等待全部仓库
+      const results = await checkAllSkillUpdates(currentWorkspace?.path ?? null, !silent, {
+        onChecked: (summary) => setSkillUpdates((prev) => ({ ...prev, [summary.skillName]: summary })),
+      });
       const nextUpdates = Object.fromEntries(results.map((result) => [result.skillName, result]));
       setSkillUpdates(nextUpdates);
       setSkillsOrder(buildSkillsOrder(skills, nextUpdates));
@@ -371,6 +582,19 @@ export default function SkillsList() {
     { value: 'antigravity', label: 'Antigravity', icon: '🚀' },
   ] as const;
 
+  const securityThresholds: { value: SecuritySeverity | 'none'; label: string }[] = [
+    { value: 'none', label: '不拦截' },
+    { value: 'medium', label: `${securitySeverityLabels.medium}及以上` },
+    { value: 'high', label: `${securitySeverityLabels.high}及以上` },
+    { value: 'critical', label: `仅${securitySeverityLabels.critical}` },
+  ];
+
+  const invocationFilters = [
+    { value: 'all', label: '全部' },
+    { value: 'user', label: '可手动调用' },
+    { value: 'model', label: '可自动调用' },
+  ] as const;
+
   const updatableCount = Object.values(skillUpdates).filter((result) => result.hasUpdate).length;
   const allSkillsChecked = skills.length > 0 && skills.every((skill) => Boolean(skillUpdates[skill.name]));
 
@@ -417,51 +641,165 @@ export default function SkillsList() {
                 </DialogTrigger>
                 <DialogContent className="max-h-[80vh] max-w-2xl overflow-y-auto">
                   <DialogHeader>
-                    <DialogTitle>从仓库安装 Skill</DialogTitle>
+                    <DialogTitle>安装 Skill</DialogTitle>
                     <DialogDescription>
-                      输入 Git 仓库 URL，扫描并选择要安装的 Skills
+                      {installSource === 'local'
+                        ? '选择本地目录或 .zip / .tar.gz 归档，扫描并选择要安装的 Skills'
+                        : '输入 Git 仓库 URL，扫描并选择要安装的 Skills'}
                     </DialogDescription>
                   </DialogHeader>
                   <div className="space-y-4">
-                    {/* 仓库 URL 输入 */}
-                    <div className="space-y-2">
-                      <Label htmlFor="repo-url">仓库 URL</Label>
-                      <div className="flex gap-2">
+                    {/* 安装来源 */}
+                    <div className="flex flex-wrap gap-2">
+                      {([['repo', 'Git 仓库'], ['local', '本地目录 / 归档']] as const).map(([value, label]) => (
+                        <Badge
+                          key={value}
+                          variant={installSource === value ? 'default' : 'outline'}
+                          className="cursor-pointer"
+                          onClick={() => {
+                            if (scanning || installing) return;
+                            setInstallSource(value);
+                            setScannedSkills([]);
+                            setSelectedSkills(new Set());
+                            setInstallError(null);
+                            setBlockedReports(null);
+                          }}
+                        >
+                          {label}
+                        </Badge>
+                      ))}
+                    </div>
+
+                    {installSource === 'local' ? (
+                      <div className="space-y-2">
+                        <Label htmlFor="local-path">本地路径</Label>
+                        <div className="flex gap-2">
+                          <Input
+                            id="local-path"
+                            placeholder="/path/to/my-skill 或 my-skill.zip"
+                            value={localPath}
+                            onChange={(e) => {
+                              setLocalPath(e.target.value);
+                              setScannedSkills([]);
+                              setSelectedSkills(new Set());
+                            }}
+                            onKeyDown={(e) => {
+                              if (e.key === 'Enter' && !scanning && scannedSkills.length === 0) {
+                                handleScanRepo();
+                              }
+                            }}
+                            disabled={scanning || installing}
+                          />
+                          <Button
+                            variant="outline"
+                            size="icon"
+                            title="选择目录"
+                            onClick={() => handlePickLocalSource(true)}
+                            disabled={scanning || installing}
+                          >
+                            <FolderOpen className="h-4 w-4" />
+                          </Button>
+                          <Button
+                            variant="outline"
+                            size="icon"
+                            title="选择归档"
+                            onClick={() => handlePickLocalSource(false)}
+                            disabled={scanning || installing}
+                          >
+                            <FileArchive className="h-4 w-4" />
+                          </Button>
+                          <Button
+                            onClick={() => handleScanRepo()}
+                            disabled={scanning || installing || !localPath.trim()}
+                          >
+                            {scanning ? (
+                              <>
+                                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                                扫描中...
+                              </>
+                            ) : (
+                              '扫描'
+                            )}
+                          </Button>
+                        </div>
+                        <p className="text-xs text-muted-foreground">
+                          支持目录和 .zip、.tar.gz、.tgz、.tar 归档；检查更新时会与该路径的当前内容对比
+                        </p>
+                      </div>
+                    ) : (
+                      <>
+                      {/* 仓库 URL 输入 */}
+                      <div className="space-y-2">
+                        <Label htmlFor="repo-url">仓库 URL</Label>
+                        <div className="flex gap-2">
+                          <Input
+                            id="repo-url"
+                            placeholder="https://github.com/username/skill-name"
+                            value={repoUrl}
+                            onChange={(e) => setRepoUrl(e.target.value)}
+                            onKeyDown={(e) => {
+                              if (e.key === 'Enter' && !scanning && scannedSkills.length === 0) {
+                                handleScanRepo();
+                              }
+                            }}
+                            disabled={scanning || installing}
+                          />
+                          <Button
+                            onClick={() => handleScanRepo()}
+                            disabled={scanning || installing || !repoUrl.trim()}
+                          >
+                            {scanning ? (
+                              <>
+                                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                                扫描中...
+                              </>
+                            ) : (
+                              '扫描'
+                            )}
+                          </Button>
+                        </div>
+                        <p className="text-xs text-muted-foreground">
+                          支持 GitHub、GitLab 等公开 Git 仓库（私有仓库需先配置 Git 凭据）
+                        </p>
+                      </div>
+
+                      {/* 分支 / 标签 / 提交 */}
+                      <div className="space-y-2">
+                        <Label htmlFor="repo-ref">分支 / 标签 / 提交（可选）</Label>
                         <Input
-                          id="repo-url"
-                          placeholder="https://github.com/username/skill-name"
-                          value={repoUrl}
-                          onChange={(e) => setRepoUrl(e.target.value)}
-                          onKeyDown={(e) => {
-                            if (e.key === 'Enter' && !scanning && scannedSkills.length === 0) {
-                              handleScanRepo();
-                            }
+                          id="repo-ref"
+                          placeholder="默认分支，例如 v1.2.0 或 main"
+                          value={repoRef}
+                          onChange={(e) => {
+                            setRepoRef(e.target.value);
+                            setScannedSkills([]);
+                            setSelectedSkills(new Set());
                           }}
                           disabled={scanning || installing}
                         />
-                        <Button
-                          onClick={() => handleScanRepo()}
-                          disabled={scanning || installing || !repoUrl.trim()}
-                        >
-                          {scanning ? (
-                            <>
-                              <Loader2 className="mr-2 h-4 w-4 animate-spin" />
-                              扫描中...
-                            </>
-                          ) : (
-                            '扫描'
-                          )}
-                        </Button>
+                        <p className="text-xs text-muted-foreground">
+                          指定后将固定在该版本，检查更新时也只跟踪该分支或标签
+                        </p>
                       </div>
-                      <p className="text-xs text-muted-foreground">
-                        支持 GitHub、GitLab 等公开 Git 仓库（私有仓库需先配置 Git 凭据）
-                      </p>
-                    </div>
+                      </>
+                    )}
 
                     {/* 错误提示 - 放在 URL 输入框下方，更醒目 */}
                     {installError && (
                       <div className="rounded-lg border border-destructive/50 bg-destructive/10 p-3">
                         <p className="text-sm text-destructive whitespace-pre-line">{installError}</p>
+                        {blockedReports && (
+                          <div className="mt-2 flex justify-end">
+                            <Button
+                              variant="destructive"
+                              size="sm"
+                              onClick={() => handleInstallSkill(undefined, true)}
+                              disabled={installing}
+                            >
+                              仍然安装
+                            </Button>
+                          </div>
+                        )}
                       </div>
                     )}
 
@@ -534,12 +872,22 @@ export default function SkillsList() {
                                       v{skill.version}
                                     </Badge>
                                   )}
+                                  <SignatureBadge signature={skill.signature} />
+                                  {skill.security.maxSeverity && (
+                                    <SecuritySeverityBadge severity={skill.security.maxSeverity} />
+                                  )}
                                 </div>
                                 {skill.description && (
                                   <p className="mt-1 text-sm text-muted-foreground">
                                     {skill.description}
                                   </p>
                                 )}
+                                {skill.requires && skill.requires.length > 0 && (
+                                  <p className="mt-1 text-xs text-muted-foreground">
+                                    依赖（缺少时自动安装）: {skill.requires.map((r) => r.name).join(', ')}
+                                  </p>
+                                )}
+                                <SecurityFindings report={skill.security} limit={3} />
                               </div>
                             </div>
                           ))}
@@ -557,10 +905,7 @@ export default function SkillsList() {
                               variant="ghost"
                               size="sm"
                               onClick={() => {
-                                const allTools = toolFilters
-                                  .filter(t => t.value !== 'all')
-                                  .map(t => t.value);
-                                setSelectedTools(new Set(allTools));
+                                setSelectedTools(new Set(installTools.map(t => t.id)));
                               }}
                             >
                               全选
@@ -575,31 +920,32 @@ export default function SkillsList() {
                           </div>
                         </div>
                         <div className="grid grid-cols-2 gap-2">
-                          {toolFilters
-                            .filter(tool => tool.value !== 'all')
-                            .map((tool) => (
+                          {installTools.map((tool) => (
                               <div
-                                key={tool.value}
+                                key={tool.id}
                                 className={cn(
                                   'flex cursor-pointer items-center gap-3 rounded-lg border p-3 transition-colors',
-                                  selectedTools.has(tool.value)
+                                  selectedTools.has(tool.id)
                                     ? 'border-primary bg-primary/5'
                                     : 'hover:bg-muted/50'
                                 )}
-                                onClick={() => toggleToolSelection(tool.value)}
+                                onClick={() => toggleToolSelection(tool.id)}
                               >
                                 <Checkbox
-                                  id={`tool-${tool.value}`}
-                                  checked={selectedTools.has(tool.value)}
-                                  onCheckedChange={() => toggleToolSelection(tool.value)}
+                                  id={`tool-${tool.id}`}
+                                  checked={selectedTools.has(tool.id)}
+                                  onCheckedChange={() => toggleToolSelection(tool.id)}
                                   disabled={installing}
                                 />
                                 <Label
-                                  htmlFor={`tool-${tool.value}`}
-                                  className="flex flex-1 cursor-pointer items-center gap-2"
+                                  htmlFor={`tool-${tool.id}`}
+                                  className="flex min-w-0 flex-1 cursor-pointer items-center gap-2"
                                 >
                                   <span>{tool.icon}</span>
-                                  <span className="font-medium">{tool.label}</span>
+                                  <span className="truncate font-medium">{tool.displayName}</span>
+                                  {!tool.installed && (
+                                    <span className="shrink-0 text-xs font-normal text-muted-foreground">未检测到</span>
+                                  )}
                                 </Label>
                               </div>
                             ))}
@@ -607,19 +953,57 @@ export default function SkillsList() {
                         <p className="text-xs text-muted-foreground">
                           Skill 将被安装到选中工具的 skills 目录中
                         </p>
+                        <div className="flex items-start gap-2">
+                          <Checkbox
+                            id="install-link-mode"
+                            checked={linkMode}
+                            onCheckedChange={(checked) => setLinkMode(checked === true)}
+                            disabled={installing}
+                            className="mt-0.5"
+                          />
+                          <Label htmlFor="install-link-mode" className="cursor-pointer text-sm font-normal leading-5">
+                            链接模式
+                            <span className="block text-xs text-muted-foreground">
+                              只保存一份副本到 ~/.cobalt/skills/store，各工具目录使用符号链接，更新时所有工具同步生效
+                            </span>
+                          </Label>
+                        </div>
+                        <div className="space-y-2">
+                          <p className="text-sm">脚本安全拦截</p>
+                          <div className="flex flex-wrap gap-2">
+                            {securityThresholds.map((option) => (
+                              <Badge
+                                key={option.value}
+                                variant={securityThreshold === option.value ? 'default' : 'outline'}
+                                className="cursor-pointer"
+                                onClick={() => !installing && setSecurityThreshold(option.value)}
+                              >
+                                {option.label}
+                              </Badge>
+                            ))}
+                          </div>
+                          <p className="text-xs text-muted-foreground">
+                            安装前扫描 skill 中的脚本，发现所选级别及以上的风险时取消整次安装
+                          </p>
+                        </div>
                       </div>
                     )}
 
+                    {(scanning || installing) && <OperationProgressBar progress={operationProgress} />}
+
                     <div className="flex justify-end gap-2">
                       <Button
                         variant="outline"
                         onClick={() => {
                           setInstallDialogOpen(false);
                           setInstallError(null);
+                          setBlockedReports(null);
                           setScannedSkills([]);
                           setSelectedSkills(new Set());
                           setSelectedTools(new Set(['claude-code']));
                           setRepoUrl('');
+                          setRepoRef('');
+                          setLocalPath('');
                         }}
                         disabled={scanning || installing}
                       >
@@ -658,6 +1042,92 @@ export default function SkillsList() {
                 <RefreshCw className={`mr-2 h-4 w-4 ${checkingAllUpdates ? 'animate-spin' : ''}`} />
                 检查更新
               </Button>
+              <Dialog open={exportDialogOpen} onOpenChange={setExportDialogOpen}>
+                <DialogTrigger asChild>
+                  <Button variant="outline" size="sm" disabled={skills.length === 0} className="h-11 px-4">
+                    <Download className="mr-2 h-4 w-4" />
+                    导出
+                  </Button>
+                </DialogTrigger>
+                <DialogContent className="max-h-[80vh] max-w-lg overflow-y-auto">
+                  <DialogHeader>
+                    <DialogTitle>导出 Skills</DialogTitle>
+                    <DialogDescription>
+                      选中的 Skills 会打包到同一个归档中，队友可以通过"本地目录 / 归档"安装
+                    </DialogDescription>
+                  </DialogHeader>
+                  <div className="space-y-4">
+                    <div className="flex items-center justify-between">
+                      <Label>选择要导出的 Skills ({exportSelection.size}/{skills.length})</Label>
+                      <Button
+                        variant="ghost"
+                        size="sm"
+                        onClick={() =>
+                          setExportSelection(
+                            exportSelection.size === skills.length ? new Set() : new Set(skills.map(s => s.name))
+                          )
+                        }
+                      >
+                        {exportSelection.size === skills.length ? '取消全选' : '全选'}
+                      </Button>
+                    </div>
+                    <div className="max-h-64 space-y-1 overflow-y-auto rounded-lg border p-2">
+                      {skills.map((skill) => (
+                        <label
+                          key={skill.name}
+                          className="flex cursor-pointer items-center gap-2 rounded px-2 py-1.5 text-sm hover:bg-muted"
+                        >
+                          <Checkbox
+                            checked={exportSelection.has(skill.name)}
+                            onCheckedChange={() => toggleExportSelection(skill.name)}
+                          />
+                          <span className="truncate">{skill.name}</span>
+                        </label>
+                      ))}
+                    </div>
+                    <div className="space-y-2">
+                      <p className="text-sm">归档格式</p>
+                      <div className="flex gap-2">
+                        {(['zip', 'tar.gz'] as const).map((format) => (
+                          <Badge
+                            key={format}
+                            variant={exportFormat === format ? 'default' : 'outline'}
+                            className="cursor-pointer"
+                            onClick={() => !exporting && setExportFormat(format)}
+                          >
+                            .{format}
+                          </Badge>
+                        ))}
+                      </div>
+                    </div>
+                    <div className="flex justify-end gap-2">
+                      <Button variant="outline" onClick={() => setExportDialogOpen(false)} disabled={exporting}>
+                        取消
+                      </Button>
+                      <Button onClick={handleExportSkills} disabled={exporting || exportSelection.size === 0}>
+                        {exporting ? (
+                          <>
+                            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                            导出中...
+                          </>
+                        ) : (
+                          `导出 (${exportSelection.size})`
+                        )}
+                      </Button>
+                    </div>
+                  </div>
+                </DialogContent>
+              </Dialog>
+              <Button variant="outline" size="sm" onClick={() => setBundlesDialogOpen(true)} className="h-11 px-4">
+                <Layers className="mr-2 h-4 w-4" />
+                套件
+              </Button>
+              <SkillBundlesDialog
+                open={bundlesDialogOpen}
+                onOpenChange={setBundlesDialogOpen}
+                workspacePath={currentWorkspace?.path ?? null}
+                onChanged={loadSkills}
+              />
             </div>
             </div>
           </div>
@@ -682,6 +1152,11 @@ export default function SkillsList() {
                     仅更新
                   </Badge>
                 )}
+                {filter.invocation && filter.invocation !== 'all' && (
+                  <Badge variant="secondary" className="ml-2">
+                    {invocationFilters.find((option) => option.value === filter.invocation)?.label}
+                  </Badge>
+                )}
               </Button>
             </DialogTrigger>
             <DialogContent>
@@ -710,6 +1185,25 @@ export default function SkillsList() {
                     ))}
                   </div>
                 </div>
+                <div>
+                  <p className="mb-2 text-sm font-medium">调用方式</p>
+                  <div className="flex flex-wrap gap-2">
+                    {invocationFilters.map((option) => (
+                      <Badge
+                        key={option.value}
+                        variant={
+                          (filter.invocation ?? 'all') === option.value ? 'default' : 'outline'
+                        }
+                        className="cursor-pointer"
+                        onClick={() =>
+                          setFilter({ ...filter, invocation: option.value })
+                        }
+                      >
+                        {option.label}
+                      </Badge>
+                    ))}
+                  </div>
+                </div>
                 <div className="flex items-center justify-between rounded-lg border p-3">
                   <div>
                     <p className="text-sm font-medium">仅看可更新</p>
@@ -772,6 +1266,31 @@ export default function SkillsList() {
           ))}
         </div>
       )}
+
+      <DependentsWarningDialog
+        warning={dependentsWarning}
+        onCancel={() => setDependentsWarning(null)}
+        onConfirm={(warning) => {
+          setDependentsWarning(null);
+          const skill = skills.find((s) => s.name === warning.skillName);
+          if (warning.action === 'uninstall') {
+            handleDeleteSkill(warning.skillName, true);
+          } else if (skill) {
+            handleToggleSkill(skill.id, false, true);
+          }
+        }}
+      />
+
+      <SkillConflictDialog
+        conflicts={skillConflicts?.conflicts ?? null}
+        onCancel={() => setSkillConflicts(null)}
+        onConfirm={(resolutions) => {
+          const retry = skillConflicts;
+          setSkillConflicts(null);
+          setCollisionResolutions(resolutions);
+          handleInstallSkill(retry?.gitAuth, retry?.ignoreSecurity ?? false, resolutions);
+        }}
+      />
     </div>
   );
 }
@@ -22,12 +22,16 @@ import {
   refreshMarketplace,
   installSkillFromMarketplace,
 } from '@/services/marketplace';
-import { parseGitAuthChallenge } from '@/services/skills';
+import { parseGitAuthChallenge, parseSecurityBlocked, parseSkillConflict } from '@/services/skills';
 import { TargetToolsDialog } from '@/components/skills/TargetToolsDialog';
 import { GitAuthDialog } from '@/components/skills/GitAuthDialog';
 import { MarketplaceSkillPreviewDialog } from '@/components/skills/MarketplaceSkillPreviewDialog';
+import { SkillConflictDialog } from '@/components/skills/SkillConflictDialog';
+import { formatFindingLocation } from '@/components/skills/SecurityFindings';
+import { OperationProgressBar } from '@/components/common/OperationProgressBar';
+import { useOperationProgress } from '@/hooks/useOperationProgress';
 import type { CachedSkillInfo } from '@/types/marketplace';
-import type { GitAuthChallenge, GitAuthInput } from '@/types/skills';
+import type { GitAuthChallenge, GitAuthInput, SkillCollision, SkillCollisionResolutions } from '@/types/skills';
 
 export default function MarketplaceDetail() {
   const { sourceId } = useParams<{ sourceId: string }>();
@@ -39,6 +43,7 @@ export default function MarketplaceDetail() {
   const [loading, setLoading] = useState(false);
   const [refreshing, setRefreshing] = useState(false);
   const [installing, setInstalling] = useState(false);
+  const { progress: installProgress, track: trackInstall } = useOperationProgress();
   const [searchQuery, setSearchQuery] = useState('');
   const [filterStatus, setFilterStatus] = useState<'all' | 'installed' | 'not-installed' | 'has-update'>('all');
   const [selectedSkills, setSelectedSkills] = useState<Set<string>>(new Set());
@@ -49,6 +54,13 @@ export default function MarketplaceDetail() {
   const [authDialogOpen, setAuthDialogOpen] = useState(false);
   const [authChallenge, setAuthChallenge] = useState<GitAuthChallenge | null>(null);
   const [authDialogLoading, setAuthDialogLoading] = useState(false);
+  // 与其他来源的 Skill 重名时待选择的处理方式，以及选择后重试安装所需的参数
+  const [skillConflicts, setSkillConflicts] = useState<{
+    conflicts: SkillCollision[];
+    targetTools: string[];
+    gitAuth?: GitAuthInput;
+    ignoreSecurity: boolean;
+  } | null>(null);
 
   // 预览对话框状态
   const [previewSkill, setPreviewSkill] = useState<CachedSkillInfo | null>(null);
@@ -110,20 +122,31 @@ export default function MarketplaceDetail() {
     setShowTargetToolsDialog(true);
   };
 
-  // 确认安装到选中的工具
-  const handleConfirmInstall = async (targetTools: string[], gitAuth?: GitAuthInput) => {
+  // 确认安装到选中的工具（默认拦截高风险脚本，ignoreSecurity 为用户确认后重试）
+  // collisions: 与其他来源的 Skill 重名时用户选择的处理方式
+  const handleConfirmInstall = async (
+    targetTools: string[],
+    gitAuth?: GitAuthInput,
+    ignoreSecurity = false,
+    collisions?: SkillCollisionResolutions
+  ) => {
     if (!sourceId || pendingInstallSkills.length === 0) return;
 
     setPendingTargetTools(targetTools);
     setInstalling(true);
     try {
-      await installSkillFromMarketplace(
+      await trackInstall((operationId) => installSkillFromMarketplace(
         sourceId,
         pendingInstallSkills,
         targetTools,
         currentWorkspace?.path ?? null,
-        gitAuth
-      );
+        gitAuth,
+        null,
+        undefined,
+        ignoreSecurity ? null : 'high',
+        collisions,
+        operationId
+      ));
       toast.success(`成功安装 ${pendingInstallSkills.length} 个 Skill(s) 到 ${targetTools.length} 个工具`);
       setSelectedSkills(new Set());
       setPendingInstallSkills([]);
@@ -141,6 +164,26 @@ export default function MarketplaceDetail() {
         return;
       }
 
+      const conflicts = parseSkillConflict(err);
+      if (conflicts) {
+        setSkillConflicts({ conflicts, targetTools, gitAuth, ignoreSecurity });
+        return;
+      }
+
+      const blocked = parseSecurityBlocked(err);
+      if (blocked) {
+        const findings = blocked.flatMap(r => r.findings.map(f => `${r.skillName}/${formatFindingLocation(f)} ${f.message}`));
+        toast.error(`安全扫描发现高风险脚本，已取消安装：${blocked.map(r => r.skillName).join(', ')}`, {
+          description: findings.slice(0, 3).join('\n'),
+          duration: 15000,
+          action: {
+            label: '仍然安装',
+            onClick: () => handleConfirmInstall(targetTools, gitAuth, true, collisions),
+          },
+        });
+        return;
+      }
+
       const message = err instanceof Error ? err.message : '安装失败';
       toast.error(message);
     } finally {
@@ -207,7 +250,8 @@ export default function MarketplaceDetail() {
         (skill) =>
           skill.name.toLowerCase().includes(query) ||
           skill.description?.toLowerCase().includes(query) ||
-          skill.tags.some((tag) => tag.toLowerCase().includes(query))
+          skill.tags.some((tag) => tag.toLowerCase().includes(query)) ||
+          skill.allowedTools.some((tool) => tool.toLowerCase().includes(query))
       );
     }
 
@@ -274,6 +318,8 @@ export default function MarketplaceDetail() {
           </div>
         </div>
 
+        {installing && <OperationProgressBar progress={installProgress} />}
+
         {/* 搜索和筛选 */}
         <div className="flex gap-4">
           <div className="flex-1 relative">
@@ -458,6 +504,18 @@ export default function MarketplaceDetail() {
         loading={authDialogLoading}
         onConfirm={handleAuthConfirm}
       />
+
+      <SkillConflictDialog
+        conflicts={skillConflicts?.conflicts ?? null}
+        onCancel={() => setSkillConflicts(null)}
+        onConfirm={(resolutions) => {
+          const retry = skillConflicts;
+          setSkillConflicts(null);
+          if (retry) {
+            handleConfirmInstall(retry.targetTools, retry.gitAuth, retry.ignoreSecurity, resolutions);
+          }
+        }}
+      />
     </div>
   );
 }
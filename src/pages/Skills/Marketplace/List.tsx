@@ -56,6 +56,8 @@ import type { MarketplaceSource, AddMarketplaceParams } from '@/types/marketplac
 import type { GitAuthChallenge, GitAuthInput } from '@/types/skills';
 import { parseGitAuthChallenge } from '@/services/skills';
 import { GitAuthDialog } from '@/components/skills/GitAuthDialog';
+import { OperationProgressBar } from '@/components/common/OperationProgressBar';
+import { createOperationId, listenOperationProgress, type OperationProgress } from '@/services/progress';
 
 export default function MarketplaceList() {
   const navigate = useNavigate();
@@ -88,6 +90,8 @@ export default function MarketplaceList() {
   const [authChallenge, setAuthChallenge] = useState<GitAuthChallenge | null>(null);
   const [authDialogLoading, setAuthDialogLoading] = useState(false);
   const [pendingAuthSourceId, setPendingAuthSourceId] = useState<string | null>(null);
+  // 正在刷新的市场源的克隆、扫描进度
+  const [sourceProgress, setSourceProgress] = useState<Record<string, OperationProgress>>({});
 
   // 加载市场源列表
   const loadSources = async () => {
@@ -188,8 +192,12 @@ export default function MarketplaceList() {
   // 刷新单个市场源
   const handleRefreshSource = async (source: MarketplaceSource, gitAuth?: GitAuthInput) => {
     setRefreshingSources(new Set([...refreshingSources, source.id]));
+    const operationId = createOperationId();
+    const unlisten = await listenOperationProgress(operationId, (progress) =>
+      setSourceProgress((prev) => ({ ...prev, [source.id]: progress }))
+    );
     try {
-      await refreshMarketplace(source.id, gitAuth);
+      await refreshMarketplace(source.id, gitAuth, operationId);
       toast.success(`市场源 ${source.name} 刷新成功`);
       setAuthDialogOpen(false);
       setAuthChallenge(null);
@@ -207,6 +215,12 @@ export default function MarketplaceList() {
       const message = err instanceof Error ? err.message : '刷新失败';
       toast.error(message);
     } finally {
+      unlisten();
+      setSourceProgress((prev) => {
+        const next = { ...prev };
+        delete next[source.id];
+        return next;
+      });
       setRefreshingSources(
         new Set([...refreshingSources].filter((id) => id !== source.id))
       );
@@ -394,6 +408,7 @@ export default function MarketplaceList() {
                       </span>
                     </div>
                   )}
+                  {sourceProgress[source.id] && <OperationProgressBar progress={sourceProgress[source.id]} />}
                   <div className="flex items-center gap-1.5 pt-3 border-t mt-3">
                     <Button
                       variant="ghost"
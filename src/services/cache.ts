@@ -11,6 +11,7 @@ export interface CacheInfo {
   conversations: CacheItem;
   backups: CacheItem;
   marketplace: CacheItem;
+  git: CacheItem;
 }
 
 export interface ClearCacheResult {
@@ -6,7 +6,7 @@ import type {
   AddMarketplaceParams,
   UpdateMarketplaceParams,
 } from '@/types/marketplace';
-import type { GitAuthInput } from '@/types/skills';
+import type { GitAuthInput, SkillCollisionResolutions, SkillInstallMode, SecuritySeverity } from '@/types/skills';
 
 /**
  * 列出所有市场源
@@ -46,8 +46,12 @@ export async function updateMarketplace(params: UpdateMarketplaceParams): Promis
 /**
  * 刷新单个市场源
  */
-export async function refreshMarketplace(sourceId: string, gitAuth?: GitAuthInput): Promise<MarketplaceCache> {
-  return invoke('refresh_marketplace', { sourceId, gitAuth: gitAuth ?? null });
+export async function refreshMarketplace(
+  sourceId: string,
+  gitAuth?: GitAuthInput,
+  operationId?: string | null
+): Promise<MarketplaceCache> {
+  return invoke('refresh_marketplace', { sourceId, gitAuth: gitAuth ?? null, operationId: operationId ?? null });
 }
 
 /**
@@ -72,13 +76,23 @@ export async function installSkillFromMarketplace(
   skillNames: string[],
   targetTools?: string[],
   workspacePath?: string | null,
-  gitAuth?: GitAuthInput
+  gitAuth?: GitAuthInput,
+  gitRef?: string | null,
+  installMode?: SkillInstallMode,
+  securityThreshold?: SecuritySeverity | null,
+  collisions?: SkillCollisionResolutions | null,
+  operationId?: string | null
 ): Promise<string> {
   return invoke('install_skill_from_marketplace', {
     sourceId,
     skillNames,
+    gitRef: gitRef || null,
     targetTools,
     workspacePath: workspacePath ?? null,
     gitAuth: gitAuth ?? null,
+    installMode: installMode ?? null,
+    securityThreshold: securityThreshold ?? null,
+    collisions: collisions ?? null,
+    operationId: operationId ?? null,
   });
 }
@@ -0,0 +1,34 @@
+// 长时间操作的进度事件
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type ProgressPhase = 'clone' | 'scan' | 'install' | 'done';
+
+export interface OperationProgress {
+  operationId: string;
+  phase: ProgressPhase;
+  percent: number | null;   // 当前阶段的百分比，无法估计时为空
+  message: string;
+  skill: string | null;     // 正在安装的 Skill
+  bytes: number | null;     // git 已接收的字节数
+}
+
+/**
+ * 生成操作 ID，传给支持进度的命令（operationId 参数）
+ */
+export function createOperationId(): string {
+  return crypto.randomUUID();
+}
+
+/**
+ * 监听指定操作的进度事件
+ */
+export async function listenOperationProgress(
+  operationId: string,
+  onProgress: (progress: OperationProgress) => void
+): Promise<UnlistenFn> {
+  return listen<OperationProgress>('operation-progress', (event) => {
+    if (event.payload.operationId === operationId) {
+      onProgress(event.payload);
+    }
+  });
+}
@@ -1,5 +1,6 @@
 // Skills 服务 - 封装 Tauri 后端调用
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import type {
   SkillRegistryEntry,
   SkillDetail,
@@ -8,9 +9,36 @@ import type {
   AiToolType,
   GitAuthInput,
   GitAuthChallenge,
+  SkillLockfile,
+  SkillUpdateOutcome,
+  SkillConflictStrategy,
+  SkillVersionEntry,
+  SkillToolCopy,
+  AiToolInfo,
+  AiToolDefinition,
+  SkillInstallMode,
+  SkillLintReport,
+  SkillFrontmatterUpdate,
+  SecuritySeverity,
+  SkillSecurityReport,
+  SkillCollision,
+  SkillCollisionResolutions,
+  SkillMetadata,
+  SkillSignatureInfo,
+  SkillTrustStore,
+  SkillExportResult,
+  SkillBundle,
+  InstalledSkillBundle,
+  SkillBundleResult,
+  SkillBundleDiff,
+  TrustedKey,
+  SignaturePolicy,
 } from '@/types/skills';
 
 const AUTH_REQUIRED_PREFIX = 'COBALT_AUTH_REQUIRED:';
+const SECURITY_BLOCKED_PREFIX = 'COBALT_SECURITY_BLOCKED:';
+const DEPENDENTS_WARNING_PREFIX = 'COBALT_DEPENDENTS_WARNING:';
+const SKILL_CONFLICT_PREFIX = 'COBALT_SKILL_CONFLICT:';
 
 /**
  * 解析格式化的 skill 字符串
@@ -47,6 +75,62 @@ export function parseGitAuthChallenge(error: unknown): GitAuthChallenge | null {
   }
 }
 
+/**
+ * 解析安全扫描拦截安装或更新时返回的报告
+ */
+export function parseSecurityBlocked(error: unknown): SkillSecurityReport[] | null {
+  const message = typeof error === 'string' ? error : (error instanceof Error ? error.message : '');
+  const idx = message.indexOf(SECURITY_BLOCKED_PREFIX);
+  if (idx === -1) return null;
+
+  const payload = message.slice(idx + SECURITY_BLOCKED_PREFIX.length).trim();
+  try {
+    return JSON.parse(payload) as SkillSecurityReport[];
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * 解析卸载或禁用时返回的依赖警告，返回依赖该 Skill 的其他 Skill
+ */
+export function parseDependentsWarning(error: unknown): string[] | null {
+  const message = typeof error === 'string' ? error : (error instanceof Error ? error.message : '');
+  const idx = message.indexOf(DEPENDENTS_WARNING_PREFIX);
+  if (idx === -1) return null;
+
+  const payload = message.slice(idx + DEPENDENTS_WARNING_PREFIX.length).trim();
+  try {
+    return JSON.parse(payload) as string[];
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * 解析安装时返回的重名信息（已安装来自其他来源的同名 Skill）
+ */
+export function parseSkillConflict(error: unknown): SkillCollision[] | null {
+  const message = typeof error === 'string' ? error : (error instanceof Error ? error.message : '');
+  const idx = message.indexOf(SKILL_CONFLICT_PREFIX);
+  if (idx === -1) return null;
+
+  const payload = message.slice(idx + SKILL_CONFLICT_PREFIX.length).trim();
+  try {
+    return JSON.parse(payload) as SkillCollision[];
+  } catch {
+    return null;
+  }
+}
+
+// 后端返回的 Skill metadata（frontmatter 字段与前端类型一致）
+type BackendSkillMetadata = Omit<Partial<SkillMetadata>, 'name' | 'version' | 'description' | 'targetTools'> & {
+  name: string;
+  version?: string;
+  description?: string;
+  targetTools?: string[];
+};
+
 // 后端返回的 Skill 注册表条目类型
 interface BackendSkillEntry {
   id: string;
@@ -55,16 +139,9 @@ interface BackendSkillEntry {
   enabled: boolean;
   installedBy?: string[];
   installedAt?: string;
-  metadata?: {
-    name: string;
-    version?: string;
-    description?: string;
-    tags?: string[];
-    targetTools?: string[];
-    repository?: string;
-    sourceId?: string;
-    commitHash?: string;
-  };
+  toolCopies?: SkillToolCopy[];
+  bundles?: string[];
+  metadata?: BackendSkillMetadata;
 }
 
 // 后端返回的 Skill 详情类型
@@ -75,16 +152,7 @@ interface BackendSkillDetail {
   enabled: boolean;
   installedBy?: string[];
   content: string;
-  metadata?: {
-    name: string;
-    version?: string;
-    description?: string;
-    tags?: string[];
-    targetTools?: string[];
-    repository?: string;
-    sourceId?: string;
-    commitHash?: string;
-  };
+  metadata?: BackendSkillMetadata;
   files: string[];
 }
 
@@ -101,6 +169,7 @@ function toSkillRegistryEntry(entry: BackendSkillEntry): SkillRegistryEntry {
     installedBy: entry.installedBy as AiToolType[],
     url: entry.metadata?.repository,
     metadata: {
+      ...entry.metadata,
       name: entry.metadata?.name || entry.name,
       version: entry.metadata?.version || '0.0.0',
       description: entry.metadata?.description || entry.description || '',
@@ -108,7 +177,11 @@ function toSkillRegistryEntry(entry: BackendSkillEntry): SkillRegistryEntry {
       targetTools: entry.metadata?.targetTools as AiToolType[],
       repository: entry.metadata?.repository,
       sourceId: entry.metadata?.sourceId,
+      commitHash: entry.metadata?.commitHash,
+      gitRef: entry.metadata?.gitRef,
     },
+    toolCopies: entry.toolCopies,
+    bundles: entry.bundles,
   };
 }
 
@@ -127,6 +200,7 @@ function toSkillDetail(detail: BackendSkillDetail): SkillDetail {
     content: detail.content,
     files: detail.files,
     metadata: {
+      ...detail.metadata,
       name: detail.metadata?.name || detail.name,
       version: detail.metadata?.version || '0.0.0',
       description: detail.metadata?.description || detail.description || '',
@@ -134,6 +208,8 @@ function toSkillDetail(detail: BackendSkillDetail): SkillDetail {
       targetTools: detail.metadata?.targetTools as AiToolType[],
       repository: detail.metadata?.repository,
       sourceId: detail.metadata?.sourceId,
+      commitHash: detail.metadata?.commitHash,
+      gitRef: detail.metadata?.gitRef,
     },
   };
 }
@@ -175,21 +251,22 @@ export async function getSkillDetail(skillName: string, workspacePath?: string |
 }
 
 /**
- * 启用/禁用 Skill
+ * 启用/禁用 Skill；禁用被其他 Skill 依赖的 Skill 时返回依赖警告，force 为 true 时仍然禁用
  */
 export async function toggleSkill(
   skillName: string,
   enabled: boolean,
-  workspacePath?: string | null
+  workspacePath?: string | null,
+  force = false
 ): Promise<void> {
-  await invoke('toggle_skill', { skillName, enabled, workspacePath: workspacePath ?? null });
+  await invoke('toggle_skill', { skillName, enabled, workspacePath: workspacePath ?? null, force });
 }
 
 /**
- * 卸载 Skill
+ * 卸载 Skill；被其他 Skill 依赖时返回依赖警告，force 为 true 时仍然卸载
  */
-export async function uninstallSkill(skillName: string, workspacePath?: string | null): Promise<void> {
-  await invoke('uninstall_skill', { skillName, workspacePath: workspacePath ?? null });
+export async function uninstallSkill(skillName: string, workspacePath?: string | null, force = false): Promise<void> {
+  await invoke('uninstall_skill', { skillName, workspacePath: workspacePath ?? null, force });
 }
 
 /**
@@ -215,11 +292,15 @@ export async function readSkillFile(
  */
 export async function scanRepoSkills(
   repoUrl: string,
-  gitAuth?: GitAuthInput
+  gitAuth?: GitAuthInput,
+  gitRef?: string | null,
+  operationId?: string | null
 ): Promise<import('@/types/skills').ScannedSkillInfo[]> {
   return invoke<import('@/types/skills').ScannedSkillInfo[]>('scan_repo_skills', {
     repoUrl,
-    gitAuth: gitAuth ?? null
+    gitRef: gitRef || null,
+    gitAuth: gitAuth ?? null,
+    operationId: operationId ?? null,
   });
 }
 
@@ -231,14 +312,56 @@ export async function installSkillFromRepo(
   skillNames?: string[],
   targetTools?: string[],
   workspacePath?: string | null,
-  gitAuth?: GitAuthInput
+  gitAuth?: GitAuthInput,
+  gitRef?: string | null,
+  installMode?: SkillInstallMode,
+  securityThreshold?: SecuritySeverity | null,
+  collisions?: SkillCollisionResolutions | null,
+  operationId?: string | null
 ): Promise<string> {
   return invoke<string>('install_skill_from_repo', {
     repoUrl,
+    gitRef: gitRef || null,
     skillNames,
     targetTools: targetTools || null,
     workspacePath: workspacePath ?? null,
     gitAuth: gitAuth ?? null,
+    installMode: installMode ?? null,
+    securityThreshold: securityThreshold ?? null,
+    collisions: collisions ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+/**
+ * 扫描本地目录或 .zip / .tar.gz / .tgz / .tar 归档中的 Skills
+ */
+export async function scanLocalSkills(
+  path: string
+): Promise<import('@/types/skills').ScannedSkillInfo[]> {
+  return invoke<import('@/types/skills').ScannedSkillInfo[]>('scan_local_skills', { path });
+}
+
+/**
+ * 从本地目录或归档安装 Skill（来源记录为 file:// 地址，可检查更新）
+ */
+export async function installSkillFromLocal(
+  path: string,
+  skillNames?: string[],
+  targetTools?: string[],
+  workspacePath?: string | null,
+  installMode?: SkillInstallMode,
+  securityThreshold?: SecuritySeverity | null,
+  collisions?: SkillCollisionResolutions | null
+): Promise<string> {
+  return invoke<string>('install_skill_from_local', {
+    path,
+    skillNames,
+    targetTools: targetTools || null,
+    workspacePath: workspacePath ?? null,
+    installMode: installMode ?? null,
+    securityThreshold: securityThreshold ?? null,
+    collisions: collisions ?? null,
   });
 }
 
@@ -264,35 +387,85 @@ export async function createSkill(params: CreateSkillParams): Promise<string> {
 /**
  * 检查 Skill 是否有更新
  */
-export async function checkSkillUpdate(skillName: string, workspacePath?: string | null): Promise<SkillUpdateCheckResult> {
-  return invoke<SkillUpdateCheckResult>('check_skill_update', { skillName, workspacePath: workspacePath ?? null });
+export async function checkSkillUpdate(
+  skillName: string,
+  workspacePath?: string | null,
+  gitAuth?: GitAuthInput
+): Promise<SkillUpdateCheckResult> {
+  return invoke<SkillUpdateCheckResult>('check_skill_update', {
+    skillName,
+    workspacePath: workspacePath ?? null,
+    gitAuth: gitAuth ?? null,
+  });
+}
+
+export interface CheckAllSkillUpdatesOptions {
+  concurrency?: number;   // 同时检查的仓库数，默认 4
+  timeoutSecs?: number;   // 单个 skill 的超时时间，默认 120 秒
+  onChecked?: (summary: SkillUpdateSummary) => void;  // 每检查完一个 Skill 回调一次
 }
 
 /**
  * 批量检查所有 Skill 是否有更新
+ * 多个仓库并行检查，通过 onChecked 逐个获取结果；命中缓存时直接返回，不触发回调
  */
 export async function checkAllSkillUpdates(
   workspacePath?: string | null,
-  force = false
+  force = false,
+  options: CheckAllSkillUpdatesOptions = {}
 ): Promise<SkillUpdateSummary[]> {
-  return invoke<SkillUpdateSummary[]>('check_all_skill_updates', {
-    workspacePath: workspacePath ?? null,
-    force,
-  });
+  const { onChecked } = options;
+  const unlisten = onChecked
+    ? await listen<SkillUpdateSummary>('skill-update-checked', (event) => onChecked(event.payload))
+    : null;
+
+  try {
+    return await invoke<SkillUpdateSummary[]>('check_all_skill_updates', {
+      workspacePath: workspacePath ?? null,
+      force,
+      concurrency: options.concurrency ?? null,
+      timeoutSecs: options.timeoutSecs ?? null,
+    });
+  } finally {
+    unlisten?.();
+  }
 }
 
 /**
  * 更新 Skill 到最新版本
+ * @param securityThreshold 上游版本的安全扫描达到该级别时拒绝更新，null 表示不拦截
  */
-export async function updateSkill(skillName: string, workspacePath?: string | null): Promise<string> {
-  return invoke<string>('update_skill', { skillName, workspacePath: workspacePath ?? null });
+export async function updateSkill(
+  skillName: string,
+  workspacePath?: string | null,
+  onConflict?: SkillConflictStrategy,
+  securityThreshold?: SecuritySeverity | null,
+  gitAuth?: GitAuthInput
+): Promise<SkillUpdateOutcome> {
+  return invoke<SkillUpdateOutcome>('update_skill', {
+    skillName,
+    workspacePath: workspacePath ?? null,
+    onConflict: onConflict ?? null,
+    securityThreshold: securityThreshold ?? null,
+    gitAuth: gitAuth ?? null,
+  });
 }
 
 /**
  * 设置 Skill 的仓库地址
  */
-export async function setSkillRepository(skillName: string, repository: string, workspacePath?: string | null): Promise<void> {
-  return invoke<void>('set_skill_repository', { skillName, repository, workspacePath: workspacePath ?? null });
+export async function setSkillRepository(
+  skillName: string,
+  repository: string,
+  workspacePath?: string | null,
+  gitRef?: string | null
+): Promise<void> {
+  return invoke<void>('set_skill_repository', {
+    skillName,
+    repository,
+    gitRef: gitRef || null,
+    workspacePath: workspacePath ?? null,
+  });
 }
 
 /**
@@ -315,3 +488,214 @@ export async function removeSkillFromTools(skillName: string, tools: string[], w
 export async function getSupportedAiTools(): Promise<import('@/types/skills').AiToolInfo[]> {
   return invoke<import('@/types/skills').AiToolInfo[]>('get_supported_ai_tools');
 }
+
+/**
+ * 读取 skills.lock 锁文件
+ */
+export async function readSkillLockfile(workspacePath?: string | null): Promise<SkillLockfile> {
+  return invoke<SkillLockfile>('read_skill_lockfile', { workspacePath: workspacePath ?? null });
+}
+
+/**
+ * 按 skills.lock 重新安装所有 Skills
+ */
+export async function installSkillsFromLockfile(
+  workspacePath?: string | null,
+  gitAuth?: GitAuthInput
+): Promise<string> {
+  return invoke<string>('install_skills_from_lockfile', {
+    workspacePath: workspacePath ?? null,
+    gitAuth: gitAuth ?? null,
+  });
+}
+
+/**
+ * 列出 Skill 的历史版本（最新的在前）
+ */
+export async function listSkillVersions(
+  skillName: string,
+  workspacePath?: string | null
+): Promise<SkillVersionEntry[]> {
+  return invoke<SkillVersionEntry[]>('list_skill_versions', {
+    skillName,
+    workspacePath: workspacePath ?? null,
+  });
+}
+
+/**
+ * 将 Skill 回滚到指定的历史版本
+ */
+export async function rollbackSkill(
+  skillName: string,
+  versionId: string,
+  workspacePath?: string | null
+): Promise<string> {
+  return invoke<string>('rollback_skill', {
+    skillName,
+    versionId,
+    workspacePath: workspacePath ?? null,
+  });
+}
+
+/**
+ * 修改 Skill 的 frontmatter 字段，同步到所有工具副本和注册表
+ */
+export async function updateSkillFrontmatter(
+  skillName: string,
+  update: SkillFrontmatterUpdate,
+  workspacePath?: string | null
+): Promise<string> {
+  return invoke<string>('update_skill_frontmatter', {
+    skillName,
+    update,
+    workspacePath: workspacePath ?? null,
+  });
+}
+
+/**
+ * 检查 Skill 是否符合规范（frontmatter、文件引用、文件大小、重名）
+ */
+export async function lintSkill(skillName: string, workspacePath?: string | null): Promise<SkillLintReport> {
+  return invoke<SkillLintReport>('lint_skill', {
+    skillName,
+    workspacePath: workspacePath ?? null,
+  });
+}
+
+/**
+ * 新增或更新自定义 AI 工具
+ */
+export async function saveCustomAiTool(tool: AiToolDefinition): Promise<AiToolInfo> {
+  return invoke<AiToolInfo>('save_custom_ai_tool', { tool });
+}
+
+/**
+ * 删除自定义 AI 工具（内置工具会恢复默认配置）
+ */
+export async function removeCustomAiTool(toolId: string): Promise<void> {
+  return invoke<void>('remove_custom_ai_tool', { toolId });
+}
+
+/**
+ * 获取信任的发布者公钥和签名策略
+ */
+export async function getSkillTrustStore(): Promise<SkillTrustStore> {
+  return invoke<SkillTrustStore>('get_skill_trust_store');
+}
+
+/**
+ * 添加信任的发布者公钥（minisign.pub 内容或 base64 公钥）
+ */
+export async function addTrustedKey(name: string, publicKey: string): Promise<TrustedKey> {
+  return invoke<TrustedKey>('add_trusted_key', { name, publicKey });
+}
+
+/**
+ * 移除信任的公钥
+ */
+export async function removeTrustedKey(keyId: string): Promise<void> {
+  return invoke('remove_trusted_key', { keyId });
+}
+
+/**
+ * 修改签名策略
+ */
+export async function updateSignaturePolicy(policy: SignaturePolicy): Promise<void> {
+  return invoke('update_signature_policy', { policy });
+}
+
+/**
+ * 为 skill 目录生成待签名的清单，返回清单路径
+ */
+export async function generateSigningManifest(skillPath: string): Promise<string> {
+  return invoke<string>('generate_signing_manifest', { skillPath });
+}
+
+/**
+ * 重新验证已安装 Skill 的签名
+ */
+export async function verifySkillSignature(
+  skillName: string,
+  workspacePath?: string | null
+): Promise<SkillSignatureInfo> {
+  return invoke<SkillSignatureInfo>('verify_skill_signature', { skillName, workspacePath: workspacePath ?? null });
+}
+
+/**
+ * 读取并校验套件 YAML 文件
+ */
+export async function readSkillBundle(path: string): Promise<SkillBundle> {
+  return invoke<SkillBundle>('read_skill_bundle', { path });
+}
+
+/**
+ * 列出已安装的套件
+ */
+export async function listSkillBundles(workspacePath?: string | null): Promise<InstalledSkillBundle[]> {
+  return invoke<InstalledSkillBundle[]>('list_skill_bundles', { workspacePath: workspacePath ?? null });
+}
+
+/**
+ * 安装套件中的全部 Skills
+ */
+export async function installSkillBundle(
+  path: string,
+  workspacePath?: string | null,
+  installMode?: SkillInstallMode,
+  securityThreshold?: SecuritySeverity | null
+): Promise<SkillBundleResult> {
+  return invoke<SkillBundleResult>('install_skill_bundle', {
+    path,
+    workspacePath: workspacePath ?? null,
+    installMode: installMode ?? null,
+    securityThreshold: securityThreshold ?? null,
+  });
+}
+
+/**
+ * 对比套件定义与本地已安装的 Skills（会检查远程更新）
+ */
+export async function diffSkillBundle(name: string, workspacePath?: string | null): Promise<SkillBundleDiff> {
+  return invoke<SkillBundleDiff>('diff_skill_bundle', { name, workspacePath: workspacePath ?? null });
+}
+
+/**
+ * 按套件定义同步：安装新增、更新已有、卸载已移出套件的 Skills
+ */
+export async function updateSkillBundle(
+  name: string,
+  workspacePath?: string | null,
+  onConflict?: SkillConflictStrategy
+): Promise<SkillBundleResult> {
+  return invoke<SkillBundleResult>('update_skill_bundle', {
+    name,
+    workspacePath: workspacePath ?? null,
+    onConflict: onConflict ?? null,
+  });
+}
+
+/**
+ * 移除套件；keepSkills 为 true 时保留已安装的 Skills
+ */
+export async function removeSkillBundle(
+  name: string,
+  workspacePath?: string | null,
+  keepSkills = false
+): Promise<SkillBundleResult> {
+  return invoke<SkillBundleResult>('remove_skill_bundle', { name, workspacePath: workspacePath ?? null, keepSkills });
+}
+
+/**
+ * 导出 Skills 为 .zip / .tar.gz 归档（多个 Skill 打包到同一归档），可通过本地安装重新导入
+ */
+export async function exportSkills(
+  skillNames: string[],
+  outputPath: string,
+  workspacePath?: string | null
+): Promise<SkillExportResult> {
+  return invoke<SkillExportResult>('export_skill', {
+    skillNames,
+    outputPath,
+    workspacePath: workspacePath ?? null,
+  });
+}
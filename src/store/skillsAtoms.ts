@@ -21,6 +21,7 @@ export const skillsFilterAtom = atom<SkillFilter>({
   enabled: undefined,
   tags: [],
   updateOnly: false,
+  invocation: 'all',
 });
 
 // Skills 排序
@@ -108,7 +109,8 @@ export const filteredSkillsAtom = atom((get) => {
     filtered = filtered.filter(
       (skill) =>
         skill.name.toLowerCase().includes(searchLower) ||
-        skill.description.toLowerCase().includes(searchLower)
+        skill.description.toLowerCase().includes(searchLower) ||
+        skill.metadata.allowedTools?.some((tool) => tool.toLowerCase().includes(searchLower))
     );
   }
 
@@ -131,6 +133,13 @@ export const filteredSkillsAtom = atom((get) => {
     );
   }
 
+  // 调用方式过滤
+  if (filter.invocation === 'user') {
+    filtered = filtered.filter((skill) => skill.metadata.userInvocable !== false);
+  } else if (filter.invocation === 'model') {
+    filtered = filtered.filter((skill) => skill.metadata.disableModelInvocation !== true);
+  }
+
   // 仅看可更新
   if (filter.updateOnly) {
     filtered = filtered.filter((skill) => skillUpdates[skill.name]?.hasUpdate);
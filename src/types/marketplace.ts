@@ -1,5 +1,7 @@
 // Skill 市场类型定义
 
+import type { SkillRequirement } from './skills';
+
 export interface MarketplaceSource {
   id: string;
   name: string;
@@ -31,6 +33,16 @@ export interface CachedSkillInfo {
   description?: string;
   tags: string[];
   targetTools: string[];
+  allowedTools: string[];
+  userInvocable?: boolean | null;
+  disableModelInvocation?: boolean | null;
+  argumentHint?: string | null;
+  model?: string | null;
+  license?: string | null;
+  /** 依赖的其他 Skill */
+  requires?: SkillRequirement[];
+  /** 未识别的 frontmatter 字段 */
+  extraFrontmatter?: Record<string, unknown>;
   installed: boolean;
   installedVersion?: string;
   hasUpdate: boolean;
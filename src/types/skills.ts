@@ -1,9 +1,14 @@
 // Skills 相关类型定义
 
 /**
- * 支持的 AI 工具类型
+ * 内置的 AI 工具类型
  */
-export type AiToolType = 'claude-code' | 'cursor' | 'codex' | 'opencode' | 'antigravity' | 'droid' | 'copilot';
+export type BuiltinAiToolType = 'claude-code' | 'cursor' | 'codex' | 'opencode' | 'antigravity' | 'droid' | 'copilot';
+
+/**
+ * 支持的 AI 工具类型（可在 ~/.cobalt/ai-tools.json 中添加自定义工具）
+ */
+export type AiToolType = BuiltinAiToolType | (string & {});
 
 /**
  * AI 工具元信息
@@ -15,6 +20,14 @@ export interface AiToolMeta {
   icon: string;
 }
 
+/**
+ * Skill 在工具目录中的格式
+ * - claude: 原样复制 SKILL.md
+ * - cursor-mdc: 额外生成 Cursor 规则文件 .cursor/rules/<skill>.mdc
+ * - agents-md: 额外生成 AGENTS.md 风格的说明文件
+ */
+export type SkillFormat = 'claude' | 'cursor-mdc' | 'agents-md';
+
 /**
  * 从后端获取的 AI 工具信息
  */
@@ -24,12 +37,40 @@ export interface AiToolInfo {
   displayName: string;
   icon: string;
   relativePath: string;  // 相对路径，如 ".claude/skills/"
+  workspaceRelativePath: string;  // 工作区内的相对路径
+  builtin: boolean;
+  /** 应用 Skill 时使用的格式 */
+  format: SkillFormat;
+  /** 本机是否安装了该工具（找到可执行文件或配置目录） */
+  installed: boolean;
+  configDirExists: boolean;
+  binaryPath: string | null;
+  version: string | null;
+}
+
+/**
+ * 自定义 AI 工具定义（保存到 ~/.cobalt/ai-tools.json）
+ */
+export interface AiToolDefinition {
+  id: string;
+  displayName: string;
+  icon?: string;
+  /** 全局 skills 目录：相对用户主目录，也支持 ~/ 开头或绝对路径 */
+  globalPath: string;
+  /** 工作区 skills 目录：相对工作区根目录，为空时与 globalPath 相同 */
+  workspacePath?: string;
+  /** 命令行可执行文件名，用于检测工具是否安装 */
+  binary?: string;
+  /** 配置目录：相对用户主目录，为空时取 globalPath 的上级目录 */
+  configDir?: string;
+  /** Skill 格式，默认 claude */
+  format?: SkillFormat;
 }
 
 /**
  * AI 工具元数据映射
  */
-export const AI_TOOL_META: Record<AiToolType, AiToolMeta> = {
+export const AI_TOOL_META: Record<BuiltinAiToolType, AiToolMeta> = {
   'claude-code': {
     id: 'claude-code',
     name: 'claude-code',
@@ -74,9 +115,39 @@ export const AI_TOOL_META: Record<AiToolType, AiToolMeta> = {
   },
 };
 
+/**
+ * 获取 AI 工具元信息，自定义工具没有内置元信息时使用 id 作为名称
+ */
+export function getAiToolMeta(toolId: AiToolType): AiToolMeta {
+  return AI_TOOL_META[toolId as BuiltinAiToolType] ?? {
+    id: toolId,
+    name: toolId,
+    displayName: toolId,
+    icon: '🧩',
+  };
+}
+
+/**
+ * 默认安装目标：本机已安装的工具，都未检测到时使用 claude-code
+ */
+export function getDefaultTargetTools(tools: AiToolInfo[]): string[] {
+  const installed = tools.filter(tool => tool.installed).map(tool => tool.id);
+  return installed.length > 0 ? installed : ['claude-code'];
+}
+
 /**
  * Skill 元数据
  */
+/**
+ * Skill 依赖：未指定 repo / source 时从同一来源或已添加的市场源中查找
+ */
+export interface SkillRequirement {
+  name: string;
+  repo?: string;
+  ref?: string;
+  source?: string;  // 市场源 ID
+}
+
 export interface SkillMetadata {
   name: string;
   version: string;
@@ -86,6 +157,18 @@ export interface SkillMetadata {
   targetTools?: AiToolType[];  // 该 Skill 适用的 AI 工具
   repository?: string;  // 仓库 URL
   sourceId?: string;  // 市场源 ID
+  commitHash?: string;  // 安装时的提交 SHA
+  gitRef?: string;  // 安装时指定的分支、标签或提交
+  upstreamName?: string;  // 以别名安装时来源仓库中的原名称
+  allowedTools?: string[];  // Claude Code 权限配置，允许 Skill 使用的工具
+  userInvocable?: boolean | null;  // 是否可以通过 /skill-name 手动调用
+  disableModelInvocation?: boolean | null;  // 是否禁止模型自动调用
+  argumentHint?: string | null;
+  model?: string | null;
+  license?: string | null;
+  requires?: SkillRequirement[];  // 依赖的其他 Skill（frontmatter 的 requires 字段）
+  extraFrontmatter?: Record<string, unknown>;  // 未识别的 frontmatter 字段
+  signature?: SkillSignatureInfo | null;  // 安装或更新时的签名验证结果
   createdAt?: string;
   updatedAt?: string;
 }
@@ -102,8 +185,33 @@ export interface SkillRegistryEntry {
   path?: string;
   url?: string;
   metadata: SkillMetadata;
+  /** 各工具中的副本及内容是否一致 */
+  toolCopies?: SkillToolCopy[];
+  /** 所属的套件 */
+  bundles?: string[];
+}
+
+/**
+ * Skill 在单个工具中的副本
+ */
+export interface SkillToolCopy {
+  tool: AiToolType;
+  path: string;
+  /** 副本当前内容的清单 hash */
+  manifestHash: string;
+  /** 是否与其他副本不一致 */
+  isDivergent: boolean;
+  /** 是否为指向规范副本的链接 */
+  linked: boolean;
 }
 
+/**
+ * 安装模式
+ * - copy: 每个工具目录一份独立副本
+ * - link: 一份规范副本（~/.cobalt/skills/store），工具目录为符号链接
+ */
+export type SkillInstallMode = 'copy' | 'link';
+
 /**
  * Skill 详情
  */
@@ -134,6 +242,12 @@ export interface SkillManifest {
   description?: string;
   /** 仓库 URL，用于更新检测 */
   repository?: string;
+  /** 生成清单时的提交 SHA */
+  commit?: string;
+  /** 安装时指定的分支、标签或提交 */
+  gitRef?: string;
+  /** 以别名安装时来源仓库中的原名称 */
+  upstreamName?: string;
   /** 文件列表，包含 hash 和 size */
   files: {
     path: string;
@@ -153,8 +267,14 @@ export interface SkillFilter {
   enabled?: boolean;
   tags?: string[];
   updateOnly?: boolean;
+  invocation?: SkillInvocationFilter;
 }
 
+/**
+ * 按调用方式过滤：可手动调用（user-invocable）或可被模型自动调用
+ */
+export type SkillInvocationFilter = 'all' | 'user' | 'model';
+
 /**
  * Skill 排序选项
  */
@@ -179,9 +299,39 @@ export interface SkillUpdateCheckResult {
   removedFiles?: string[];
   /** 落后于远端的工具 */
   outdatedTools?: string[];
+  /** 相对安装时清单有本地修改的文件 */
+  localModifiedFiles?: string[];
   error?: string;
 }
 
+/**
+ * 更新时的合并冲突
+ */
+export interface SkillMergeConflict {
+  path: string;
+  reason: 'content' | 'binary' | 'deleted-locally' | 'deleted-upstream' | 'added-both' | 'missing-base';
+}
+
+/**
+ * 冲突处理策略
+ */
+export type SkillConflictStrategy = 'abort' | 'keep-local' | 'take-upstream' | 'markers';
+
+/**
+ * Skill 更新结果
+ */
+export interface SkillUpdateOutcome {
+  /** 是否已应用更新（存在冲突且未指定处理策略时为 false） */
+  applied: boolean;
+  message: string;
+  localModifiedFiles: string[];
+  /** 自动合并了本地修改的文件 */
+  mergedFiles: string[];
+  /** 保留本地修改的文件 */
+  keptLocalFiles: string[];
+  conflicts: SkillMergeConflict[];
+}
+
 export interface SkillUpdateSummary extends SkillUpdateCheckResult {
   skillName: string;
   checkedAt: string;
@@ -207,6 +357,121 @@ export interface ScannedSkillInfo {
   description?: string;
   version?: string;
   alreadyInstalled: boolean;
+  security: SkillSecurityReport;
+  signature: SkillSignatureInfo;
+  requires?: SkillRequirement[];
+}
+
+// 脚本安全扫描的风险级别
+export type SecuritySeverity = 'low' | 'medium' | 'high' | 'critical';
+
+export interface SecurityFinding {
+  severity: SecuritySeverity;
+  rule: string;
+  message: string;
+  file: string;
+  line: number;
+  snippet: string;
+}
+
+export interface SkillSecurityReport {
+  skillName: string;
+  findings: SecurityFinding[];
+  maxSeverity?: SecuritySeverity | null;
+  scannedFiles: number;
+}
+
+// 与已安装的、来自其他来源的 Skill 重名
+export interface SkillCollision {
+  name: string;
+  existingRepository?: string | null;  // 手动放入的 Skill 没有来源
+  incomingRepository: string;
+  suggestedAlias: string;
+}
+
+// 重名的处理方式：跳过、替换已安装的 Skill，或以别名安装
+export type SkillCollisionResolution =
+  | { action: 'skip' }
+  | { action: 'replace' }
+  | { action: 'alias'; alias: string };
+
+// 按原名称指定的处理方式
+export type SkillCollisionResolutions = Record<string, SkillCollisionResolution>;
+
+// 签名验证状态
+export type SignatureStatus = 'verified' | 'unsigned' | 'untrusted-key' | 'invalid';
+
+export interface SkillSignatureInfo {
+  status: SignatureStatus;
+  keyId?: string | null;  // 签名使用的公钥 ID
+  publisher?: string | null;  // 信任列表中该公钥对应的发布者
+  message?: string | null;
+  checkedAt: string;
+}
+
+// 信任的发布者公钥（minisign）
+export interface TrustedKey {
+  keyId: string;
+  name: string;
+  publicKey: string;
+  addedAt: string;
+}
+
+export interface SignaturePolicy {
+  requireSignatures: boolean;  // 非官方来源的 Skill 必须签名
+  officialSources: string[];  // 不要求签名的官方仓库
+}
+
+export interface SkillTrustStore {
+  keys: TrustedKey[];
+  policy: SignaturePolicy;
+}
+
+export interface SkillBundleSource {
+  repo: string;  // Git 仓库地址，或 file:// 开头的本地目录 / 归档
+  ref?: string;
+  skills?: string[];  // 为空表示仓库中的全部 skill
+  targetTools?: string[];
+}
+
+export interface SkillBundle {
+  name: string;
+  description?: string;
+  targetTools?: string[];
+  skills: SkillBundleSource[];
+}
+
+export interface InstalledSkillBundle {
+  definition: SkillBundle;
+  sourcePath?: string;  // 套件文件路径，更新时重新读取
+  skills: string[];
+  installedAt: string;
+  updatedAt?: string;
+}
+
+export interface SkillBundleResult {
+  bundleName: string;
+  installed: string[];
+  updated: string[];
+  removed: string[];
+  unchanged: string[];
+  failed: string[];
+}
+
+export interface SkillBundleDiff {
+  bundleName: string;
+  missing: string[];  // 套件中有、本地未安装
+  extra: string[];  // 已从套件定义中移除
+  outdated: string[];
+  refChanged: string[];  // 仓库地址或 ref 与套件定义不一致
+  upToDate: string[];
+  errors: string[];
+}
+
+export interface SkillExportResult {
+  outputPath: string;
+  skills: string[];
+  fileCount: number;
 }
 
 export interface GitAuthInput {
@@ -221,3 +486,82 @@ export interface GitAuthChallenge {
   canUseHttps: boolean;
   canUseSsh: boolean;
 }
+
+/**
+ * skills.lock 中的 Skill 条目
+ */
+export interface SkillLockEntry {
+  name: string;
+  repository: string;
+  /** 安装时指定的分支、标签或提交 */
+  gitRef?: string;
+  /** 安装时的提交 SHA */
+  commit?: string;
+  /** Skill 在仓库中的相对路径，空字符串表示仓库根目录 */
+  subpath: string;
+  manifestHash: string;
+  targetTools: string[];
+  /** 以别名安装时来源仓库中的原名称 */
+  upstreamName?: string;
+}
+
+/**
+ * skills.lock 锁文件
+ */
+export interface SkillLockfile {
+  version: number;
+  skills: SkillLockEntry[];
+}
+
+/**
+ * Skill 历史版本
+ */
+export interface SkillVersionEntry {
+  id: string;
+  /** 快照目录 */
+  backupPath: string;
+  version?: string;
+  repository?: string;
+  commit?: string;
+  gitRef?: string;
+  /** 快照时的清单 */
+  manifest?: SkillManifest;
+  /** 产生快照的操作 */
+  reason: 'update' | 'rollback';
+  createdAt: string;
+}
+
+/**
+ * 修改 SKILL.md frontmatter 的参数
+ * 不传表示不修改，空字符串或空列表表示删除该字段
+ */
+export interface SkillFrontmatterUpdate {
+  description?: string;
+  tags?: string[];
+  aiTools?: string[];
+  allowedTools?: string[];
+}
+
+/** Skill 检查诊断级别 */
+export type SkillDiagnosticSeverity = 'error' | 'warning';
+
+/** Skill 检查的单条诊断 */
+export interface SkillDiagnostic {
+  severity: SkillDiagnosticSeverity;
+  /** 规则标识，如 invalid-name、broken-reference */
+  code: string;
+  message: string;
+  /** 相对 skill 目录的文件路径 */
+  file: string;
+  /** 行号（从 1 开始） */
+  line?: number | null;
+}
+
+/** Skill 检查报告 */
+export interface SkillLintReport {
+  skillName: string;
+  skillPath: string;
+  diagnostics: SkillDiagnostic[];
+  errorCount: number;
+  warningCount: number;
+}